@@ -0,0 +1,117 @@
+use std::{sync::Arc, time::Duration};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use crate::{
+    application::memo::history::SearchHistory,
+    domain::{
+        api_key::repository::ApiKeyRepository,
+        folder::repository::FolderRepository,
+        identity::repository::OAuthIdentityRepository,
+        memo::repository::MemoRepository,
+        session::repository::SessionRepository,
+        user::repository::UserRepository,
+    },
+    error::AppResult,
+    infrastructure::persistence::redis::RedisCache,
+};
+
+/// 削除待ちのユーザーIDのセット
+const PENDING_KEY: &str = "account_deletion:pending";
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// アカウント削除のバックグラウンドジョブ
+///
+/// 各手順は冪等なので、途中で中断した場合も最初から再実行すれば完了します。
+/// 削除が完了するまでユーザーIDは削除待ちのセットに残ります。
+pub struct AccountDeletionJob {
+    redis: Arc<RedisCache>,
+    user_repository: Arc<dyn UserRepository>,
+    memo_repository: Arc<dyn MemoRepository>,
+    folder_repository: Arc<dyn FolderRepository>,
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    identity_repository: Arc<dyn OAuthIdentityRepository>,
+    session_repository: Arc<dyn SessionRepository>,
+    search_history: Arc<SearchHistory>,
+}
+
+impl AccountDeletionJob {
+    pub fn new(
+        redis: Arc<RedisCache>,
+        user_repository: Arc<dyn UserRepository>,
+        memo_repository: Arc<dyn MemoRepository>,
+        folder_repository: Arc<dyn FolderRepository>,
+        api_key_repository: Arc<dyn ApiKeyRepository>,
+        identity_repository: Arc<dyn OAuthIdentityRepository>,
+        session_repository: Arc<dyn SessionRepository>,
+        search_history: Arc<SearchHistory>,
+    ) -> Self {
+        Self {
+            redis,
+            user_repository,
+            memo_repository,
+            folder_repository,
+            api_key_repository,
+            identity_repository,
+            session_repository,
+            search_history,
+        }
+    }
+
+    pub async fn enqueue(&self, user_id: Uuid) -> AppResult<()> {
+        self.redis.add_to_set(PENDING_KEY, &user_id.to_string()).await
+    }
+
+    /// ユーザーのデータをすべての保存先から削除
+    pub async fn process(&self, user_id: Uuid) -> AppResult<()> {
+        self.memo_repository.delete_all_by_user_id(user_id).await?;
+
+        for folder in self.folder_repository.find_all_by_user_id(user_id).await? {
+            self.folder_repository.delete(&folder).await?;
+        }
+
+        for api_key in self.api_key_repository.find_all_by_user_id(user_id).await? {
+            self.api_key_repository.delete(&api_key).await?;
+        }
+        for identity in self.identity_repository.find_all_by_user_id(user_id).await? {
+            self.identity_repository.delete(&identity).await?;
+        }
+        self.session_repository.delete_all_by_user_id(user_id).await?;
+        self.search_history.clear(user_id).await?;
+
+        // ユーザー本体は最後に削除（それまではログイン不可の削除待ち状態が残る）
+        self.user_repository.delete(user_id).await
+    }
+
+    /// 削除待ちのアカウントをすべて処理
+    pub async fn process_pending(&self) -> AppResult<()> {
+        for member in self.redis.set_members(PENDING_KEY).await? {
+            let Ok(user_id) = Uuid::parse_str(&member) else {
+                warn!("Dropping invalid account deletion entry: {}", member);
+                self.redis.remove_from_set(PENDING_KEY, &member).await?;
+                continue;
+            };
+
+            match self.process(user_id).await {
+                Ok(()) => {
+                    self.redis.remove_from_set(PENDING_KEY, &member).await?;
+                    info!("Deleted account {}", user_id);
+                }
+                // 次回の実行で再試行
+                Err(e) => error!("Failed to delete account {}: {}", user_id, e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 削除待ちのアカウントを定期的に処理し続けるワーカー
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.process_pending().await {
+                error!("Failed to process account deletions: {}", e);
+            }
+        }
+    }
+}
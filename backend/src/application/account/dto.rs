@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use validator::Validate;
+use crate::{
+    application::user::dto::UserResponse,
+    domain::{identity::entity::OAuthIdentity, memo::entity::Memo},
+};
+
+/// アカウント削除の確認
+///
+/// パスワードを省略した場合は直近のログインによるセッションであることを求めます
+/// （外部アカウントで登録したユーザーはパスワードを持たないため）。
+#[derive(Debug, Deserialize, Validate)]
+pub struct DeleteAccountDto {
+    #[validate(length(min = 1, message = "Password cannot be empty"))]
+    pub password: Option<String>,
+}
+
+/// エクスポートに含めるプロフィール
+#[derive(Debug, Serialize)]
+pub struct ExportProfile {
+    #[serde(flatten)]
+    pub user: UserResponse,
+    pub linked_accounts: Vec<LinkedAccountResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkedAccountResponse {
+    pub provider: String,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<OAuthIdentity> for LinkedAccountResponse {
+    fn from(identity: OAuthIdentity) -> Self {
+        Self {
+            provider: identity.provider,
+            email: identity.email,
+            created_at: identity.created_at,
+        }
+    }
+}
+
+/// タグごとの使用数
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct TagUsage {
+    pub tag: String,
+    pub count: usize,
+}
+
+impl TagUsage {
+    /// メモからタグの使用数を集計（使用数の多い順、同数はタグ名順）
+    pub fn from_memos(memos: &[Memo]) -> Vec<Self> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for tag in memos.iter().flat_map(|memo| memo.tags.iter()) {
+            *counts.entry(tag.as_str()).or_default() += 1;
+        }
+
+        let mut usages: Vec<Self> = counts
+            .into_iter()
+            .map(|(tag, count)| Self {
+                tag: tag.to_string(),
+                count,
+            })
+            .collect();
+        usages.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        usages
+    }
+}
+
+/// ダウンロード用のエクスポートファイル
+pub struct DataExport {
+    pub filename: String,
+    pub content: Vec<u8>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_tag_usage_is_sorted_by_count() {
+        let user_id = Uuid::new_v4();
+        let memos = vec![
+            Memo::new("a".into(), "a".into(), vec!["rust".into(), "work".into()], user_id),
+            Memo::new("b".into(), "b".into(), vec!["work".into()], user_id),
+            Memo::new("c".into(), "c".into(), vec!["home".into()], user_id),
+        ];
+
+        let usages = TagUsage::from_memos(&memos);
+
+        assert_eq!(
+            usages,
+            vec![
+                TagUsage { tag: "work".into(), count: 2 },
+                TagUsage { tag: "home".into(), count: 1 },
+                TagUsage { tag: "rust".into(), count: 1 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod deletion;
+pub mod dto;
+pub mod service;
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use chrono::Utc;
+use serde::Serialize;
+use validator::Validate;
+use crate::{
+    application::{
+        api_key::dto::ApiKeyResponse,
+        auth::AuthenticatedUser,
+        folder::dto::FolderResponse,
+        memo::dto::MemoResponse,
+        session::service::SessionService,
+        user::dto::UserResponse,
+    },
+    domain::{
+        api_key::repository::ApiKeyRepository,
+        folder::{entity::FolderTree, repository::FolderRepository},
+        identity::repository::OAuthIdentityRepository,
+        memo::repository::MemoRepository,
+        user::{entity::User, repository::UserRepository},
+    },
+    error::{AppError, AppResult},
+    infrastructure::{archive::build_zip, auth::password::verify_password},
+};
+use super::{
+    deletion::AccountDeletionJob,
+    dto::{DataExport, DeleteAccountDto, ExportProfile, LinkedAccountResponse, TagUsage},
+};
+
+/// 個人データのエクスポートとアカウント削除
+pub struct AccountService {
+    user_repository: Arc<dyn UserRepository>,
+    memo_repository: Arc<dyn MemoRepository>,
+    folder_repository: Arc<dyn FolderRepository>,
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    identity_repository: Arc<dyn OAuthIdentityRepository>,
+    session_service: Arc<SessionService>,
+    deletion_job: Arc<AccountDeletionJob>,
+}
+
+impl AccountService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        memo_repository: Arc<dyn MemoRepository>,
+        folder_repository: Arc<dyn FolderRepository>,
+        api_key_repository: Arc<dyn ApiKeyRepository>,
+        identity_repository: Arc<dyn OAuthIdentityRepository>,
+        session_service: Arc<SessionService>,
+        deletion_job: Arc<AccountDeletionJob>,
+    ) -> Self {
+        Self {
+            user_repository,
+            memo_repository,
+            folder_repository,
+            api_key_repository,
+            identity_repository,
+            session_service,
+            deletion_job,
+        }
+    }
+
+    /// ユーザーの全データをZIPアーカイブとしてエクスポート
+    ///
+    /// 添付ファイルは現在保存していないため、アーカイブには含まれません。
+    pub async fn export_data(&self, current: &AuthenticatedUser) -> AppResult<DataExport> {
+        current.require_session()?;
+
+        let user = self.find_user(current).await?;
+        let memos = self.memo_repository.find_all_by_user_id(user.id).await?;
+        let folders = self.folder_repository.find_all_by_user_id(user.id).await?;
+        let identities = self.identity_repository.find_all_by_user_id(user.id).await?;
+        let api_keys = self.api_key_repository.find_all_by_user_id(user.id).await?;
+        let sessions = self.session_service.list_sessions(current).await?;
+
+        let tags = TagUsage::from_memos(&memos);
+        let profile = ExportProfile {
+            user: UserResponse::from(user),
+            linked_accounts: identities.into_iter().map(LinkedAccountResponse::from).collect(),
+        };
+        let memos: Vec<MemoResponse> = memos.into_iter().map(MemoResponse::from).collect();
+        let tree = FolderTree::new(folders.clone());
+        let folders: Vec<FolderResponse> = folders
+            .iter()
+            .map(|folder| FolderResponse::new(folder, &tree))
+            .collect();
+        let api_keys: Vec<ApiKeyResponse> = api_keys.into_iter().map(ApiKeyResponse::from).collect();
+
+        let files = vec![
+            ("profile.json".to_string(), to_json(&profile)?),
+            ("memos.json".to_string(), to_json(&memos)?),
+            ("folders.json".to_string(), to_json(&folders)?),
+            ("tags.json".to_string(), to_json(&tags)?),
+            ("api_keys.json".to_string(), to_json(&api_keys)?),
+            ("sessions.json".to_string(), to_json(&sessions)?),
+        ];
+
+        Ok(DataExport {
+            filename: format!("memo-app-export-{}.zip", Utc::now().format("%Y%m%d")),
+            content: build_zip(&files)?,
+        })
+    }
+
+    /// アカウント削除を受け付け、削除処理をバックグラウンドジョブに登録
+    pub async fn delete_account(
+        &self,
+        current: &AuthenticatedUser,
+        dto: DeleteAccountDto,
+    ) -> AppResult<()> {
+        current.require_session()?;
+        dto.validate()?;
+
+        let mut user = self.find_user(current).await?;
+        match &dto.password {
+            Some(password) => {
+                if !verify_password(password, &user.password_hash).await? {
+                    return Err(AppError::Unauthorized("Password is incorrect".into()));
+                }
+            }
+            None => self.session_service.require_recent_login(current).await?,
+        }
+
+        user.request_deletion();
+        self.user_repository.save(&mut user).await?;
+
+        // 削除の完了を待たずにすべてのアクセス手段を無効化
+        for api_key in self.api_key_repository.find_all_by_user_id(user.id).await? {
+            self.api_key_repository.delete(&api_key).await?;
+        }
+        self.session_service.revoke_all(user.id).await?;
+
+        self.deletion_job.enqueue(user.id).await
+    }
+
+    async fn find_user(&self, user: &AuthenticatedUser) -> AppResult<User> {
+        self.user_repository
+            .find_by_id(user.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> AppResult<Vec<u8>> {
+    serde_json::to_vec_pretty(value)
+        .map_err(|e| AppError::InternalServerError(format!("Failed to serialize export: {}", e)))
+}
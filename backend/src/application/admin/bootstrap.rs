@@ -0,0 +1,63 @@
+// src/application/admin/bootstrap.rs
+
+use std::sync::Arc;
+use crate::{
+    domain::user::{entity::User, repository::UserRepository, role::Role},
+    error::AppResult,
+};
+
+/// 管理者の初期設定
+pub struct AdminConfig {
+    /// ログイン時に管理者へ昇格させるメールアドレス（小文字に正規化済み）
+    pub emails: Vec<String>,
+}
+
+impl AdminConfig {
+    /// 環境変数から設定を読み込む
+    ///
+    /// * `ADMIN_EMAILS` - 管理者にするメールアドレス（カンマ区切り、既定は空）
+    pub fn from_env() -> AppResult<Self> {
+        let emails = std::env::var("ADMIN_EMAILS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|email| email.trim().to_lowercase())
+            .filter(|email| !email.is_empty())
+            .collect();
+
+        Ok(Self { emails })
+    }
+}
+
+/// 設定されたメールアドレスのユーザーをログイン時に管理者へ昇格
+///
+/// 最初の管理者を作成する手段です。メールアドレスの所有を確認できていない
+/// ユーザーは昇格させません（登録だけで管理者権限を得られないようにするため）。
+pub struct AdminBootstrap {
+    user_repository: Arc<dyn UserRepository>,
+    emails: Vec<String>,
+}
+
+impl AdminBootstrap {
+    pub fn new(user_repository: Arc<dyn UserRepository>, config: AdminConfig) -> Self {
+        Self {
+            user_repository,
+            emails: config.emails,
+        }
+    }
+
+    /// 対象のユーザーであれば管理者に昇格して保存
+    pub async fn apply(&self, mut user: User) -> AppResult<User> {
+        if user.role == Role::Admin
+            || !user.email_verified()
+            || !self.emails.iter().any(|email| *email == user.email)
+        {
+            return Ok(user);
+        }
+
+        user.change_role(Role::Admin);
+        self.user_repository.save(&mut user).await?;
+
+        log::info!("User {} was promoted to admin by ADMIN_EMAILS", user.id);
+        Ok(user)
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::user::role::Role;
+use super::reindex::{ReindexJob, ReindexStatus};
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeRoleDto {
+    pub role: Role,
+}
+
+/// 再インデックスの進捗
+#[derive(Debug, Serialize)]
+pub struct ReindexJobResponse {
+    pub id: Uuid,
+    pub status: ReindexStatus,
+    pub index: Option<String>,
+    pub processed: usize,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<ReindexJob> for ReindexJobResponse {
+    fn from(job: ReindexJob) -> Self {
+        Self {
+            id: job.id,
+            status: job.status,
+            index: job.index,
+            processed: job.processed,
+            last_error: job.last_error,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            completed_at: job.completed_at,
+        }
+    }
+}
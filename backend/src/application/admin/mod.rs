@@ -0,0 +1,4 @@
+pub mod bootstrap;
+pub mod dto;
+pub mod reindex;
+pub mod service;
@@ -0,0 +1,301 @@
+// src/application/admin/reindex.rs
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use crate::{
+    domain::{folder::entity::FolderTree, memo::entity::Memo},
+    error::{AppError, AppResult},
+    infrastructure::persistence::{
+        elasticsearch::{ElasticsearchClient, REINDEX_STATE_TTL},
+        redis::RedisCache,
+        scylla::ScyllaDB,
+    },
+};
+
+/// 実行中または最後に実行した再インデックスのジョブ
+const JOB_KEY: &str = "search_reindex:job";
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// 最後に更新されてからジョブの状態を保持する期間
+const JOB_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const MAX_ATTEMPTS: u32 = 5;
+/// 再インデックスを実行しているインスタンスのリース
+const LEASE_KEY: &str = "search_reindex:lease";
+/// リースの有効期間（1ページの処理ごとに延長する）
+const LEASE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReindexStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// 検索インデックスの再インデックスジョブ
+///
+/// `index` は作成中のインデックス名、`paging_state` は読み終えた位置までの
+/// ページングステート（Base64）で、中断した場合はその続きから再開します。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexJob {
+    pub id: Uuid,
+    pub status: ReindexStatus,
+    pub index: Option<String>,
+    pub processed: usize,
+    pub paging_state: Option<String>,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl ReindexJob {
+    fn new() -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            status: ReindexStatus::Pending,
+            index: None,
+            processed: 0,
+            paging_state: None,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        matches!(self.status, ReindexStatus::Pending | ReindexStatus::Running)
+    }
+}
+
+/// ScyllaDB のメモを新しい版のインデックスへ流し込み、エイリアスを切り替えるワーカー
+///
+/// ジョブの開始から切り替えまでの間、メモの書き込みは旧インデックスと
+/// 新インデックスの両方に行われます。複数のインスタンスで起動しても、
+/// Redis のリースを取得した1つのインスタンスだけがジョブを実行します。
+pub struct SearchReindexer {
+    scylla: Arc<ScyllaDB>,
+    redis: Arc<RedisCache>,
+    elasticsearch: Arc<ElasticsearchClient>,
+    /// リースの所有者としてこのインスタンスを識別する値
+    instance_id: String,
+}
+
+impl SearchReindexer {
+    pub fn new(
+        scylla: Arc<ScyllaDB>,
+        redis: Arc<RedisCache>,
+        elasticsearch: Arc<ElasticsearchClient>,
+    ) -> Self {
+        Self {
+            scylla,
+            redis,
+            elasticsearch,
+            instance_id: Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// 再インデックスを開始（実行中のジョブがあれば Conflict）
+    pub async fn enqueue(&self) -> AppResult<ReindexJob> {
+        let job = ReindexJob::new();
+        // 実行中のジョブの確認と保存を1回の操作で行う
+        let saved = self.redis
+            .set_unless(JOB_KEY, "status", &["pending", "running"], &job, JOB_TTL)
+            .await?;
+        if !saved {
+            return Err(AppError::Conflict("A search reindex is already running".into()));
+        }
+
+        Ok(job)
+    }
+
+    /// インデックスの定義が古ければ再インデックスを開始
+    pub async fn enqueue_if_outdated(&self) -> AppResult<()> {
+        if self.elasticsearch.index_up_to_date().await?
+            || self.current().await?.is_some_and(|job| job.is_active())
+        {
+            return Ok(());
+        }
+
+        let job = self.enqueue().await?;
+        info!("Search index is outdated; scheduled reindex job {}", job.id);
+        Ok(())
+    }
+
+    pub async fn current(&self) -> AppResult<Option<ReindexJob>> {
+        self.redis.get::<ReindexJob>(JOB_KEY).await
+    }
+
+    async fn save(&self, job: &ReindexJob) -> AppResult<()> {
+        self.redis.set(JOB_KEY, job, Some(JOB_TTL)).await
+    }
+
+    /// リースを延長（他のインスタンスに移っていればエラー）
+    async fn renew_lease(&self) -> AppResult<()> {
+        if !self.redis.acquire_lease(LEASE_KEY, &self.instance_id, LEASE_TTL).await? {
+            return Err(AppError::Conflict("Lost the search reindex lease".into()));
+        }
+        Ok(())
+    }
+
+    /// 再インデックス先に追加したメモを ScyllaDB で確かめ直し、
+    /// 読み込み後にゴミ箱へ移動・削除されたものを取り除く
+    ///
+    /// 追加より前に行われた二重書き込みの削除は再インデックス先に反映されないため、
+    /// 追加した後で最新の状態を読み直します。
+    async fn remove_stale(&self, index: &str, documents: &[(Memo, Vec<Uuid>)]) -> AppResult<()> {
+        let mut ids_by_user: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (memo, _) in documents {
+            ids_by_user.entry(memo.user_id).or_default().push(memo.id);
+        }
+
+        let mut stale = Vec::new();
+        for (user_id, ids) in ids_by_user {
+            let current: HashMap<Uuid, Memo> = self.scylla
+                .find_memos_by_ids(user_id, &ids)
+                .await?
+                .into_iter()
+                .map(|memo| (memo.id, memo))
+                .collect();
+            stale.extend(
+                ids.into_iter()
+                    .filter(|id| current.get(id).is_none_or(|memo| memo.is_trashed())),
+            );
+        }
+
+        self.elasticsearch.delete_memos_from(index, &stale).await
+    }
+
+    /// 保存済みの位置からメモを流し込み、最後にエイリアスを切り替える
+    async fn process(&self, job: &mut ReindexJob) -> AppResult<()> {
+        let index = self.elasticsearch.begin_reindex().await?;
+        if job.index.as_ref() != Some(&index) {
+            // 作成先が変わった場合は最初から読み直す
+            job.processed = 0;
+            job.paging_state = None;
+        }
+        job.index = Some(index.clone());
+        job.status = ReindexStatus::Running;
+        job.updated_at = Utc::now();
+        self.save(job).await?;
+
+        // すべてのインスタンスが二重書き込みを始めてから読み込む
+        tokio::time::sleep(REINDEX_STATE_TTL * 2).await;
+        self.renew_lease().await?;
+
+        let mut paging_state = match &job.paging_state {
+            Some(encoded) => Some(Arc::from(
+                URL_SAFE_NO_PAD
+                    .decode(encoded)
+                    .map_err(|e| AppError::InternalServerError(format!("Invalid paging state: {}", e)))?,
+            )),
+            None => None,
+        };
+        let mut folder_trees: HashMap<Uuid, FolderTree> = HashMap::new();
+
+        loop {
+            let (memos, next_page) = self.scylla.scan_memos(paging_state).await?;
+
+            let mut documents = Vec::with_capacity(memos.len());
+            for memo in memos {
+                job.processed += 1;
+                // ゴミ箱のメモは検索対象から外す
+                if memo.is_trashed() {
+                    continue;
+                }
+
+                let folder_path = match memo.folder_id {
+                    Some(folder_id) => {
+                        if !folder_trees.contains_key(&memo.user_id) {
+                            let folders = self.scylla.find_folders_by_user_id(memo.user_id).await?;
+                            folder_trees.insert(memo.user_id, FolderTree::new(folders));
+                        }
+                        folder_trees[&memo.user_id].path(folder_id)
+                    }
+                    None => Vec::new(),
+                };
+                documents.push((memo, folder_path));
+            }
+            self.elasticsearch.create_memos(&index, &documents).await?;
+            self.remove_stale(&index, &documents).await?;
+            self.renew_lease().await?;
+
+            job.paging_state = next_page.as_ref().map(|bytes| URL_SAFE_NO_PAD.encode(bytes));
+            job.updated_at = Utc::now();
+            self.save(job).await?;
+
+            match next_page {
+                Some(next_page) => paging_state = Some(next_page),
+                None => break,
+            }
+        }
+
+        self.elasticsearch.finish_reindex(&index).await?;
+
+        job.status = ReindexStatus::Completed;
+        job.completed_at = Some(Utc::now());
+        job.updated_at = Utc::now();
+        self.save(job).await
+    }
+
+    /// 実行待ち・実行中のジョブがあれば処理
+    ///
+    /// 他のインスタンスがリースを保持している間は何もしません。
+    pub async fn process_pending(&self) -> AppResult<()> {
+        if !self.redis.acquire_lease(LEASE_KEY, &self.instance_id, LEASE_TTL).await? {
+            return Ok(());
+        }
+
+        let result = self.process_current().await;
+        self.redis.release_lease(LEASE_KEY, &self.instance_id).await?;
+        result
+    }
+
+    async fn process_current(&self) -> AppResult<()> {
+        // リースの取得前に他のインスタンスが進めた状態から再開する
+        let Some(mut job) = self.current().await?.filter(ReindexJob::is_active) else {
+            return Ok(());
+        };
+
+        match self.process(&mut job).await {
+            Ok(()) => info!("Completed search reindex {} ({} memos)", job.id, job.processed),
+            // 他のインスタンスが引き継いだジョブの状態は上書きしない
+            Err(AppError::Conflict(e)) => warn!("Stopped search reindex {}: {}", job.id, e),
+            Err(e) => {
+                job.attempts += 1;
+                job.last_error = Some(e.to_string());
+                job.updated_at = Utc::now();
+
+                if job.attempts >= MAX_ATTEMPTS {
+                    error!("Giving up on search reindex {} after {} attempts: {}", job.id, job.attempts, e);
+                    job.status = ReindexStatus::Failed;
+                } else {
+                    // 次回の実行で再開
+                    warn!("Failed to run search reindex {} (attempt {}): {}", job.id, job.attempts, e);
+                }
+                self.save(&job).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 再インデックスのジョブを定期的に確認し続けるワーカー
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.process_pending().await {
+                error!("Failed to process search reindex: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::{
+    application::{
+        auth::AuthenticatedUser,
+        mfa::service::MfaService,
+        session::service::SessionService,
+        user::dto::UserResponse,
+    },
+    domain::user::{entity::User, repository::UserRepository, role::Permission},
+    error::{AppError, AppResult},
+};
+use super::{
+    dto::{ChangeRoleDto, ReindexJobResponse},
+    reindex::SearchReindexer,
+};
+
+/// 管理者によるユーザー管理と検索インデックスの保守
+pub struct AdminService {
+    user_repository: Arc<dyn UserRepository>,
+    session_service: Arc<SessionService>,
+    mfa_service: Arc<MfaService>,
+    reindexer: Arc<SearchReindexer>,
+}
+
+impl AdminService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        session_service: Arc<SessionService>,
+        mfa_service: Arc<MfaService>,
+        reindexer: Arc<SearchReindexer>,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_service,
+            mfa_service,
+            reindexer,
+        }
+    }
+
+    pub async fn get_user(&self, current: &AuthenticatedUser, user_id: Uuid) -> AppResult<UserResponse> {
+        Self::require_admin(current)?;
+
+        let user = self.find_user(user_id).await?;
+        Ok(UserResponse::from(user))
+    }
+
+    /// ロールを変更し、既存のセッションを失効（新しいロールは再ログイン後に反映）
+    pub async fn change_role(
+        &self,
+        current: &AuthenticatedUser,
+        user_id: Uuid,
+        dto: ChangeRoleDto,
+    ) -> AppResult<UserResponse> {
+        Self::require_admin(current)?;
+
+        // 最後の管理者が自分の権限を失うことを防ぐ
+        if current.owns(user_id) {
+            return Err(AppError::BadRequest("Admins cannot change their own role".into()));
+        }
+
+        let mut user = self.find_user(user_id).await?;
+        user.change_role(dto.role);
+        self.user_repository.save(&mut user).await?;
+        // セッションのロールを書き換えると並行したリフレッシュで上書きされうるため失効させる
+        self.session_service.revoke_all(user.id).await?;
+
+        log::info!("User {} changed the role of {} to {}", current.user_id, user.id, user.role);
+        Ok(UserResponse::from(user))
+    }
+
+    /// 端末を紛失したユーザーのMFAをリセット
+    pub async fn reset_mfa(&self, current: &AuthenticatedUser, user_id: Uuid) -> AppResult<()> {
+        Self::require_admin(current)?;
+        self.mfa_service.reset(user_id).await
+    }
+
+    /// 検索インデックスの再インデックスを開始（処理はバックグラウンドで行う）
+    pub async fn start_reindex(&self, current: &AuthenticatedUser) -> AppResult<ReindexJobResponse> {
+        Self::require_admin(current)?;
+
+        let job = self.reindexer.enqueue().await?;
+        log::info!("User {} started search reindex {}", current.user_id, job.id);
+        Ok(job.into())
+    }
+
+    /// 実行中または最後に実行した再インデックスの進捗
+    pub async fn get_reindex(&self, current: &AuthenticatedUser) -> AppResult<ReindexJobResponse> {
+        Self::require_admin(current)?;
+
+        self.reindexer
+            .current()
+            .await?
+            .map(ReindexJobResponse::from)
+            .ok_or_else(|| AppError::NotFound("No search reindex has been run".into()))
+    }
+
+    fn require_admin(current: &AuthenticatedUser) -> AppResult<()> {
+        current.require_session()?;
+        current.require_permission(Permission::UsersManage)
+    }
+
+    async fn find_user(&self, user_id: Uuid) -> AppResult<User> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+}
@@ -0,0 +1,124 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    application::auth::AuthenticatedUser,
+    domain::{
+        api_key::{entity::ApiKey, repository::ApiKeyRepository},
+        user::repository::UserRepository,
+    },
+    error::{AppError, AppResult},
+    infrastructure::auth::token::{generate_token, hash_token},
+};
+use super::dto::{ApiKeyResponse, CreateApiKeyDto, CreatedApiKeyResponse};
+
+/// APIキーの接頭辞（JWTと区別するため）
+pub const API_KEY_PREFIX: &str = "mk_";
+/// 一覧表示用に保持するキー先頭の長さ（接頭辞を除く）
+const DISPLAY_PREFIX_LEN: usize = 8;
+/// 最終使用日時を更新する間隔
+const LAST_USED_UPDATE_INTERVAL_SECS: i64 = 60;
+
+pub struct ApiKeyService {
+    api_key_repository: Arc<dyn ApiKeyRepository>,
+    user_repository: Arc<dyn UserRepository>,
+}
+
+impl ApiKeyService {
+    pub fn new(
+        api_key_repository: Arc<dyn ApiKeyRepository>,
+        user_repository: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self {
+            api_key_repository,
+            user_repository,
+        }
+    }
+
+    pub fn is_api_key(token: &str) -> bool {
+        token.starts_with(API_KEY_PREFIX)
+    }
+
+    pub async fn create_api_key(
+        &self,
+        user: &AuthenticatedUser,
+        dto: CreateApiKeyDto,
+    ) -> AppResult<CreatedApiKeyResponse> {
+        user.require_session()?;
+        dto.validate()?;
+
+        if dto.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(AppError::ValidationError("expires_at must be in the future".into()));
+        }
+
+        let mut scopes = dto.scopes;
+        scopes.sort_by_key(|scope| scope.as_str());
+        scopes.dedup();
+
+        let secret = generate_token(32);
+        let key = format!("{}{}", API_KEY_PREFIX, secret);
+        let api_key = ApiKey::new(
+            user.user_id,
+            dto.name,
+            format!("{}{}", API_KEY_PREFIX, &secret[..DISPLAY_PREFIX_LEN]),
+            hash_token(&key),
+            scopes,
+            dto.expires_at,
+        );
+        self.api_key_repository.save(&api_key).await?;
+
+        Ok(CreatedApiKeyResponse {
+            key,
+            api_key: ApiKeyResponse::from(api_key),
+        })
+    }
+
+    pub async fn list_api_keys(&self, user: &AuthenticatedUser) -> AppResult<Vec<ApiKeyResponse>> {
+        user.require_session()?;
+
+        let mut api_keys = self.api_key_repository.find_all_by_user_id(user.user_id).await?;
+        api_keys.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(api_keys.into_iter().map(ApiKeyResponse::from).collect())
+    }
+
+    pub async fn revoke_api_key(&self, user: &AuthenticatedUser, id: Uuid) -> AppResult<()> {
+        user.require_session()?;
+
+        let api_key = self
+            .api_key_repository
+            .find_by_id(user.user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("API key not found".into()))?;
+
+        self.api_key_repository.delete(&api_key).await
+    }
+
+    /// `mk_...` 形式のキーを検証し、スコープ付きの主体を返す
+    pub async fn authenticate(&self, key: &str) -> AppResult<AuthenticatedUser> {
+        let mut api_key = self
+            .api_key_repository
+            .find_by_hash(&hash_token(key))
+            .await?
+            .filter(|api_key| !api_key.is_expired())
+            .ok_or_else(|| AppError::Unauthorized("Invalid or expired API key".into()))?;
+
+        // ロールは発行時ではなく現在の所有者のものを使う
+        let owner = self
+            .user_repository
+            .find_by_id(api_key.user_id)
+            .await?
+            .filter(|user| !user.is_pending_deletion())
+            .ok_or_else(|| AppError::Unauthorized("Invalid or expired API key".into()))?;
+
+        let stale = api_key.last_used_at.is_none_or(|last_used_at| {
+            Utc::now() - last_used_at > Duration::seconds(LAST_USED_UPDATE_INTERVAL_SECS)
+        });
+        if stale {
+            api_key.touch();
+            self.api_key_repository.touch(&api_key).await?;
+        }
+
+        Ok(AuthenticatedUser::with_api_key(api_key.user_id, owner.role, api_key.scopes))
+    }
+}
@@ -0,0 +1,93 @@
+// src/application/auth/login_guard.rs
+
+use std::sync::Arc;
+use tracing::{error, warn};
+use crate::{
+    application::session::dto::ClientInfo,
+    domain::audit::{
+        entity::{AuditEvent, AuditEventKind},
+        repository::AuditRepository,
+    },
+    error::AppResult,
+    infrastructure::auth::throttle::{LoginThrottle, RateLimit, ThrottleTarget},
+};
+
+/// ログイン試行の総当たり対策
+///
+/// アカウント単位と接続元IP単位で失敗回数を数え、
+/// ロックアウトに達した場合は監査ログに記録します。
+pub struct LoginGuard {
+    throttle: Arc<LoginThrottle>,
+    audit_repository: Arc<dyn AuditRepository>,
+}
+
+impl LoginGuard {
+    pub fn new(throttle: Arc<LoginThrottle>, audit_repository: Arc<dyn AuditRepository>) -> Self {
+        Self {
+            throttle,
+            audit_repository,
+        }
+    }
+
+    fn targets<'a>(email: &'a str, client: &'a ClientInfo) -> Vec<ThrottleTarget<'a>> {
+        let mut targets = vec![ThrottleTarget::Account(email)];
+        if let Some(ip) = client.ip.as_deref() {
+            targets.push(ThrottleTarget::Ip(ip));
+        }
+        targets
+    }
+
+    /// パスワード検証の前に呼び出し、制限中であれば 429 を返す
+    pub async fn check(&self, email: &str, client: &ClientInfo) -> AppResult<()> {
+        self.throttle.check(&Self::targets(email, client)).await
+    }
+
+    /// 認証失敗を記録
+    pub async fn record_failure(&self, email: &str, client: &ClientInfo) -> AppResult<()> {
+        let locked = self
+            .throttle
+            .record_failure(&Self::targets(email, client))
+            .await?;
+
+        for target in locked {
+            let (kind, subject) = match target {
+                ThrottleTarget::Account(email) => (AuditEventKind::AccountLocked, email.trim().to_lowercase()),
+                ThrottleTarget::Ip(ip) => (AuditEventKind::IpLocked, ip.to_string()),
+            };
+            warn!("Login locked out: {} {}", kind, subject);
+
+            let event = AuditEvent::new(
+                kind,
+                subject,
+                None,
+                client.ip.clone(),
+                client.device.clone(),
+            );
+            // 監査ログの書き込み失敗でログイン処理自体は失敗させない
+            if let Err(e) = self.audit_repository.save(&event).await {
+                error!("Failed to record audit event: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// パスワード再設定メールの送信回数を制限（上限を超えていれば 429）
+    ///
+    /// 登録の有無を推測されないよう、存在しないアカウント宛ての要求も数えます。
+    pub async fn check_password_reset(&self, email: &str, client: &ClientInfo) -> AppResult<()> {
+        for target in Self::targets(email, client) {
+            let limit = match target {
+                ThrottleTarget::Account(_) => RateLimit::PASSWORD_RESET_ACCOUNT,
+                ThrottleTarget::Ip(_) => RateLimit::PASSWORD_RESET_IP,
+            };
+            self.throttle.limit("password_reset", target, limit).await?;
+        }
+        Ok(())
+    }
+
+    /// 認証成功時にアカウントの失敗回数をリセット
+    pub async fn record_success(&self, email: &str) -> AppResult<()> {
+        self.throttle.record_success(ThrottleTarget::Account(email)).await
+    }
+}
@@ -0,0 +1,145 @@
+// src/application/auth/mod.rs
+
+pub mod login_guard;
+
+use uuid::Uuid;
+use crate::{
+    domain::{
+        api_key::entity::ApiScope,
+        user::role::{Permission, Role},
+    },
+    error::{AppError, AppResult},
+};
+
+/// 認証済みのリクエスト主体
+///
+/// インターフェース層で資格情報を検証したうえで生成され、
+/// サービス層の認可チェックに使用されます。
+/// 認証の失敗は 401、認可の失敗は 403（`AppError::Forbidden`）として扱います。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedUser {
+    pub user_id: Uuid,
+    pub role: Role,
+    /// ログインセッション（セッションを伴わない資格情報の場合は `None`）
+    pub session_id: Option<Uuid>,
+    /// APIキーで認証された場合に許可されたスコープ（ログインセッションは `None` で全権限）
+    pub scopes: Option<Vec<ApiScope>>,
+}
+
+impl AuthenticatedUser {
+    /// ログインセッションによる主体
+    pub fn new(user_id: Uuid, role: Role, session_id: Option<Uuid>) -> Self {
+        Self {
+            user_id,
+            role,
+            session_id,
+            scopes: None,
+        }
+    }
+
+    /// APIキーによる主体
+    pub fn with_api_key(user_id: Uuid, role: Role, scopes: Vec<ApiScope>) -> Self {
+        Self {
+            user_id,
+            role,
+            session_id: None,
+            scopes: Some(scopes),
+        }
+    }
+
+    /// 指定ユーザーの所有物かどうか
+    pub fn owns(&self, owner_id: Uuid) -> bool {
+        self.user_id == owner_id
+    }
+
+    pub fn has_scope(&self, scope: ApiScope) -> bool {
+        self.scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.contains(&scope))
+    }
+
+    /// APIキーに指定スコープが付与されているか確認
+    pub fn require_scope(&self, scope: ApiScope) -> AppResult<()> {
+        if self.has_scope(scope) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(format!("API key lacks the '{}' scope", scope)))
+        }
+    }
+
+    /// アカウント設定など、対話的なログインが必要な操作か確認
+    pub fn require_session(&self) -> AppResult<()> {
+        if self.scopes.is_none() {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden("This operation is not available to API keys".into()))
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.role.has_permission(permission)
+    }
+
+    /// ロールに指定の権限があるか確認
+    pub fn require_permission(&self, permission: Permission) -> AppResult<()> {
+        if self.has_permission(permission) {
+            Ok(())
+        } else {
+            Err(AppError::Forbidden(format!("The '{}' permission is required", permission)))
+        }
+    }
+
+    /// 所有者本人であるか、他人のリソースに対する権限があるか確認
+    pub fn require_owner_or(&self, owner_id: Uuid, permission: Permission) -> AppResult<()> {
+        if self.owns(owner_id) {
+            Ok(())
+        } else {
+            self.require_permission(permission)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_user_has_all_scopes() {
+        let user = AuthenticatedUser::new(Uuid::new_v4(), Role::Member, Some(Uuid::new_v4()));
+
+        assert!(user.require_scope(ApiScope::MemosWrite).is_ok());
+        assert!(user.require_session().is_ok());
+    }
+
+    #[test]
+    fn test_api_key_user_is_limited_to_scopes() {
+        let user = AuthenticatedUser::with_api_key(Uuid::new_v4(), Role::Member, vec![ApiScope::MemosRead]);
+
+        assert!(user.require_scope(ApiScope::MemosRead).is_ok());
+        assert!(matches!(user.require_scope(ApiScope::MemosWrite), Err(AppError::Forbidden(_))));
+        assert!(matches!(user.require_session(), Err(AppError::Forbidden(_))));
+    }
+
+    #[test]
+    fn test_owner_or_permission() {
+        let owner_id = Uuid::new_v4();
+        let member = AuthenticatedUser::new(Uuid::new_v4(), Role::Member, None);
+        let admin = AuthenticatedUser::new(Uuid::new_v4(), Role::Admin, None);
+        let owner = AuthenticatedUser::new(owner_id, Role::ReadOnly, None);
+
+        assert!(owner.require_owner_or(owner_id, Permission::MemosModerate).is_ok());
+        assert!(admin.require_owner_or(owner_id, Permission::MemosModerate).is_ok());
+        assert!(matches!(
+            member.require_owner_or(owner_id, Permission::MemosModerate),
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_only_user_cannot_write() {
+        let user = AuthenticatedUser::new(Uuid::new_v4(), Role::ReadOnly, None);
+
+        assert!(user.require_permission(Permission::MemosRead).is_ok());
+        assert!(matches!(user.require_permission(Permission::MemosWrite), Err(AppError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+use crate::domain::folder::entity::{Folder, FolderTree};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateFolderDto {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RenameFolderDto {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+}
+
+/// フォルダの移動（`parent_id` が `null` の場合は最上位へ移動）
+#[derive(Debug, Deserialize)]
+pub struct MoveFolderDto {
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FolderResponse {
+    pub id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    /// 最上位からのフォルダ名のパス（例: `仕事/議事録`）
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FolderResponse {
+    pub fn new(folder: &Folder, tree: &FolderTree) -> Self {
+        Self {
+            id: folder.id,
+            parent_id: folder.parent_id,
+            name: folder.name.clone(),
+            path: tree.display_path(folder.id),
+            created_at: folder.created_at,
+            updated_at: folder.updated_at,
+        }
+    }
+}
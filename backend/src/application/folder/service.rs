@@ -0,0 +1,263 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    application::{
+        auth::AuthenticatedUser,
+        memo::{dto::SearchResponse, trash::MemoTrash},
+    },
+    domain::{
+        api_key::entity::ApiScope,
+        folder::{
+            entity::{Folder, FolderTree, MAX_DEPTH},
+            repository::FolderRepository,
+        },
+        memo::{entity::Memo, repository::MemoRepository},
+        user::role::Permission,
+    },
+    error::{AppError, AppResult},
+};
+use super::dto::{CreateFolderDto, FolderResponse, MoveFolderDto, RenameFolderDto};
+
+/// フォルダの管理
+///
+/// フォルダは所有者本人のみが操作できます（他人のフォルダは存在しないものとして扱う）。
+pub struct FolderService {
+    folder_repository: Arc<dyn FolderRepository>,
+    memo_repository: Arc<dyn MemoRepository>,
+    trash: Arc<MemoTrash>,
+}
+
+impl FolderService {
+    pub fn new(
+        folder_repository: Arc<dyn FolderRepository>,
+        memo_repository: Arc<dyn MemoRepository>,
+        trash: Arc<MemoTrash>,
+    ) -> Self {
+        Self {
+            folder_repository,
+            memo_repository,
+            trash,
+        }
+    }
+
+    pub async fn create_folder(
+        &self,
+        dto: CreateFolderDto,
+        user: &AuthenticatedUser,
+    ) -> AppResult<FolderResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+        dto.validate()?;
+
+        let mut tree = self.load_tree(user.user_id).await?;
+
+        if let Some(parent_id) = dto.parent_id {
+            if tree.get(parent_id).is_none() {
+                return Err(AppError::NotFound("Parent folder not found".into()));
+            }
+            if tree.path(parent_id).len() >= MAX_DEPTH {
+                return Err(AppError::BadRequest(format!(
+                    "Folders cannot be nested more than {} levels deep",
+                    MAX_DEPTH
+                )));
+            }
+        }
+
+        let folder = Folder::new(user.user_id, dto.name.trim().to_string(), dto.parent_id);
+        Self::validate_folder(&folder, &tree)?;
+
+        self.folder_repository.save(&folder).await?;
+        tree.insert(folder.clone());
+        Ok(FolderResponse::new(&folder, &tree))
+    }
+
+    /// フォルダ一覧（パス順）
+    pub async fn list_folders(&self, user: &AuthenticatedUser) -> AppResult<Vec<FolderResponse>> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let folders = self.folder_repository.find_all_by_user_id(user.user_id).await?;
+        let tree = FolderTree::new(folders.clone());
+
+        let mut responses: Vec<FolderResponse> = folders
+            .iter()
+            .map(|folder| FolderResponse::new(folder, &tree))
+            .collect();
+        responses.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(responses)
+    }
+
+    pub async fn get_folder(&self, id: Uuid, user: &AuthenticatedUser) -> AppResult<FolderResponse> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let tree = self.load_tree(user.user_id).await?;
+        let folder = Self::find_in(&tree, id)?;
+
+        Ok(FolderResponse::new(folder, &tree))
+    }
+
+    pub async fn rename_folder(
+        &self,
+        id: Uuid,
+        dto: RenameFolderDto,
+        user: &AuthenticatedUser,
+    ) -> AppResult<FolderResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+        dto.validate()?;
+
+        let mut tree = self.load_tree(user.user_id).await?;
+        let mut folder = Self::find_in(&tree, id)?.clone();
+
+        folder.rename(dto.name.trim().to_string());
+        Self::validate_folder(&folder, &tree)?;
+
+        self.folder_repository.save(&folder).await?;
+        tree.insert(folder.clone());
+        Ok(FolderResponse::new(&folder, &tree))
+    }
+
+    /// フォルダを別の親フォルダの下へ移動（配下のメモの検索用パスも更新）
+    pub async fn move_folder(
+        &self,
+        id: Uuid,
+        dto: MoveFolderDto,
+        user: &AuthenticatedUser,
+    ) -> AppResult<FolderResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        let mut tree = self.load_tree(user.user_id).await?;
+        let mut folder = Self::find_in(&tree, id)?.clone();
+
+        if let Some(parent_id) = dto.parent_id {
+            if tree.get(parent_id).is_none() {
+                return Err(AppError::NotFound("Parent folder not found".into()));
+            }
+        }
+        if !tree.can_move(id, dto.parent_id) {
+            return Err(AppError::BadRequest(format!(
+                "A folder cannot be moved into itself, into one of its subfolders, or more than {} levels deep",
+                MAX_DEPTH
+            )));
+        }
+
+        folder.move_to(dto.parent_id);
+        Self::validate_folder(&folder, &tree)?;
+        self.folder_repository.save(&folder).await?;
+
+        tree.insert(folder.clone());
+        let subtree = tree.subtree(id);
+        for memo in self.memos_in(user.user_id, &subtree).await? {
+            // 読み込み済みのフォルダからパスを求め、メモごとにフォルダを読み込まない
+            let folder_path = memo.folder_id.map(|folder_id| tree.path(folder_id)).unwrap_or_default();
+            self.memo_repository.reindex(&memo, &folder_path).await?;
+        }
+
+        Ok(FolderResponse::new(&folder, &tree))
+    }
+
+    /// フォルダの削除
+    ///
+    /// 空でないフォルダは `cascade` を指定しない限り削除を拒否します。
+    /// `cascade` の場合は配下のフォルダを削除し、配下のメモはゴミ箱へ移動します。
+    pub async fn delete_folder(&self, id: Uuid, cascade: bool, user: &AuthenticatedUser) -> AppResult<()> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        let tree = self.load_tree(user.user_id).await?;
+        Self::find_in(&tree, id)?;
+
+        let subtree = tree.subtree(id);
+        let memos = self.memos_in(user.user_id, &subtree).await?;
+
+        if !cascade && (subtree.len() > 1 || !memos.is_empty()) {
+            return Err(AppError::Conflict(
+                "Folder is not empty; pass cascade=true to delete it with its contents".into(),
+            ));
+        }
+
+        for mut memo in memos {
+            memo.move_to_trash();
+            self.memo_repository.update_metadata(&memo).await?;
+            self.trash.track(&memo).await?;
+        }
+
+        // 子フォルダから順に削除し、中断しても親から辿れる状態を保つ
+        for folder_id in subtree.iter().rev() {
+            if let Some(folder) = tree.get(*folder_id) {
+                self.folder_repository.delete(folder).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// フォルダ直下のメモ一覧（更新日時の新しい順）
+    pub async fn list_memos(
+        &self,
+        id: Uuid,
+        page: usize,
+        limit: usize,
+        user: &AuthenticatedUser,
+    ) -> AppResult<SearchResponse> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        self.folder_repository
+            .find_by_id(user.user_id, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Folder not found".into()))?;
+
+        let mut memos = self.memos_in(user.user_id, &[id]).await?;
+        memos.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+        Ok(SearchResponse::paginate(memos, page, limit))
+    }
+
+    async fn load_tree(&self, user_id: Uuid) -> AppResult<FolderTree> {
+        let folders = self.folder_repository.find_all_by_user_id(user_id).await?;
+        Ok(FolderTree::new(folders))
+    }
+
+    fn find_in(tree: &FolderTree, id: Uuid) -> AppResult<&Folder> {
+        tree.get(id)
+            .ok_or_else(|| AppError::NotFound("Folder not found".into()))
+    }
+
+    /// 名前の形式と、同じ親の下での名前の重複を確認
+    fn validate_folder(folder: &Folder, tree: &FolderTree) -> AppResult<()> {
+        if !folder.validate() {
+            return Err(AppError::ValidationError(
+                "Folder name must not be empty or contain '/'".into(),
+            ));
+        }
+
+        let siblings = match folder.parent_id {
+            Some(parent_id) => tree.children(parent_id),
+            None => tree.roots(),
+        };
+        if siblings
+            .iter()
+            .any(|sibling| sibling.id != folder.id && sibling.name == folder.name)
+        {
+            return Err(AppError::Conflict("A folder with the same name already exists here".into()));
+        }
+
+        Ok(())
+    }
+
+    /// 指定フォルダのいずれかに属する、ゴミ箱にないメモ
+    async fn memos_in(&self, user_id: Uuid, folder_ids: &[Uuid]) -> AppResult<Vec<Memo>> {
+        let memos = self.memo_repository.find_all_by_user_id(user_id).await?;
+
+        Ok(memos
+            .into_iter()
+            .filter(|memo| !memo.is_trashed())
+            .filter(|memo| memo.folder_id.is_some_and(|folder_id| folder_ids.contains(&folder_id)))
+            .collect())
+    }
+}
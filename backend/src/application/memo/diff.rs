@@ -0,0 +1,245 @@
+// src/application/memo/diff.rs
+
+use std::collections::BTreeSet;
+use serde::Serialize;
+use similar::{ChangeTag, DiffOp, TextDiff};
+use uuid::Uuid;
+use crate::domain::memo::entity::MemoVersion;
+
+/// 変更箇所の前後に含める文脈行数
+const CONTEXT_LINES: usize = 3;
+
+/// 2つのバージョン間の差分
+#[derive(Debug, Serialize)]
+pub struct MemoDiff {
+    pub memo_id: Uuid,
+    pub from: i32,
+    pub to: i32,
+    pub title: Vec<DiffHunk>,
+    pub content: Vec<DiffHunk>,
+    pub tags: TagDiff,
+}
+
+/// unified diff 形式のハンク（行番号は1始まり）
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LineKind {
+    Context,
+    Insert,
+    Delete,
+}
+
+/// 差分の1行
+///
+/// `segments` は行を単語単位に分割したもので、
+/// 変更された単語は `changed` が `true` になります。
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: LineKind,
+    pub text: String,
+    pub segments: Vec<DiffSegment>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct DiffSegment {
+    pub text: String,
+    pub changed: bool,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+pub struct TagDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl MemoDiff {
+    pub fn between(from: &MemoVersion, to: &MemoVersion) -> Self {
+        let old_tags: BTreeSet<&String> = from.tags.iter().collect();
+        let new_tags: BTreeSet<&String> = to.tags.iter().collect();
+
+        Self {
+            memo_id: to.memo_id,
+            from: from.version,
+            to: to.version,
+            title: diff_hunks(&from.title, &to.title),
+            content: diff_hunks(&from.content, &to.content),
+            tags: TagDiff {
+                added: new_tags.difference(&old_tags).map(|tag| tag.to_string()).collect(),
+                removed: old_tags.difference(&new_tags).map(|tag| tag.to_string()).collect(),
+            },
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.title.is_empty()
+            && self.content.is_empty()
+            && self.tags.added.is_empty()
+            && self.tags.removed.is_empty()
+    }
+}
+
+/// `text/x-diff` 形式の差分（タイトル・本文・タグをそれぞれ別ファイルとして表現）
+pub fn unified_diff(from: &MemoVersion, to: &MemoVersion) -> String {
+    let sorted_tags = |tags: &[String]| {
+        let tags: BTreeSet<&String> = tags.iter().collect();
+        tags.into_iter().map(|tag| format!("{}\n", tag)).collect::<String>()
+    };
+
+    let fields = [
+        ("title", from.title.clone(), to.title.clone()),
+        ("content", from.content.clone(), to.content.clone()),
+        ("tags", sorted_tags(&from.tags), sorted_tags(&to.tags)),
+    ];
+
+    fields
+        .iter()
+        .map(|(name, old, new)| {
+            TextDiff::from_lines(old.as_str(), new.as_str())
+                .unified_diff()
+                .context_radius(CONTEXT_LINES)
+                .header(
+                    &format!("a/{} (v{})", name, from.version),
+                    &format!("b/{} (v{})", name, to.version),
+                )
+                .to_string()
+        })
+        .collect()
+}
+
+/// 行単位で差分を取り、変更行の中は単語単位で変更箇所を示す
+fn diff_hunks(old: &str, new: &str) -> Vec<DiffHunk> {
+    let diff = TextDiff::from_lines(old, new);
+
+    diff.grouped_ops(CONTEXT_LINES)
+        .iter()
+        .filter_map(|group| {
+            let (first, last) = (group.first()?, group.last()?);
+            let old_range = first.old_range().start..last.old_range().end;
+            let new_range = first.new_range().start..last.new_range().end;
+
+            let lines = group
+                .iter()
+                .flat_map(|op: &DiffOp| diff.iter_inline_changes(op))
+                .map(|change| {
+                    let kind = match change.tag() {
+                        ChangeTag::Equal => LineKind::Context,
+                        ChangeTag::Insert => LineKind::Insert,
+                        ChangeTag::Delete => LineKind::Delete,
+                    };
+                    let segments: Vec<DiffSegment> = change
+                        .iter_strings_lossy()
+                        .map(|(changed, text)| DiffSegment {
+                            text: text.trim_end_matches('\n').to_string(),
+                            changed,
+                        })
+                        .filter(|segment| !segment.text.is_empty())
+                        .collect();
+
+                    DiffLine {
+                        kind,
+                        text: segments.iter().map(|segment| segment.text.as_str()).collect(),
+                        segments,
+                    }
+                })
+                .collect();
+
+            Some(DiffHunk {
+                old_start: old_range.start + 1,
+                old_lines: old_range.len(),
+                new_start: new_range.start + 1,
+                new_lines: new_range.len(),
+                lines,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn snapshot(version: i32, title: &str, content: &str, tags: &[&str]) -> MemoVersion {
+        MemoVersion {
+            memo_id: Uuid::nil(),
+            version,
+            title: title.to_string(),
+            content: content.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            saved_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_line_and_word_level_changes() {
+        let from = snapshot(3, "Title", "first line\nsecond line\nthird line\n", &["a"]);
+        let to = snapshot(7, "Title", "first line\nsecond word\nthird line\n", &["a"]);
+
+        let diff = MemoDiff::between(&from, &to);
+
+        assert!(diff.title.is_empty());
+        assert_eq!(diff.content.len(), 1);
+
+        let hunk = &diff.content[0];
+        assert_eq!((hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines), (1, 3, 1, 3));
+
+        let kinds: Vec<LineKind> = hunk.lines.iter().map(|line| line.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![LineKind::Context, LineKind::Delete, LineKind::Insert, LineKind::Context]
+        );
+
+        let inserted = &hunk.lines[2];
+        assert_eq!(inserted.text, "second word");
+        let changed: Vec<&str> = inserted
+            .segments
+            .iter()
+            .filter(|segment| segment.changed)
+            .map(|segment| segment.text.as_str())
+            .collect();
+        assert_eq!(changed, vec!["word"]);
+    }
+
+    #[test]
+    fn test_tag_changes() {
+        let from = snapshot(1, "Title", "body", &["rust", "memo"]);
+        let to = snapshot(2, "Title", "body", &["memo", "scylla"]);
+
+        let diff = MemoDiff::between(&from, &to);
+
+        assert_eq!(diff.tags.added, vec!["scylla"]);
+        assert_eq!(diff.tags.removed, vec!["rust"]);
+        assert!(diff.content.is_empty());
+    }
+
+    #[test]
+    fn test_identical_versions_have_no_changes() {
+        let from = snapshot(1, "Title", "body", &["a", "b"]);
+        let to = snapshot(2, "Title", "body", &["b", "a"]);
+
+        assert!(MemoDiff::between(&from, &to).is_empty());
+        assert!(unified_diff(&from, &to).is_empty());
+    }
+
+    #[test]
+    fn test_unified_diff_text() {
+        let from = snapshot(3, "Old", "body\n", &["a"]);
+        let to = snapshot(7, "New", "body\n", &["a"]);
+
+        let text = unified_diff(&from, &to);
+
+        assert!(text.contains("--- a/title (v3)\n+++ b/title (v7)\n"));
+        assert!(text.contains("-Old"));
+        assert!(text.contains("+New"));
+        assert!(!text.contains("a/content"));
+    }
+}
@@ -1,12 +1,16 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use crate::application::account::dto::TagUsage;
+use crate::domain::memo::repository::{MemoSort, SearchFacets, SortOrder};
 
 #[derive(Debug, Deserialize)]
 pub struct CreateMemoDto {
     pub title: String,
     pub content: String,
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub folder_id: Option<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -17,6 +21,12 @@ pub struct UpdateMemoDto {
     pub version: i32,
 }
 
+/// メモのフォルダ移動（`folder_id` が `null` の場合は最上位へ移動）
+#[derive(Debug, Deserialize)]
+pub struct MoveMemoDto {
+    pub folder_id: Option<Uuid>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct MemoResponse {
     pub id: Uuid,
@@ -27,14 +37,354 @@ pub struct MemoResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub version: i32,
+    pub folder_id: Option<Uuid>,
+    pub pinned: bool,
+    pub archived: bool,
+    pub favorite: bool,
 }
 
+/// 本文を含まないメモの一覧項目
 #[derive(Debug, Serialize)]
-pub struct SearchResponse {
-    pub items: Vec<MemoResponse>,
-    pub total: usize,
+pub struct MemoSummaryResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i32,
+    pub folder_id: Option<Uuid>,
+    pub pinned: bool,
+    pub archived: bool,
+    pub favorite: bool,
+}
+
+/// メモ一覧の絞り込み（未指定の条件では絞り込まない）
+///
+/// 日時の範囲は `*_since` 以降、`*_until` より前です。
+#[derive(Debug, Default, Deserialize)]
+pub struct MemoListFilter {
+    pub tag: Option<String>,
+    pub created_since: Option<DateTime<Utc>>,
+    pub created_until: Option<DateTime<Utc>>,
+    pub updated_since: Option<DateTime<Utc>>,
+    pub updated_until: Option<DateTime<Utc>>,
+    pub pinned: Option<bool>,
+    pub archived: Option<bool>,
+    pub favorite: Option<bool>,
+    /// 固定したメモを先頭に並べる
+    #[serde(default)]
+    pub pinned_first: bool,
+}
+
+impl MemoListFilter {
+    pub fn matches(&self, memo: &crate::domain::memo::entity::Memo) -> bool {
+        let flag =
+            |expected: Option<bool>, actual: bool| expected.is_none_or(|expected| expected == actual);
+        let in_range = |at: DateTime<Utc>, since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>| {
+            since.is_none_or(|since| at >= since) && until.is_none_or(|until| at < until)
+        };
+
+        self.tag.as_ref().is_none_or(|tag| memo.tags.contains(tag))
+            && in_range(memo.created_at, self.created_since, self.created_until)
+            && in_range(memo.updated_at, self.updated_since, self.updated_until)
+            && flag(self.pinned, memo.pinned)
+            && flag(self.archived, memo.archived)
+            && flag(self.favorite, memo.favorite)
+    }
+}
+
+/// メモ一覧のページ指定
+#[derive(Debug, Deserialize)]
+pub struct MemoListQuery {
+    #[serde(default)]
+    pub sort: MemoSort,
+    /// 未指定の場合は並び順の基準ごとの既定の向き
+    pub order: Option<SortOrder>,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// 前のページの `next_cursor`
+    pub cursor: Option<String>,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoListResponse<T = MemoResponse> {
+    pub items: Vec<T>,
+    /// 次のページのカーソル（最後のページでは `null`）
+    ///
+    /// 絞り込みで読み飛ばした行が多いと `limit` 件に満たないページや空のページでも続きがあるため、
+    /// `null` になるまで読み進めてください。
+    pub next_cursor: Option<String>,
+}
+
+/// ゴミ箱のメモ（本文は含まない）
+#[derive(Debug, Serialize)]
+pub struct TrashedMemoResponse {
+    #[serde(flatten)]
+    pub memo: MemoSummaryResponse,
+    pub deleted_at: DateTime<Utc>,
+    /// 完全に削除される予定日時
+    pub purge_at: DateTime<Utc>,
+}
+
+/// メモの検索条件とページ指定
+#[derive(Debug, Deserialize)]
+pub struct SearchMemosQuery {
+    /// 検索式（構文は `search::parse_query` を参照）
+    pub query: Option<String>,
+    pub tag: Option<String>,
+    /// 指定フォルダ（配下のフォルダを含む）に絞り込む
+    pub folder: Option<Uuid>,
+    #[serde(default = "default_page")]
     pub page: usize,
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+    /// 前のページの `search_after`（指定した場合は `page` によらずその続きを返す）
+    pub search_after: Option<String>,
+    /// 一致した箇所の前後に挿入するタグ
+    #[serde(default = "default_highlight_pre_tag")]
+    pub highlight_pre_tag: String,
+    #[serde(default = "default_highlight_post_tag")]
+    pub highlight_post_tag: String,
+    /// 本文の断片と抜粋の最大文字数
+    #[serde(default = "default_fragment_size")]
+    pub fragment_size: usize,
+    /// 結果に本文全体を含める
+    #[serde(default)]
+    pub include_content: bool,
+    /// 絞り込み候補の集計を含める
+    #[serde(default = "default_facets")]
+    pub facets: bool,
+}
+
+/// 強調表示に使えるタグ名
+const HIGHLIGHT_TAG_NAMES: [&str; 4] = ["mark", "em", "strong", "b"];
+
+impl SearchMemosQuery {
+    /// 強調表示のタグが許可したものか
+    ///
+    /// 断片はそのままHTMLとして表示されるため、`HIGHLIGHT_TAG_NAMES` のタグと、
+    /// 英数字・`-`・`_` のクラス名だけを指定した開始タグ、対応する終了タグの組に限ります。
+    pub fn has_allowed_highlight_tags(&self) -> bool {
+        let Some(name) = Self::highlight_tag_name(&self.highlight_pre_tag) else {
+            return false;
+        };
+        self.highlight_post_tag == format!("</{}>", name)
+    }
+
+    /// `<name>` または `<name class="...">` のタグ名
+    fn highlight_tag_name(tag: &str) -> Option<&str> {
+        let inner = tag.strip_prefix('<')?.strip_suffix('>')?;
+        let (name, attributes) = match inner.split_once(' ') {
+            Some((name, attributes)) => (name, Some(attributes)),
+            None => (inner, None),
+        };
+        if !HIGHLIGHT_TAG_NAMES.contains(&name) {
+            return None;
+        }
+
+        if let Some(attributes) = attributes {
+            let class = attributes.strip_prefix("class=\"")?.strip_suffix('"')?;
+            let valid = !class.is_empty()
+                && class.split(' ').all(|class| {
+                    !class.is_empty()
+                        && class.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                });
+            if !valid {
+                return None;
+            }
+        }
+
+        Some(name)
+    }
+}
+
+fn default_page() -> usize {
+    1
+}
+
+fn default_highlight_pre_tag() -> String {
+    "<mark>".to_string()
+}
+
+fn default_highlight_post_tag() -> String {
+    "</mark>".to_string()
+}
+
+fn default_fragment_size() -> usize {
+    150
+}
+
+fn default_facets() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResponse<T = MemoResponse> {
+    pub items: Vec<T>,
+    pub total: usize,
+    /// ページ番号（`search_after` で続きを取得した場合は `null`）
+    pub page: Option<usize>,
     pub total_pages: usize,
+    /// 次のページを取得するための `search_after`（検索結果でのみ返す）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_after: Option<String>,
+    /// 絞り込み候補（検索結果でのみ返す）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<SearchFacetsResponse>,
+}
+
+/// 検索条件に一致したメモ全体の集計
+#[derive(Debug, Serialize)]
+pub struct SearchFacetsResponse {
+    pub tags: Vec<TagUsage>,
+    /// 作成月（UTC）ごとのメモ数（古い順）
+    pub created_months: Vec<MonthCount>,
+    pub folders: Vec<FolderCount>,
+    pub states: StateCounts,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonthCount {
+    /// `YYYY-MM`
+    pub month: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FolderCount {
+    pub folder_id: Uuid,
+    pub count: usize,
+}
+
+/// ピン留め・アーカイブ・お気に入りのメモ数
+#[derive(Debug, Serialize)]
+pub struct StateCounts {
+    pub pinned: usize,
+    pub archived: usize,
+    pub favorite: usize,
+}
+
+impl From<SearchFacets> for SearchFacetsResponse {
+    fn from(facets: SearchFacets) -> Self {
+        Self {
+            tags: facets
+                .tags
+                .into_iter()
+                .map(|(tag, count)| TagUsage {
+                    tag,
+                    count: count as usize,
+                })
+                .collect(),
+            created_months: facets
+                .created_months
+                .into_iter()
+                .map(|(month, count)| MonthCount {
+                    month,
+                    count: count as usize,
+                })
+                .collect(),
+            folders: facets
+                .folders
+                .into_iter()
+                .map(|(folder_id, count)| FolderCount {
+                    folder_id,
+                    count: count as usize,
+                })
+                .collect(),
+            states: StateCounts {
+                pinned: facets.pinned as usize,
+                archived: facets.archived as usize,
+                favorite: facets.favorite as usize,
+            },
+        }
+    }
+}
+
+/// 検索結果の1件
+///
+/// 本文全体は `include_content` を指定した場合のみ含め、一覧の表示には `snippet` を使います。
+#[derive(Debug, Serialize)]
+pub struct SearchHitResponse {
+    pub id: Uuid,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    pub tags: Vec<String>,
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i32,
+    pub folder_id: Option<Uuid>,
+    pub pinned: bool,
+    pub archived: bool,
+    pub favorite: bool,
+    pub highlights: SearchHighlights,
+    /// 最も一致した箇所の周辺の本文（プレーンテキスト）
+    pub snippet: String,
+}
+
+/// 一致した箇所を強調した断片（HTMLエスケープ済み）
+#[derive(Debug, Serialize)]
+pub struct SearchHighlights {
+    pub title: Vec<String>,
+    pub content: Vec<String>,
+}
+
+/// 補完候補の取得条件
+#[derive(Debug, Deserialize)]
+pub struct SuggestQuery {
+    /// 入力途中の検索語
+    #[serde(default)]
+    pub q: String,
+    #[serde(default = "default_suggest_limit")]
+    pub limit: usize,
+}
+
+fn default_suggest_limit() -> usize {
+    5
+}
+
+/// 入力途中の検索語に対する補完候補
+#[derive(Debug, Serialize)]
+pub struct SuggestResponse {
+    pub titles: Vec<TitleSuggestion>,
+    pub tags: Vec<TagUsage>,
+    /// 前方一致した最近の検索語（新しい順）
+    pub recent_queries: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TitleSuggestion {
+    pub id: Uuid,
+    pub title: String,
+}
+
+impl SearchResponse {
+    /// メモ一覧から指定ページを切り出す（`page` は1始まり）
+    pub fn paginate(memos: Vec<crate::domain::memo::entity::Memo>, page: usize, limit: usize) -> Self {
+        let limit = limit.clamp(1, 100);
+        let page = page.max(1);
+        let total = memos.len();
+
+        Self {
+            items: memos
+                .into_iter()
+                .skip((page - 1) * limit)
+                .take(limit)
+                .map(MemoResponse::from)
+                .collect(),
+            total,
+            page: Some(page),
+            total_pages: total.div_ceil(limit),
+            search_after: None,
+            facets: None,
+        }
+    }
 }
 
 impl From<crate::domain::memo::entity::Memo> for MemoResponse {
@@ -48,6 +398,180 @@ impl From<crate::domain::memo::entity::Memo> for MemoResponse {
             created_at: memo.created_at,
             updated_at: memo.updated_at,
             version: memo.version,
+            folder_id: memo.folder_id,
+            pinned: memo.pinned,
+            archived: memo.archived,
+            favorite: memo.favorite,
+        }
+    }
+}
+
+impl From<crate::domain::memo::entity::Memo> for MemoSummaryResponse {
+    fn from(memo: crate::domain::memo::entity::Memo) -> Self {
+        Self {
+            id: memo.id,
+            title: memo.title,
+            tags: memo.tags,
+            user_id: memo.user_id,
+            created_at: memo.created_at,
+            updated_at: memo.updated_at,
+            version: memo.version,
+            folder_id: memo.folder_id,
+            pinned: memo.pinned,
+            archived: memo.archived,
+            favorite: memo.favorite,
+        }
+    }
+}
+
+/// バージョン履歴の一覧項目（本文は含まない）
+#[derive(Debug, Serialize)]
+pub struct MemoVersionSummary {
+    pub version: i32,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl From<crate::domain::memo::entity::MemoVersion> for MemoVersionSummary {
+    fn from(snapshot: crate::domain::memo::entity::MemoVersion) -> Self {
+        Self {
+            version: snapshot.version,
+            title: snapshot.title,
+            tags: snapshot.tags,
+            saved_at: snapshot.saved_at,
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoVersionResponse {
+    pub memo_id: Uuid,
+    pub version: i32,
+    pub title: String,
+    pub content: String,
+    pub tags: Vec<String>,
+    pub saved_at: DateTime<Utc>,
+}
+
+impl From<crate::domain::memo::entity::MemoVersion> for MemoVersionResponse {
+    fn from(snapshot: crate::domain::memo::entity::MemoVersion) -> Self {
+        Self {
+            memo_id: snapshot.memo_id,
+            version: snapshot.version,
+            title: snapshot.title,
+            content: snapshot.content,
+            tags: snapshot.tags,
+            saved_at: snapshot.saved_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::memo::entity::{Memo, MemoFlag};
+
+    fn memo(title: &str, flags: &[MemoFlag]) -> Memo {
+        let mut memo = Memo::new(title.into(), "content".into(), vec![], Uuid::nil());
+        for flag in flags {
+            memo.set_flag(*flag, true);
+        }
+        memo
+    }
+
+    #[test]
+    fn test_list_filter_by_flags() {
+        let filter = MemoListFilter {
+            archived: Some(false),
+            favorite: Some(true),
+            ..Default::default()
+        };
+
+        assert!(filter.matches(&memo("a", &[MemoFlag::Favorite, MemoFlag::Pinned])));
+        assert!(!filter.matches(&memo("b", &[MemoFlag::Favorite, MemoFlag::Archived])));
+        assert!(!filter.matches(&memo("c", &[])));
+    }
+
+    #[test]
+    fn test_list_filter_by_tag_and_date_range() {
+        let mut memo = memo("a", &[]);
+        memo.tags = vec!["rust".into()];
+        let created_at = memo.created_at;
+
+        let filter = MemoListFilter {
+            tag: Some("rust".into()),
+            created_since: Some(created_at),
+            created_until: Some(created_at + chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(filter.matches(&memo));
+
+        // 終了日時ちょうどは範囲外
+        let filter = MemoListFilter {
+            created_until: Some(created_at),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&memo));
+
+        let filter = MemoListFilter {
+            tag: Some("scylla".into()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&memo));
+    }
+
+    fn search_query(pre_tag: &str, post_tag: &str) -> SearchMemosQuery {
+        serde_json::from_value(serde_json::json!({
+            "highlight_pre_tag": pre_tag,
+            "highlight_post_tag": post_tag,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_allowed_highlight_tags() {
+        assert!(search_query("<mark>", "</mark>").has_allowed_highlight_tags());
+        assert!(search_query("<em>", "</em>").has_allowed_highlight_tags());
+        assert!(search_query("<strong class=\"hit primary\">", "</strong>").has_allowed_highlight_tags());
+
+        // 許可していないタグや属性、対応しない終了タグは拒否
+        assert!(!search_query("<script>", "</script>").has_allowed_highlight_tags());
+        assert!(!search_query("<mark onmouseover=\"alert(1)\">", "</mark>").has_allowed_highlight_tags());
+        assert!(!search_query("<mark class=\"a\" onclick=\"b\">", "</mark>").has_allowed_highlight_tags());
+        assert!(!search_query("<mark class=\"\">", "</mark>").has_allowed_highlight_tags());
+        assert!(!search_query("<mark>", "</em>").has_allowed_highlight_tags());
+        assert!(!search_query("[", "]").has_allowed_highlight_tags());
+    }
+
+    #[test]
+    fn test_search_hit_omits_content_unless_requested() {
+        let memo = memo("a", &[]);
+        let hit = |content: Option<String>| SearchHitResponse {
+            id: memo.id,
+            title: memo.title.clone(),
+            content,
+            tags: memo.tags.clone(),
+            user_id: memo.user_id,
+            created_at: memo.created_at,
+            updated_at: memo.updated_at,
+            version: memo.version,
+            folder_id: memo.folder_id,
+            pinned: memo.pinned,
+            archived: memo.archived,
+            favorite: memo.favorite,
+            highlights: SearchHighlights {
+                title: vec![],
+                content: vec!["<mark>content</mark>".into()],
+            },
+            snippet: "content".into(),
+        };
+
+        let json = serde_json::to_value(hit(None)).unwrap();
+        assert!(json.get("content").is_none());
+        assert_eq!(json["highlights"]["content"][0], "<mark>content</mark>");
+
+        let json = serde_json::to_value(hit(Some(memo.content.clone()))).unwrap();
+        assert_eq!(json["content"], "content");
+    }
+}
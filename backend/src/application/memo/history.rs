@@ -0,0 +1,103 @@
+// src/application/memo/history.rs
+
+use std::{sync::Arc, time::Duration};
+use chrono::Utc;
+use uuid::Uuid;
+use crate::{error::AppResult, infrastructure::persistence::redis::RedisCache};
+
+/// ユーザーごとに保持する検索語の件数
+const MAX_ENTRIES: isize = 20;
+/// 最後に検索してから履歴を保持する期間
+const HISTORY_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// ユーザーごとの最近の検索語（新しい順）
+///
+/// 検索語をメンバー、最後に検索した時刻をスコアとするソート済みセットに保存します。
+pub struct SearchHistory {
+    redis: Arc<RedisCache>,
+}
+
+impl SearchHistory {
+    pub fn new(redis: Arc<RedisCache>) -> Self {
+        Self { redis }
+    }
+
+    fn key(user_id: Uuid) -> String {
+        format!("search_history:{}", user_id)
+    }
+
+    /// 検索語を記録（同じ検索語は最後に検索した時刻に更新）
+    pub async fn record(&self, user_id: Uuid, query: &str) -> AppResult<()> {
+        let Some(query) = normalize_query(query) else {
+            return Ok(());
+        };
+
+        let key = Self::key(user_id);
+        self.redis
+            .add_to_sorted_set(&key, query, Utc::now().timestamp_millis())
+            .await?;
+        self.redis.trim_sorted_set(&key, MAX_ENTRIES).await?;
+        self.redis.expire(&key, HISTORY_TTL).await
+    }
+
+    /// `prefix` で始まる最近の検索語（大文字・小文字は区別しない）
+    pub async fn matching(&self, user_id: Uuid, prefix: &str, limit: usize) -> AppResult<Vec<String>> {
+        let queries = self
+            .redis
+            .sorted_set_latest(&Self::key(user_id), MAX_ENTRIES)
+            .await?;
+        Ok(filter_by_prefix(queries, prefix, limit))
+    }
+
+    /// ユーザーの検索履歴をすべて削除
+    pub async fn clear(&self, user_id: Uuid) -> AppResult<()> {
+        self.redis.delete(&Self::key(user_id)).await
+    }
+}
+
+/// 記録する検索語（前後の空白を除き、空なら記録しない）
+fn normalize_query(query: &str) -> Option<&str> {
+    let query = query.trim();
+    (!query.is_empty()).then_some(query)
+}
+
+/// 新しい順の検索語から `prefix` で始まるものを最大 `limit` 件選ぶ
+fn filter_by_prefix(queries: Vec<String>, prefix: &str, limit: usize) -> Vec<String> {
+    let prefix = prefix.to_lowercase();
+
+    queries
+        .into_iter()
+        .filter(|query| query.to_lowercase().starts_with(&prefix))
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_query() {
+        assert_eq!(normalize_query("  rust  "), Some("rust"));
+        assert_eq!(normalize_query("   "), None);
+        assert_eq!(normalize_query(""), None);
+    }
+
+    #[test]
+    fn test_filter_by_prefix_keeps_order_and_ignores_case() {
+        let queries = vec![
+            "Rust async".to_string(),
+            "python".to_string(),
+            "rust macro".to_string(),
+            "RUST".to_string(),
+        ];
+
+        assert_eq!(
+            filter_by_prefix(queries.clone(), "rust", 10),
+            vec!["Rust async", "rust macro", "RUST"]
+        );
+        assert_eq!(filter_by_prefix(queries.clone(), "Rust", 2), vec!["Rust async", "rust macro"]);
+        assert_eq!(filter_by_prefix(queries.clone(), "", 10).len(), 4);
+        assert!(filter_by_prefix(queries, "go", 10).is_empty());
+    }
+}
@@ -0,0 +1,260 @@
+// src/application/memo/merge.rs
+
+use serde::Serialize;
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+const CURRENT_MARKER: &str = "<<<<<<< current";
+const SEPARATOR_MARKER: &str = "=======";
+const INCOMING_MARKER: &str = ">>>>>>> incoming";
+
+/// マージ対象となるメモの内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoFields {
+    pub title: String,
+    pub content: String,
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoField {
+    Title,
+    Content,
+}
+
+/// 3方向マージの結果
+///
+/// 競合した項目は両方の変更を競合マーカーで囲んだ候補になります。
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct MergeResult {
+    pub title: String,
+    pub content: String,
+    pub tags: Vec<String>,
+    pub conflicts: Vec<MemoField>,
+}
+
+impl MergeResult {
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// 共通の基底バージョンをもとに、保存済みの内容と送信された内容をマージ
+pub fn merge_memo(base: &MemoFields, current: &MemoFields, incoming: &MemoFields) -> MergeResult {
+    let mut conflicts = Vec::new();
+
+    let (title, title_conflicted) = merge_text(&base.title, &current.title, &incoming.title);
+    if title_conflicted {
+        conflicts.push(MemoField::Title);
+    }
+
+    let (content, content_conflicted) = merge_text(&base.content, &current.content, &incoming.content);
+    if content_conflicted {
+        conflicts.push(MemoField::Content);
+    }
+
+    MergeResult {
+        title,
+        content,
+        tags: merge_tags(&base.tags, &current.tags, &incoming.tags),
+        conflicts,
+    }
+}
+
+/// 行単位の3方向マージ（diff3）
+///
+/// 戻り値の2つ目は競合があったかどうか。
+pub fn merge_text(base: &str, current: &str, incoming: &str) -> (String, bool) {
+    if current == incoming || incoming == base {
+        return (current.to_string(), false);
+    }
+    if current == base {
+        return (incoming.to_string(), false);
+    }
+
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let current_lines: Vec<&str> = current.split_inclusive('\n').collect();
+    let incoming_lines: Vec<&str> = incoming.split_inclusive('\n').collect();
+
+    let current_matches = matching_lines(&base_lines, &current_lines);
+    let incoming_matches = matching_lines(&base_lines, &incoming_lines);
+
+    let mut merged = String::new();
+    let mut conflicted = false;
+    let (mut i, mut j, mut k) = (0, 0, 0);
+
+    loop {
+        // 両方の変更で基底の行が残っている次の位置（安定点）を探す
+        let stable = (i..base_lines.len())
+            .find_map(|m| Some((m, current_matches[m]?, incoming_matches[m]?)));
+        let (m, next_j, next_k) =
+            stable.unwrap_or((base_lines.len(), current_lines.len(), incoming_lines.len()));
+
+        let base_chunk = &base_lines[i..m];
+        let current_chunk = &current_lines[j..next_j];
+        let incoming_chunk = &incoming_lines[k..next_k];
+
+        if current_chunk == base_chunk || current_chunk == incoming_chunk {
+            incoming_chunk.iter().for_each(|line| merged.push_str(line));
+        } else if incoming_chunk == base_chunk {
+            current_chunk.iter().for_each(|line| merged.push_str(line));
+        } else {
+            conflicted = true;
+            push_conflict(&mut merged, current_chunk, incoming_chunk);
+        }
+
+        if stable.is_none() {
+            break;
+        }
+
+        // 安定点の行は共通なのでそのまま出力
+        merged.push_str(base_lines[m]);
+        (i, j, k) = (m + 1, next_j + 1, next_k + 1);
+    }
+
+    (merged, conflicted)
+}
+
+/// タグの集合としてのマージ（送信側で追加・削除されたタグを保存済みのタグに適用）
+pub fn merge_tags(base: &[String], current: &[String], incoming: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = current
+        .iter()
+        .filter(|tag| incoming.contains(tag) || !base.contains(tag))
+        .cloned()
+        .collect();
+
+    for tag in incoming {
+        if !base.contains(tag) && !merged.contains(tag) {
+            merged.push(tag.clone());
+        }
+    }
+
+    merged
+}
+
+/// 基底の各行が相手側の何行目に対応するか
+fn matching_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal { old_index, new_index, len } = op {
+            for offset in 0..len {
+                matches[old_index + offset] = Some(new_index + offset);
+            }
+        }
+    }
+
+    matches
+}
+
+fn push_conflict(merged: &mut String, current: &[&str], incoming: &[&str]) {
+    let push_block = |merged: &mut String, lines: &[&str]| {
+        lines.iter().for_each(|line| merged.push_str(line));
+        if !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+    };
+
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    merged.push_str(CURRENT_MARKER);
+    merged.push('\n');
+    push_block(merged, current);
+    merged.push_str(SEPARATOR_MARKER);
+    merged.push('\n');
+    push_block(merged, incoming);
+    merged.push_str(INCOMING_MARKER);
+    merged.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_non_overlapping_edits_merge_cleanly() {
+        let base = "one\ntwo\nthree\nfour\nfive\n";
+        let current = "ONE\ntwo\nthree\nfour\nfive\n";
+        let incoming = "one\ntwo\nthree\nfour\nFIVE\nsix\n";
+
+        let (merged, conflicted) = merge_text(base, current, incoming);
+
+        assert!(!conflicted);
+        assert_eq!(merged, "ONE\ntwo\nthree\nfour\nFIVE\nsix\n");
+    }
+
+    #[test]
+    fn test_identical_edits_are_not_conflicts() {
+        let base = "one\ntwo\n";
+        let edited = "one\n2\n";
+
+        assert_eq!(merge_text(base, edited, edited), (edited.to_string(), false));
+    }
+
+    #[test]
+    fn test_overlapping_edits_produce_conflict_markers() {
+        let base = "one\ntwo\nthree\n";
+        let current = "one\nTWO\nthree\n";
+        let incoming = "one\nzwei\nthree\n";
+
+        let (merged, conflicted) = merge_text(base, current, incoming);
+
+        assert!(conflicted);
+        assert_eq!(
+            merged,
+            "one\n<<<<<<< current\nTWO\n=======\nzwei\n>>>>>>> incoming\nthree\n"
+        );
+    }
+
+    #[test]
+    fn test_conflict_without_trailing_newline() {
+        let (merged, conflicted) = merge_text("title", "current title", "incoming title");
+
+        assert!(conflicted);
+        assert_eq!(
+            merged,
+            "<<<<<<< current\ncurrent title\n=======\nincoming title\n>>>>>>> incoming\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_tags_applies_incoming_changes() {
+        let merged = merge_tags(
+            &tags(&["a", "b", "c"]),
+            &tags(&["a", "b", "c", "d"]),
+            &tags(&["b", "c", "e"]),
+        );
+
+        assert_eq!(merged, tags(&["b", "c", "d", "e"]));
+    }
+
+    #[test]
+    fn test_merge_memo_reports_conflicting_fields() {
+        let base = MemoFields {
+            title: "Title".into(),
+            content: "line\n".into(),
+            tags: tags(&["a"]),
+        };
+        let current = MemoFields {
+            title: "Current".into(),
+            content: "line\nadded by current\n".into(),
+            tags: tags(&["a"]),
+        };
+        let incoming = MemoFields {
+            title: "Incoming".into(),
+            content: "line\n".into(),
+            tags: tags(&["a", "b"]),
+        };
+
+        let result = merge_memo(&base, &current, &incoming);
+
+        assert_eq!(result.conflicts, vec![MemoField::Title]);
+        assert_eq!(result.content, "line\nadded by current\n");
+        assert_eq!(result.tags, tags(&["a", "b"]));
+    }
+}
@@ -1,2 +1,8 @@
+pub mod diff;
 pub mod dto;
+pub mod history;
+pub mod merge;
+pub mod search;
 pub mod service;
+pub mod snippet;
+pub mod trash;
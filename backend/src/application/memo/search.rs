@@ -0,0 +1,512 @@
+// src/application/memo/search.rs
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use crate::{
+    domain::memo::repository::{DateField, SearchExpr, TextField},
+    error::{AppError, AppResult},
+};
+
+/// 括弧と否定を入れ子にできる深さの上限
+const MAX_DEPTH: usize = 32;
+
+/// 検索ボックスに入力された検索式を解析
+///
+/// * 空白で区切った語句はすべてに一致するもの（AND）、`OR` はいずれかに一致するもの
+/// * `NOT` または先頭の `-` で否定、`( )` でまとめる（優先順位は NOT、AND、OR の順）
+/// * `"..."` は語順どおりのフレーズ
+/// * `title:`、`content:`、`tag:` でフィールドを指定
+/// * `created:`、`updated:` に `>`、`>=`、`<`、`<=` と日付（`YYYY-MM-DD`）、
+///   または `2024-01-01..2024-03-31` の範囲を指定。`before:`、`after:` は作成日の比較
+///
+/// 日付は UTC の日単位で比較します。空の検索式は `None` です。
+/// 解析できない場合は位置（1文字目を1とする文字数）を含めた BadRequest を返します。
+pub fn parse_query(input: &str) -> AppResult<Option<SearchExpr>> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Ok(None);
+    }
+
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        end: input.chars().count() + 1,
+    };
+    let expr = parser.parse_or(0)?;
+    if let Some(token) = parser.peek() {
+        return Err(parse_error(token.position, format!("Unexpected {}", token.kind.describe())));
+    }
+
+    Ok(Some(expr))
+}
+
+fn parse_error(position: usize, message: impl AsRef<str>) -> AppError {
+    AppError::BadRequest(format!(
+        "Invalid search query at position {}: {}",
+        position,
+        message.as_ref()
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Content,
+    Tag,
+    Created,
+    Updated,
+    Before,
+    After,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "title" => Some(Self::Title),
+            "content" => Some(Self::Content),
+            "tag" => Some(Self::Tag),
+            "created" => Some(Self::Created),
+            "updated" => Some(Self::Updated),
+            "before" => Some(Self::Before),
+            "after" => Some(Self::After),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    /// 語句の直前の `-`
+    Minus,
+    Word(String),
+    Phrase(String),
+    /// `title:` などのフィールド指定（直後に値が続く）
+    Field(Field),
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            Self::LParen => "'('".to_string(),
+            Self::RParen => "')'".to_string(),
+            Self::And => "'AND'".to_string(),
+            Self::Or => "'OR'".to_string(),
+            Self::Not => "'NOT'".to_string(),
+            Self::Minus => "'-'".to_string(),
+            Self::Word(word) => format!("'{}'", word),
+            Self::Phrase(phrase) => format!("\"{}\"", phrase),
+            Self::Field(_) => "field".to_string(),
+        }
+    }
+
+    /// 否定や語句の始まりになるトークンか
+    fn starts_operand(&self) -> bool {
+        !matches!(self, Self::RParen | Self::And | Self::Or)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// 入力内の位置（1文字目を1とする）
+    position: usize,
+}
+
+fn tokenize(input: &str) -> AppResult<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let position = i + 1;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '(' => {
+                tokens.push(Token { kind: TokenKind::LParen, position });
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token { kind: TokenKind::RParen, position });
+                i += 1;
+            }
+            '"' => {
+                let (phrase, next) = read_phrase(&chars, i)?;
+                tokens.push(Token { kind: TokenKind::Phrase(phrase), position });
+                i = next;
+            }
+            '-' if chars.get(i + 1).is_some_and(|next| !next.is_whitespace()) => {
+                tokens.push(Token { kind: TokenKind::Minus, position });
+                i += 1;
+            }
+            _ => {
+                let start = i;
+                while i < chars.len() && !is_delimiter(chars[i]) {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+
+                let field = word
+                    .split_once(':')
+                    .and_then(|(name, value)| Some((Field::from_name(name)?, name, value)));
+                if let Some((field, name, value)) = field {
+                    tokens.push(Token { kind: TokenKind::Field(field), position });
+
+                    let value_position = position + name.chars().count() + 1;
+                    if !value.is_empty() {
+                        tokens.push(Token {
+                            kind: TokenKind::Word(value.to_string()),
+                            position: value_position,
+                        });
+                    } else if chars.get(i) == Some(&'"') {
+                        let (phrase, next) = read_phrase(&chars, i)?;
+                        tokens.push(Token {
+                            kind: TokenKind::Phrase(phrase),
+                            position: value_position,
+                        });
+                        i = next;
+                    } else {
+                        return Err(parse_error(
+                            value_position,
+                            format!("Expected a value after '{}:'", name),
+                        ));
+                    }
+                    continue;
+                }
+
+                let kind = match word.as_str() {
+                    "AND" => TokenKind::And,
+                    "OR" => TokenKind::Or,
+                    "NOT" => TokenKind::Not,
+                    _ => TokenKind::Word(word),
+                };
+                tokens.push(Token { kind, position });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '"')
+}
+
+/// `start` の `"` から閉じる `"` までを読む（`\"` と `\\` はエスケープ）
+fn read_phrase(chars: &[char], start: usize) -> AppResult<(String, usize)> {
+    let mut phrase = String::new();
+    let mut i = start + 1;
+
+    while i < chars.len() {
+        match chars[i] {
+            '"' if phrase.trim().is_empty() => return Err(parse_error(start + 1, "Empty phrase")),
+            '"' => return Ok((phrase, i + 1)),
+            '\\' if matches!(chars.get(i + 1), Some('"' | '\\')) => {
+                phrase.push(chars[i + 1]);
+                i += 2;
+            }
+            c => {
+                phrase.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    Err(parse_error(start + 1, "Unterminated phrase"))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    /// 入力の末尾の位置
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect_operand(&mut self) -> AppResult<Token> {
+        self.advance()
+            .ok_or_else(|| parse_error(self.end, "Unexpected end of query"))
+    }
+
+    fn parse_or(&mut self, depth: usize) -> AppResult<SearchExpr> {
+        let mut operands = vec![self.parse_and(depth)?];
+        while self.peek().is_some_and(|token| token.kind == TokenKind::Or) {
+            self.position += 1;
+            operands.push(self.parse_and(depth)?);
+        }
+
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            SearchExpr::Or(operands)
+        })
+    }
+
+    fn parse_and(&mut self, depth: usize) -> AppResult<SearchExpr> {
+        let mut operands = vec![self.parse_unary(depth)?];
+        loop {
+            match self.peek().map(|token| &token.kind) {
+                Some(TokenKind::And) => {
+                    self.position += 1;
+                }
+                // 演算子を省略した並びは AND
+                Some(kind) if kind.starts_operand() => {}
+                _ => break,
+            }
+            operands.push(self.parse_unary(depth)?);
+        }
+
+        Ok(if operands.len() == 1 {
+            operands.remove(0)
+        } else {
+            SearchExpr::And(operands)
+        })
+    }
+
+    fn parse_unary(&mut self, depth: usize) -> AppResult<SearchExpr> {
+        let token = self.expect_operand()?;
+        if depth >= MAX_DEPTH {
+            return Err(parse_error(token.position, "Query is nested too deeply"));
+        }
+
+        match token.kind {
+            TokenKind::Not | TokenKind::Minus => {
+                Ok(SearchExpr::Not(Box::new(self.parse_unary(depth + 1)?)))
+            }
+            TokenKind::LParen => {
+                let expr = self.parse_or(depth + 1)?;
+                match self.advance() {
+                    Some(Token { kind: TokenKind::RParen, .. }) => Ok(expr),
+                    _ => Err(parse_error(token.position, "Unclosed parenthesis")),
+                }
+            }
+            TokenKind::Word(text) => Ok(SearchExpr::Text {
+                field: TextField::Any,
+                text,
+                phrase: false,
+            }),
+            TokenKind::Phrase(text) => Ok(SearchExpr::Text {
+                field: TextField::Any,
+                text,
+                phrase: true,
+            }),
+            TokenKind::Field(field) => self.parse_field(field),
+            kind => Err(parse_error(token.position, format!("Unexpected {}", kind.describe()))),
+        }
+    }
+
+    /// フィールド指定の値（字句解析で直後に値があることを確認済み）
+    fn parse_field(&mut self, field: Field) -> AppResult<SearchExpr> {
+        let token = self.expect_operand()?;
+        let (value, phrase) = match token.kind {
+            TokenKind::Word(value) => (value, false),
+            TokenKind::Phrase(value) => (value, true),
+            kind => return Err(parse_error(token.position, format!("Unexpected {}", kind.describe()))),
+        };
+
+        let date_field = match field {
+            Field::Title | Field::Content => {
+                return Ok(SearchExpr::Text {
+                    field: if field == Field::Title { TextField::Title } else { TextField::Content },
+                    text: value,
+                    phrase,
+                })
+            }
+            Field::Tag => return Ok(SearchExpr::Tag(value)),
+            Field::Created | Field::Before | Field::After => DateField::CreatedAt,
+            Field::Updated => DateField::UpdatedAt,
+        };
+
+        let (from, until) = match field {
+            Field::Before => (None, Some(start_of_day(parse_date(&value, token.position)?))),
+            Field::After => (Some(day_after(parse_date(&value, token.position)?, token.position)?), None),
+            _ => parse_date_comparison(&value, token.position)?,
+        };
+
+        Ok(SearchExpr::Date {
+            field: date_field,
+            from,
+            until,
+        })
+    }
+}
+
+type DateRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// `>=2024-01-01` などの比較、または `2024-01-01..2024-03-31` の範囲
+fn parse_date_comparison(value: &str, position: usize) -> AppResult<DateRange> {
+    if let Some((start, end)) = value.split_once("..") {
+        let end_position = position + start.chars().count() + 2;
+        let start = parse_date(start, position)?;
+        let end = parse_date(end, end_position)?;
+        if end < start {
+            return Err(parse_error(position, "The end of a date range must not precede its start"));
+        }
+        return Ok((Some(start_of_day(start)), Some(day_after(end, position)?)));
+    }
+
+    let (operator, date, date_position) = match [">=", "<=", ">", "<", "="]
+        .into_iter()
+        .find_map(|operator| Some((operator, value.strip_prefix(operator)?)))
+    {
+        Some((operator, date)) => (operator, date, position + operator.len()),
+        None => ("=", value, position),
+    };
+    let date = parse_date(date, date_position)?;
+
+    Ok(match operator {
+        ">=" => (Some(start_of_day(date)), None),
+        ">" => (Some(day_after(date, position)?), None),
+        "<=" => (None, Some(day_after(date, position)?)),
+        "<" => (None, Some(start_of_day(date))),
+        _ => (Some(start_of_day(date)), Some(day_after(date, position)?)),
+    })
+}
+
+fn parse_date(value: &str, position: usize) -> AppResult<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| parse_error(position, format!("Invalid date '{}'; expected YYYY-MM-DD", value)))
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+}
+
+fn day_after(date: NaiveDate, position: usize) -> AppResult<DateTime<Utc>> {
+    date.checked_add_days(Days::new(1))
+        .map(start_of_day)
+        .ok_or_else(|| parse_error(position, "Date is out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> SearchExpr {
+        SearchExpr::Text {
+            field: TextField::Any,
+            text: text.to_string(),
+            phrase: false,
+        }
+    }
+
+    fn day(value: &str) -> DateTime<Utc> {
+        start_of_day(NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap())
+    }
+
+    fn error_message(input: &str) -> String {
+        match parse_query(input) {
+            Err(AppError::BadRequest(message)) => message,
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_field_operators() {
+        let expr = parse_query(r#"tag:rust before:2024-06-01 title:"design doc" -draft"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            expr,
+            SearchExpr::And(vec![
+                SearchExpr::Tag("rust".into()),
+                SearchExpr::Date {
+                    field: DateField::CreatedAt,
+                    from: None,
+                    until: Some(day("2024-06-01")),
+                },
+                SearchExpr::Text {
+                    field: TextField::Title,
+                    text: "design doc".into(),
+                    phrase: true,
+                },
+                SearchExpr::Not(Box::new(text("draft"))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_operator_precedence() {
+        let expr = parse_query("a b OR NOT c AND (d OR e)").unwrap().unwrap();
+
+        assert_eq!(
+            expr,
+            SearchExpr::Or(vec![
+                SearchExpr::And(vec![text("a"), text("b")]),
+                SearchExpr::And(vec![
+                    SearchExpr::Not(Box::new(text("c"))),
+                    SearchExpr::Or(vec![text("d"), text("e")]),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_date_comparisons() {
+        let range = |input: &str| match parse_query(input).unwrap().unwrap() {
+            SearchExpr::Date { from, until, .. } => (from, until),
+            other => panic!("expected a date, got {:?}", other),
+        };
+
+        assert_eq!(range("created:>=2024-01-01"), (Some(day("2024-01-01")), None));
+        assert_eq!(range("created:>2024-01-01"), (Some(day("2024-01-02")), None));
+        assert_eq!(range("updated:<2024-01-01"), (None, Some(day("2024-01-01"))));
+        assert_eq!(range("updated:<=2024-01-01"), (None, Some(day("2024-01-02"))));
+        assert_eq!(range("created:2024-02-29"), (Some(day("2024-02-29")), Some(day("2024-03-01"))));
+        assert_eq!(
+            range("created:2024-01-01..2024-03-31"),
+            (Some(day("2024-01-01")), Some(day("2024-04-01")))
+        );
+        assert_eq!(range("after:2024-06-01"), (Some(day("2024-06-02")), None));
+    }
+
+    #[test]
+    fn test_parse_keeps_unknown_prefixes_and_hyphens_as_text() {
+        let expr = parse_query("https://example.com e-mail - x").unwrap().unwrap();
+
+        assert_eq!(
+            expr,
+            SearchExpr::And(vec![
+                text("https://example.com"),
+                text("e-mail"),
+                text("-"),
+                text("x"),
+            ])
+        );
+        assert_eq!(parse_query("   ").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        assert!(error_message(r#"title:"design doc"#).contains("position 7: Unterminated phrase"));
+        assert!(error_message("(a OR b").contains("position 1: Unclosed parenthesis"));
+        assert!(error_message("a OR").contains("position 5: Unexpected end of query"));
+        assert!(error_message("OR a").contains("position 1: Unexpected 'OR'"));
+        assert!(error_message("a )").contains("position 3: Unexpected ')'"));
+        assert!(error_message("tag: rust").contains("position 5: Expected a value after 'tag:'"));
+        assert!(error_message("created:>=2024-13-01").contains("position 11: Invalid date"));
+        assert!(error_message(&"(".repeat(40)).contains("nested too deeply"));
+    }
+}
@@ -1,98 +1,698 @@
 use std::sync::Arc;
 use uuid::Uuid;
 use crate::{
-    domain::memo::{entity::Memo, repository::MemoRepository},
+    application::{account::dto::TagUsage, auth::AuthenticatedUser},
+    domain::api_key::entity::ApiScope,
+    domain::user::role::Permission,
+    domain::folder::repository::FolderRepository,
+    domain::memo::{
+        entity::{Memo, MemoFlag, MemoVersion},
+        repository::{HighlightOptions, MemoPage, MemoRepository, MemoSearch, SearchPaging},
+    },
     error::{AppError, AppResult},
 };
-use super::dto::{CreateMemoDto, UpdateMemoDto, MemoResponse, SearchResponse};
+use super::diff::{self, MemoDiff};
+use super::merge::{self, MemoFields};
+use super::history::SearchHistory;
+use super::search::parse_query;
+use super::snippet::snippet;
+use super::trash::MemoTrash;
+use super::dto::{
+    CreateMemoDto, UpdateMemoDto, MoveMemoDto, MemoListFilter, MemoListQuery, MemoListResponse, MemoResponse,
+    MemoSummaryResponse, SearchFacetsResponse, SearchHighlights, SearchHitResponse, SearchMemosQuery,
+    MemoVersionResponse, MemoVersionSummary, SearchResponse, SuggestQuery, SuggestResponse, TitleSuggestion,
+    TrashedMemoResponse,
+};
+
+/// メモ一覧・検索結果の1ページの最大件数
+const MAX_LIST_LIMIT: usize = 100;
+/// ページ番号で取得できる検索結果の範囲（Elasticsearch の `index.max_result_window`）
+const MAX_SEARCH_WINDOW: usize = 10_000;
+/// 強調表示のタグの最大文字数
+const MAX_HIGHLIGHT_TAG_LENGTH: usize = 32;
+/// 補完候補の種類ごとの最大件数
+const MAX_SUGGEST_LIMIT: usize = 10;
+/// 補完候補を求める検索語の最大文字数
+const MAX_SUGGEST_PREFIX_LENGTH: usize = 100;
 
 pub struct MemoService {
     memo_repository: Arc<dyn MemoRepository>,
+    folder_repository: Arc<dyn FolderRepository>,
+    trash: Arc<MemoTrash>,
+    history: Arc<SearchHistory>,
 }
 
 impl MemoService {
-    pub fn new(memo_repository: Arc<dyn MemoRepository>) -> Self {
-        Self { memo_repository }
+    pub fn new(
+        memo_repository: Arc<dyn MemoRepository>,
+        folder_repository: Arc<dyn FolderRepository>,
+        trash: Arc<MemoTrash>,
+        history: Arc<SearchHistory>,
+    ) -> Self {
+        Self {
+            memo_repository,
+            folder_repository,
+            trash,
+            history,
+        }
+    }
+
+    pub async fn create_memo(&self, dto: CreateMemoDto, user: &AuthenticatedUser) -> AppResult<MemoResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        if let Some(folder_id) = dto.folder_id {
+            self.require_folder(user.user_id, folder_id).await?;
+        }
+
+        let mut memo = Memo::new(dto.title, dto.content, dto.tags, user.user_id);
+        memo.move_to_folder(dto.folder_id);
+        self.memo_repository.create(&memo).await?;
+        Ok(MemoResponse::from(memo))
     }
 
-    pub async fn create_memo(&self, dto: CreateMemoDto, user_id: Uuid) -> AppResult<MemoResponse> {
-        let memo = Memo::new(dto.title, dto.content, dto.tags, user_id);
-        self.memo_repository.save(&memo).await?;
+    /// メモを別のフォルダへ移動
+    pub async fn move_memo(&self, id: Uuid, dto: MoveMemoDto, user: &AuthenticatedUser) -> AppResult<MemoResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        let mut memo = self.find_active(id).await?;
+
+        user.require_owner_or(memo.user_id, Permission::MemosModerate)?;
+
+        // 移動先は所有者のフォルダに限る
+        if let Some(folder_id) = dto.folder_id {
+            self.require_folder(memo.user_id, folder_id).await?;
+        }
+
+        memo.move_to_folder(dto.folder_id);
+        self.memo_repository.update_metadata(&memo).await?;
+        Ok(MemoResponse::from(memo))
+    }
+
+    /// 固定・アーカイブ・お気に入りの設定と解除
+    pub async fn set_flag(
+        &self,
+        id: Uuid,
+        flag: MemoFlag,
+        value: bool,
+        user: &AuthenticatedUser,
+    ) -> AppResult<MemoResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        let mut memo = self.find_active(id).await?;
+
+        user.require_owner_or(memo.user_id, Permission::MemosModerate)?;
+
+        if memo.flag(flag) != value {
+            memo.set_flag(flag, value);
+            self.memo_repository.update_flag(&memo, flag).await?;
+        }
+
         Ok(MemoResponse::from(memo))
     }
 
+    async fn require_folder(&self, user_id: Uuid, folder_id: Uuid) -> AppResult<()> {
+        self.folder_repository
+            .find_by_id(user_id, folder_id)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| AppError::NotFound("Folder not found".into()))
+    }
+
     pub async fn update_memo(
         &self,
         id: Uuid,
         dto: UpdateMemoDto,
-        user_id: Uuid,
+        user: &AuthenticatedUser,
     ) -> AppResult<MemoResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        let mut memo = self.find_active(id).await?;
+
+        // 他のユーザーのメモは管理者のみ編集可能
+        user.require_owner_or(memo.user_id, Permission::MemosModerate)?;
+
+        if memo.version == dto.version {
+            memo.update(dto.title, dto.content, dto.tags);
+        } else {
+            // 編集の基になったバージョンが残っていれば3方向マージを試みる
+            let base = match self.memo_repository.find_version(id, dto.version).await? {
+                Some(base) if base.version < memo.version => base,
+                _ => return Err(AppError::Conflict("Memo has been updated by another user".into())),
+            };
+
+            let merged = Self::merge_update(&base, &memo, dto)?;
+            memo.update(Some(merged.title), Some(merged.content), Some(merged.tags));
+        }
+
+        self.memo_repository.update(&memo).await?;
+        Ok(MemoResponse::from(memo))
+    }
+
+    /// 基底バージョン・保存済みの内容・送信された変更を3方向マージ
+    ///
+    /// 競合した場合は競合マーカー入りの候補を返し、クライアントに解決を委ねます。
+    fn merge_update(base: &MemoVersion, memo: &Memo, dto: UpdateMemoDto) -> AppResult<merge::MergeResult> {
+        let base = MemoFields {
+            title: base.title.clone(),
+            content: base.content.clone(),
+            tags: base.tags.clone(),
+        };
+        let current = MemoFields {
+            title: memo.title.clone(),
+            content: memo.content.clone(),
+            tags: memo.tags.clone(),
+        };
+        // 送信されなかった項目は基底から変更なしとして扱う
+        let incoming = MemoFields {
+            title: dto.title.unwrap_or_else(|| base.title.clone()),
+            content: dto.content.unwrap_or_else(|| base.content.clone()),
+            tags: dto.tags.unwrap_or_else(|| base.tags.clone()),
+        };
+
+        let merged = merge::merge_memo(&base, &current, &incoming);
+        if !merged.has_conflicts() {
+            return Ok(merged);
+        }
+
+        Err(AppError::MergeConflict {
+            message: "Memo has been updated by another user and the changes conflict".into(),
+            details: serde_json::json!({
+                "base_version": dto.version,
+                "current_version": memo.version,
+                "conflicts": merged.conflicts,
+                "merged": {
+                    "title": merged.title,
+                    "content": merged.content,
+                    "tags": merged.tags,
+                },
+            }),
+        })
+    }
+
+    pub async fn get_memo(&self, id: Uuid, user: &AuthenticatedUser) -> AppResult<MemoResponse> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let memo = self.find_active(id).await?;
+
+        user.require_owner_or(memo.user_id, Permission::MemosReadAny)?;
+
+        Ok(MemoResponse::from(memo))
+    }
+
+    /// メモをゴミ箱に移動（`permanent` の場合はゴミ箱を経由せず完全に削除）
+    pub async fn delete_memo(&self, id: Uuid, permanent: bool, user: &AuthenticatedUser) -> AppResult<()> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
         let mut memo = self
             .memo_repository
             .find_by_id(id)
             .await?
             .ok_or_else(|| AppError::NotFound("Memo not found".into()))?;
 
-        if memo.user_id != user_id {
-            return Err(AppError::Unauthorized("Not authorized to update this memo".into()));
+        user.require_owner_or(memo.user_id, Permission::MemosModerate)?;
+
+        if permanent {
+            self.memo_repository.delete(id).await?;
+            return self.trash.untrack(id).await;
         }
 
-        if memo.version != dto.version {
-            return Err(AppError::Conflict("Memo has been updated by another user".into()));
+        if !memo.is_trashed() {
+            memo.move_to_trash();
+            self.memo_repository.update_metadata(&memo).await?;
+            self.trash.track(&memo).await?;
         }
 
-        memo.update(dto.title, dto.content, dto.tags);
-        self.memo_repository.save(&memo).await?;
-        Ok(MemoResponse::from(memo))
+        Ok(())
     }
 
-    pub async fn get_memo(&self, id: Uuid, user_id: Uuid) -> AppResult<MemoResponse> {
-        let memo = self
+    /// ゴミ箱のメモを元に戻す
+    pub async fn restore_from_trash(&self, id: Uuid, user: &AuthenticatedUser) -> AppResult<MemoResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        let mut memo = self
             .memo_repository
             .find_by_id(id)
             .await?
             .ok_or_else(|| AppError::NotFound("Memo not found".into()))?;
 
-        if memo.user_id != user_id {
-            return Err(AppError::Unauthorized("Not authorized to view this memo".into()));
+        user.require_owner_or(memo.user_id, Permission::MemosModerate)?;
+
+        if !memo.is_trashed() {
+            return Err(AppError::BadRequest("Memo is not in the trash".into()));
+        }
+
+        // 元のフォルダが削除されていれば最上位に戻す
+        if let Some(folder_id) = memo.folder_id {
+            if self.folder_repository.find_by_id(memo.user_id, folder_id).await?.is_none() {
+                memo.move_to_folder(None);
+            }
         }
 
+        memo.restore_from_trash();
+        self.memo_repository.update_metadata(&memo).await?;
+        self.trash.untrack(id).await?;
         Ok(MemoResponse::from(memo))
     }
 
-    pub async fn delete_memo(&self, id: Uuid, user_id: Uuid) -> AppResult<()> {
-        let memo = self
-            .memo_repository
+    /// ゴミ箱のメモ一覧（カーソルによるページ送り、本文は含まない）
+    pub async fn list_trash(
+        &self,
+        query: &MemoListQuery,
+        user: &AuthenticatedUser,
+    ) -> AppResult<MemoListResponse<TrashedMemoResponse>> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let limit = query.limit.clamp(1, MAX_LIST_LIMIT);
+        let page = self
+            .find_page(user.user_id, query, query.cursor.as_deref(), limit, &Memo::is_trashed, false)
+            .await?;
+
+        Ok(MemoListResponse {
+            items: page
+                .memos
+                .into_iter()
+                .filter_map(|memo| {
+                    let deleted_at = memo.deleted_at?;
+                    Some(TrashedMemoResponse {
+                        deleted_at,
+                        purge_at: self.trash.purge_at(deleted_at),
+                        memo: MemoSummaryResponse::from(memo),
+                    })
+                })
+                .collect(),
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// メモのバージョン履歴（新しい順）
+    pub async fn list_versions(&self, id: Uuid, user: &AuthenticatedUser) -> AppResult<Vec<MemoVersionSummary>> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let memo = self.find_active(id).await?;
+
+        user.require_owner_or(memo.user_id, Permission::MemosReadAny)?;
+
+        let versions = self.memo_repository.find_versions(id).await?;
+        Ok(versions.into_iter().map(MemoVersionSummary::from).collect())
+    }
+
+    pub async fn get_version(
+        &self,
+        id: Uuid,
+        version: i32,
+        user: &AuthenticatedUser,
+    ) -> AppResult<MemoVersionResponse> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let memo = self.find_active(id).await?;
+
+        user.require_owner_or(memo.user_id, Permission::MemosReadAny)?;
+
+        let snapshot = self.find_snapshot(&memo, version).await?;
+        Ok(MemoVersionResponse::from(snapshot))
+    }
+
+    /// 過去のバージョンの内容を新しいバージョンとして保存
+    pub async fn restore_version(
+        &self,
+        id: Uuid,
+        version: i32,
+        user: &AuthenticatedUser,
+    ) -> AppResult<MemoResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+
+        let mut memo = self.find_active(id).await?;
+
+        user.require_owner_or(memo.user_id, Permission::MemosModerate)?;
+
+        let snapshot = self.find_snapshot(&memo, version).await?;
+        memo.restore(&snapshot);
+        self.memo_repository.update(&memo).await?;
+        Ok(MemoResponse::from(memo))
+    }
+
+    /// 2つのバージョン間の差分
+    pub async fn diff_versions(
+        &self,
+        id: Uuid,
+        from: i32,
+        to: i32,
+        user: &AuthenticatedUser,
+    ) -> AppResult<MemoDiff> {
+        let (from, to) = self.find_snapshot_pair(id, from, to, user).await?;
+        Ok(MemoDiff::between(&from, &to))
+    }
+
+    /// 2つのバージョン間の差分（unified diff 形式のテキスト）
+    pub async fn unified_diff_versions(
+        &self,
+        id: Uuid,
+        from: i32,
+        to: i32,
+        user: &AuthenticatedUser,
+    ) -> AppResult<String> {
+        let (from, to) = self.find_snapshot_pair(id, from, to, user).await?;
+        Ok(diff::unified_diff(&from, &to))
+    }
+
+    async fn find_snapshot_pair(
+        &self,
+        id: Uuid,
+        from: i32,
+        to: i32,
+        user: &AuthenticatedUser,
+    ) -> AppResult<(MemoVersion, MemoVersion)> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let memo = self.find_active(id).await?;
+
+        user.require_owner_or(memo.user_id, Permission::MemosReadAny)?;
+
+        Ok((
+            self.find_snapshot(&memo, from).await?,
+            self.find_snapshot(&memo, to).await?,
+        ))
+    }
+
+    /// 指定バージョンのスナップショットを取得
+    ///
+    /// 履歴の記録を始める前に保存されたメモでも、現在のバージョンは参照できるようにします。
+    async fn find_snapshot(&self, memo: &Memo, version: i32) -> AppResult<MemoVersion> {
+        if version == memo.version {
+            return Ok(MemoVersion::snapshot(memo));
+        }
+
+        self.memo_repository
+            .find_version(memo.id, version)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Memo version not found".into()))
+    }
+
+    /// ゴミ箱にないメモを取得
+    async fn find_active(&self, id: Uuid) -> AppResult<Memo> {
+        self.memo_repository
             .find_by_id(id)
             .await?
-            .ok_or_else(|| AppError::NotFound("Memo not found".into()))?;
+            .filter(|memo| !memo.is_trashed())
+            .ok_or_else(|| AppError::NotFound("Memo not found".into()))
+    }
+
+    /// 自分のメモ一覧（カーソルによるページ送り）
+    pub async fn list_memos(
+        &self,
+        query: &MemoListQuery,
+        filter: &MemoListFilter,
+        user: &AuthenticatedUser,
+    ) -> AppResult<MemoListResponse> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let page = self.list_page(user.user_id, query, filter, true).await?;
+        Ok(MemoListResponse {
+            items: page.memos.into_iter().map(MemoResponse::from).collect(),
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// 指定ユーザーのメモ一覧（本人以外は管理者のみ、本文は含まない）
+    pub async fn get_memos_of_user(
+        &self,
+        owner_id: Uuid,
+        query: &MemoListQuery,
+        filter: &MemoListFilter,
+        user: &AuthenticatedUser,
+    ) -> AppResult<MemoListResponse<MemoSummaryResponse>> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_owner_or(owner_id, Permission::MemosReadAny)?;
 
-        if memo.user_id != user_id {
-            return Err(AppError::Unauthorized("Not authorized to delete this memo".into()));
+        let page = self.list_page(owner_id, query, filter, false).await?;
+        Ok(MemoListResponse {
+            items: page.memos.into_iter().map(MemoSummaryResponse::from).collect(),
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// ゴミ箱にないメモの一覧の1ページ
+    ///
+    /// `pinned_first` の場合は固定したメモを読み終えてから固定していないメモを読みます。
+    /// カーソルの先頭にはどちらを読んでいるか（`p.` / `u.`）を付けます。
+    async fn list_page(
+        &self,
+        owner_id: Uuid,
+        query: &MemoListQuery,
+        filter: &MemoListFilter,
+        with_content: bool,
+    ) -> AppResult<MemoPage> {
+        let limit = query.limit.clamp(1, MAX_LIST_LIMIT);
+        let matches = |memo: &Memo| !memo.is_trashed() && filter.matches(memo);
+
+        if !filter.pinned_first {
+            return self
+                .find_page(owner_id, query, query.cursor.as_deref(), limit, &matches, with_content)
+                .await;
         }
 
-        self.memo_repository.delete(id).await
+        let (reading_pinned, mut cursor) = match query.cursor.as_deref().map(|cursor| cursor.split_once('.')) {
+            None => (true, None),
+            Some(Some(("p", cursor))) => (true, Some(cursor)),
+            Some(Some(("u", cursor))) => (false, Some(cursor).filter(|cursor| !cursor.is_empty())),
+            Some(_) => return Err(AppError::BadRequest("Invalid cursor".into())),
+        };
+
+        let mut memos = Vec::new();
+        if reading_pinned {
+            let page = self
+                .find_page(owner_id, query, cursor, limit, &|memo: &Memo| matches(memo) && memo.pinned, with_content)
+                .await?;
+            memos = page.memos;
+
+            let next_cursor = match page.next_cursor {
+                Some(next_cursor) => Some(format!("p.{}", next_cursor)),
+                // 固定したメモを読み終えた時点でページが埋まった場合は、次のページから固定していないメモを読む
+                None if memos.len() >= limit => Some("u.".to_string()),
+                None => None,
+            };
+            if next_cursor.is_some() {
+                return Ok(MemoPage { memos, next_cursor });
+            }
+            cursor = None;
+        }
+
+        let page = self
+            .find_page(
+                owner_id,
+                query,
+                cursor,
+                limit - memos.len(),
+                &|memo: &Memo| matches(memo) && !memo.pinned,
+                with_content,
+            )
+            .await?;
+        memos.extend(page.memos);
+
+        Ok(MemoPage {
+            memos,
+            next_cursor: page.next_cursor.map(|next_cursor| format!("u.{}", next_cursor)),
+        })
     }
 
-    pub async fn get_user_memos(&self, user_id: Uuid) -> AppResult<Vec<MemoResponse>> {
-        let memos = self.memo_repository.find_all_by_user_id(user_id).await?;
-        Ok(memos.into_iter().map(MemoResponse::from).collect())
+    /// `query` の並び順で1ページ分を読み込む（`with_content` でなければ本文は空）
+    async fn find_page(
+        &self,
+        owner_id: Uuid,
+        query: &MemoListQuery,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+        with_content: bool,
+    ) -> AppResult<MemoPage> {
+        let sort = query.sort;
+        let order = query.order.unwrap_or(sort.default_order());
+
+        if with_content {
+            self.memo_repository
+                .find_page_by_user_id(owner_id, sort, order, cursor, limit, filter)
+                .await
+        } else {
+            self.memo_repository
+                .find_summary_page_by_user_id(owner_id, sort, order, cursor, limit, filter)
+                .await
+        }
     }
 
+    /// メモの検索
+    ///
+    /// `from` + `size` で取得できるのは先頭から `MAX_SEARCH_WINDOW` 件までで、
+    /// それより先は `search_after` で続きを取得します。
     pub async fn search_memos(
         &self,
-        query: &str,
-        tag: Option<String>,
-        user_id: Uuid,
-    ) -> AppResult<SearchResponse> {
-        let memos = self.memo_repository.search(query, tag, user_id).await?;
-        let total = memos.len();
-        
+        query: &SearchMemosQuery,
+        user: &AuthenticatedUser,
+    ) -> AppResult<SearchResponse<SearchHitResponse>> {
+        user.require_scope(ApiScope::Search)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let limit = query.limit.clamp(1, MAX_LIST_LIMIT);
+        let page = query.page.max(1);
+        if query.search_after.is_none() && page.saturating_mul(limit) > MAX_SEARCH_WINDOW {
+            return Err(AppError::BadRequest(format!(
+                "Only the first {} results can be paged by number; use search_after to continue",
+                MAX_SEARCH_WINDOW
+            )));
+        }
+
+        if [&query.highlight_pre_tag, &query.highlight_post_tag]
+            .iter()
+            .any(|tag| tag.chars().count() > MAX_HIGHLIGHT_TAG_LENGTH)
+        {
+            return Err(AppError::ValidationError(format!(
+                "Highlight tags must be at most {} characters",
+                MAX_HIGHLIGHT_TAG_LENGTH
+            )));
+        }
+        if !query.has_allowed_highlight_tags() {
+            return Err(AppError::ValidationError(
+                "Highlight tags must be a <mark>, <em>, <strong> or <b> tag, optionally with a class, \
+                and its matching closing tag"
+                    .into(),
+            ));
+        }
+
+        let search = MemoSearch {
+            user_id: user.user_id,
+            query: parse_query(query.query.as_deref().unwrap_or_default())?,
+            tag: query.tag.clone(),
+            folder_id: query.folder,
+            paging: SearchPaging {
+                from: (page - 1) * limit,
+                size: limit,
+                search_after: query.search_after.clone(),
+            },
+            highlight: HighlightOptions {
+                pre_tag: query.highlight_pre_tag.clone(),
+                post_tag: query.highlight_post_tag.clone(),
+                fragment_size: query.fragment_size.clamp(20, 500),
+            },
+            facets: query.facets,
+        };
+        let result = self.memo_repository.search(&search).await?;
+        let total = result.total as usize;
+
+        // 続きのページの取得は新しい検索として記録しない
+        if let (Some(text), 1, None) = (&query.query, page, &query.search_after) {
+            if let Err(e) = self.history.record(user.user_id, text).await {
+                log::warn!("Failed to record search history for {}: {}", user.user_id, e);
+            }
+        }
+
+        let items = result
+            .hits
+            .into_iter()
+            .map(|hit| {
+                let memo = hit.memo;
+                SearchHitResponse {
+                    snippet: snippet(
+                        &memo.content,
+                        hit.content_highlights.first().map(String::as_str),
+                        &search.highlight.pre_tag,
+                        &search.highlight.post_tag,
+                        search.highlight.fragment_size,
+                    ),
+                    highlights: SearchHighlights {
+                        title: hit.title_highlights,
+                        content: hit.content_highlights,
+                    },
+                    id: memo.id,
+                    title: memo.title,
+                    content: query.include_content.then_some(memo.content),
+                    tags: memo.tags,
+                    user_id: memo.user_id,
+                    created_at: memo.created_at,
+                    updated_at: memo.updated_at,
+                    version: memo.version,
+                    folder_id: memo.folder_id,
+                    pinned: memo.pinned,
+                    archived: memo.archived,
+                    favorite: memo.favorite,
+                }
+            })
+            .collect();
+
         Ok(SearchResponse {
-            items: memos.into_iter().map(MemoResponse::from).collect(),
+            items,
             total,
-            page: 1,
-            total_pages: 1,
+            // `search_after` で取得したページは番号で表せない
+            page: query.search_after.is_none().then_some(page),
+            total_pages: total.div_ceil(limit),
+            search_after: result.search_after,
+            facets: result.facets.map(SearchFacetsResponse::from),
+        })
+    }
+
+    /// 入力途中の検索語に対する補完候補（タイトル、タグ、最近の検索語）
+    pub async fn suggest(&self, query: &SuggestQuery, user: &AuthenticatedUser) -> AppResult<SuggestResponse> {
+        user.require_scope(ApiScope::Search)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let prefix = query.q.trim();
+        if prefix.chars().count() > MAX_SUGGEST_PREFIX_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Suggestion queries must be at most {} characters",
+                MAX_SUGGEST_PREFIX_LENGTH
+            )));
+        }
+        let limit = query.limit.clamp(1, MAX_SUGGEST_LIMIT);
+
+        // 入力が空の場合は最近の検索語だけを返す
+        if prefix.is_empty() {
+            return Ok(SuggestResponse {
+                titles: Vec::new(),
+                tags: Vec::new(),
+                recent_queries: self.history.matching(user.user_id, prefix, limit).await?,
+            });
+        }
+
+        let (suggestions, recent_queries) = tokio::join!(
+            self.memo_repository.suggest(user.user_id, prefix, limit),
+            self.history.matching(user.user_id, prefix, limit),
+        );
+        let suggestions = suggestions?;
+
+        Ok(SuggestResponse {
+            titles: suggestions
+                .titles
+                .into_iter()
+                .map(|(id, title)| TitleSuggestion { id, title })
+                .collect(),
+            tags: suggestions
+                .tags
+                .into_iter()
+                .map(|(tag, count)| TagUsage {
+                    tag,
+                    count: count as usize,
+                })
+                .collect(),
+            recent_queries: recent_queries?,
         })
     }
-}
\ No newline at end of file
+
+    /// 最近の検索語をすべて削除
+    pub async fn clear_search_history(&self, user: &AuthenticatedUser) -> AppResult<()> {
+        user.require_scope(ApiScope::Search)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        self.history.clear(user.user_id).await
+    }
+}
@@ -0,0 +1,91 @@
+// src/application/memo/snippet.rs
+
+const ELLIPSIS: &str = "…";
+
+/// 検索結果の一覧に表示する本文の抜粋（プレーンテキスト）
+///
+/// 最も一致度の高い強調表示の断片があればそれを、なければ本文の先頭を
+/// `max_chars` 文字まで使います。本文の途中を切り出した場合は前後に `…` を付けます。
+pub fn snippet(
+    content: &str,
+    best_fragment: Option<&str>,
+    pre_tag: &str,
+    post_tag: &str,
+    max_chars: usize,
+) -> String {
+    let content = content.trim();
+
+    let (text, truncated_start, truncated_end) = match best_fragment {
+        Some(fragment) => {
+            let text = unescape_html(&fragment.replace(pre_tag, "").replace(post_tag, ""));
+            let text = text.trim().to_string();
+            let truncated_start = !content.starts_with(&text);
+            let truncated_end = !content.ends_with(&text);
+            (text, truncated_start, truncated_end)
+        }
+        None => {
+            let text: String = content.chars().take(max_chars).collect();
+            let truncated_end = text.len() < content.len();
+            (text, false, truncated_end)
+        }
+    };
+
+    let mut snippet = String::new();
+    if truncated_start {
+        snippet.push_str(ELLIPSIS);
+    }
+    snippet.push_str(&collapse_whitespace(&text));
+    if truncated_end {
+        snippet.push_str(ELLIPSIS);
+    }
+    snippet
+}
+
+/// 改行などの連続する空白を1つの空白にまとめる
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Elasticsearch の `html` エンコーダがエスケープする文字を元に戻す
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#x2F;", "/")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_from_best_fragment() {
+        let content = "Intro line.\nScylla stores memos & versions.\nOutro line.";
+        let fragment = "Scylla stores <mark>memos</mark> &amp; versions.";
+
+        assert_eq!(
+            snippet(content, Some(fragment), "<mark>", "</mark>", 150),
+            "…Scylla stores memos & versions.…"
+        );
+    }
+
+    #[test]
+    fn test_snippet_falls_back_to_leading_content() {
+        let content = "日本語の本文\n二行目";
+
+        assert_eq!(snippet(content, None, "<em>", "</em>", 4), "日本語の…");
+        assert_eq!(snippet(content, None, "<em>", "</em>", 100), "日本語の本文 二行目");
+    }
+
+    #[test]
+    fn test_snippet_covering_whole_content_has_no_ellipsis() {
+        let content = "short memo";
+
+        assert_eq!(
+            snippet(content, Some("short <em>memo</em>"), "<em>", "</em>", 150),
+            "short memo"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+// src/application/memo/trash.rs
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use chrono::{DateTime, Utc};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use crate::{
+    domain::memo::{entity::Memo, repository::MemoRepository},
+    error::{AppError, AppResult},
+    infrastructure::persistence::{redis::RedisCache, scylla::ScyllaDB},
+};
+
+/// ゴミ箱のメモID（スコアはゴミ箱に移動した日時のUNIX秒）
+const TRASH_KEY: &str = "memo_trash:deleted_at";
+const PURGE_INTERVAL: Duration = Duration::from_secs(60);
+/// ScyllaDB のゴミ箱の状態から削除予定を登録し直す間隔
+const RESYNC_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 1回の実行で完全に削除する最大件数
+const PURGE_BATCH_SIZE: isize = 100;
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// ゴミ箱の設定
+pub struct TrashConfig {
+    /// ゴミ箱に移動してから完全に削除するまでの期間
+    pub retention: chrono::Duration,
+}
+
+impl TrashConfig {
+    /// 環境変数から設定を読み込む
+    ///
+    /// * `MEMO_TRASH_RETENTION_DAYS` - ゴミ箱の保持日数（既定は30日）
+    pub fn from_env() -> AppResult<Self> {
+        let days = match std::env::var("MEMO_TRASH_RETENTION_DAYS") {
+            Ok(days) => days.parse::<i64>().map_err(|e| {
+                AppError::InternalServerError(format!("Invalid MEMO_TRASH_RETENTION_DAYS: {}", e))
+            })?,
+            Err(_) => DEFAULT_RETENTION_DAYS,
+        };
+        if days < 0 {
+            return Err(AppError::InternalServerError(
+                "MEMO_TRASH_RETENTION_DAYS must not be negative".into(),
+            ));
+        }
+
+        Ok(Self {
+            retention: chrono::Duration::days(days),
+        })
+    }
+}
+
+/// ゴミ箱の保持期限の管理と、期限切れのメモを完全に削除するバックグラウンドジョブ
+pub struct MemoTrash {
+    redis: Arc<RedisCache>,
+    scylla: Arc<ScyllaDB>,
+    memo_repository: Arc<dyn MemoRepository>,
+    retention: chrono::Duration,
+}
+
+impl MemoTrash {
+    pub fn new(
+        redis: Arc<RedisCache>,
+        scylla: Arc<ScyllaDB>,
+        memo_repository: Arc<dyn MemoRepository>,
+        config: TrashConfig,
+    ) -> Self {
+        Self {
+            redis,
+            scylla,
+            memo_repository,
+            retention: config.retention,
+        }
+    }
+
+    /// 完全に削除される日時
+    pub fn purge_at(&self, deleted_at: DateTime<Utc>) -> DateTime<Utc> {
+        deleted_at + self.retention
+    }
+
+    /// ゴミ箱に移動したメモを削除予定に登録
+    pub async fn track(&self, memo: &Memo) -> AppResult<()> {
+        let Some(deleted_at) = memo.deleted_at else {
+            return Ok(());
+        };
+
+        self.redis
+            .add_to_sorted_set(TRASH_KEY, &memo.id.to_string(), deleted_at.timestamp())
+            .await
+    }
+
+    /// 削除予定から外す（ゴミ箱から戻した、または完全に削除した場合）
+    pub async fn untrack(&self, id: Uuid) -> AppResult<()> {
+        self.redis.remove_from_sorted_set(TRASH_KEY, &id.to_string()).await?;
+        Ok(())
+    }
+
+    /// 保持期限を過ぎたメモを完全に削除し、削除した件数を返す
+    pub async fn purge_expired(&self) -> AppResult<usize> {
+        let cutoff = Utc::now() - self.retention;
+        let members = self
+            .redis
+            .sorted_set_range_by_score(TRASH_KEY, cutoff.timestamp(), PURGE_BATCH_SIZE)
+            .await?;
+
+        let mut purged = 0;
+        for member in members {
+            let Ok(id) = Uuid::parse_str(&member) else {
+                warn!("Dropping invalid trash entry: {}", member);
+                self.redis.remove_from_sorted_set(TRASH_KEY, &member).await?;
+                continue;
+            };
+
+            if let Some(memo) = self.memo_repository.find_by_id(id).await? {
+                match memo.deleted_at {
+                    Some(deleted_at) if deleted_at <= cutoff => {
+                        if let Err(e) = self.memo_repository.delete(id).await {
+                            // 次回の実行で再試行
+                            error!("Failed to purge memo {}: {}", id, e);
+                            continue;
+                        }
+                        purged += 1;
+                    }
+                    // 後から再度ゴミ箱に移動されたメモは期限を登録し直す
+                    Some(_) => {
+                        self.track(&memo).await?;
+                        continue;
+                    }
+                    None => {}
+                }
+            }
+
+            self.untrack(id).await?;
+        }
+
+        Ok(purged)
+    }
+
+    /// ゴミ箱にあるメモをすべて削除予定に登録し直し、登録した件数を返す
+    ///
+    /// 削除予定は Redis にしかないため、Redis のデータが失われた場合や
+    /// ゴミ箱への移動後に登録できなかった場合もここで復元します。
+    pub async fn resync(&self) -> AppResult<usize> {
+        let mut tracked = 0;
+        let mut paging_state = None;
+        loop {
+            let (memos, next_page) = self.scylla.scan_memos(paging_state).await?;
+            for memo in memos.iter().filter(|memo| memo.is_trashed()) {
+                self.track(memo).await?;
+                tracked += 1;
+            }
+
+            match next_page {
+                Some(next_page) => paging_state = Some(next_page),
+                None => return Ok(tracked),
+            }
+        }
+    }
+
+    /// 期限切れのメモを定期的に削除し続けるワーカー（起動時と一定間隔で削除予定も登録し直す）
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        let mut last_resync: Option<Instant> = None;
+        loop {
+            interval.tick().await;
+            if !matches!(last_resync, Some(at) if at.elapsed() < RESYNC_INTERVAL) {
+                match self.resync().await {
+                    Ok(tracked) => info!("Resynced {} trashed memos", tracked),
+                    Err(e) => error!("Failed to resync trash: {}", e),
+                }
+                last_resync = Some(Instant::now());
+            }
+
+            match self.purge_expired().await {
+                Ok(0) => {}
+                Ok(purged) => info!("Purged {} memos from trash", purged),
+                Err(e) => error!("Failed to purge trash: {}", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use crate::application::session::dto::TokenResponse;
+
+/// ログイン結果（多要素認証が有効な場合はチャレンジを返す）
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum LoginResponse {
+    Authenticated(TokenResponse),
+    MfaRequired {
+        mfa_required: bool,
+        mfa_token: String,
+        expires_in: i64,
+    },
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MfaLoginDto {
+    #[validate(length(min = 1, message = "MFA token cannot be empty"))]
+    pub mfa_token: String,
+    /// TOTPコードまたはリカバリーコード
+    #[validate(length(min = 1, message = "Code cannot be empty"))]
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MfaCodeDto {
+    #[validate(length(equal = 6, message = "Code must be 6 digits"))]
+    pub code: String,
+}
+
+/// MFAの無効化
+///
+/// パスワードを省略した場合は第2要素のコードだけで本人確認します
+/// （外部アカウントで登録したユーザーはパスワードを持たないため）。
+#[derive(Debug, Deserialize, Validate)]
+pub struct DisableMfaDto {
+    #[validate(length(min = 1, message = "Password cannot be empty"))]
+    pub password: Option<String>,
+    /// TOTPコードまたはリカバリーコード
+    #[validate(length(min = 1, message = "Code cannot be empty"))]
+    pub code: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MfaEnrollmentResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
@@ -0,0 +1,293 @@
+use std::sync::Arc;
+use chrono::Utc;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    application::{
+        auth::{login_guard::LoginGuard, AuthenticatedUser},
+        session::{
+            dto::{ClientInfo, TokenResponse},
+            service::SessionService,
+        },
+    },
+    domain::user::{entity::User, repository::UserRepository},
+    error::{AppError, AppResult},
+    infrastructure::auth::{
+        mfa::{MfaChallengeStore, MFA_CHALLENGE_TTL},
+        password::{hash_password_blocking, run_blocking, verify_password, verify_password_blocking},
+        token::generate_token,
+        totp,
+    },
+};
+use super::dto::{
+    DisableMfaDto, LoginResponse, MfaCodeDto, MfaEnrollmentResponse, MfaLoginDto,
+    RecoveryCodesResponse,
+};
+
+/// 認証アプリに表示される発行者名
+const MFA_ISSUER: &str = "Memo App";
+const RECOVERY_CODE_COUNT: usize = 10;
+
+pub struct MfaService {
+    user_repository: Arc<dyn UserRepository>,
+    session_service: Arc<SessionService>,
+    challenge_store: Arc<MfaChallengeStore>,
+    login_guard: Arc<LoginGuard>,
+}
+
+impl MfaService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        session_service: Arc<SessionService>,
+        challenge_store: Arc<MfaChallengeStore>,
+        login_guard: Arc<LoginGuard>,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_service,
+            challenge_store,
+            login_guard,
+        }
+    }
+
+    /// 第1要素の認証後に呼び出し、MFAが有効ならチャレンジを、無効ならトークンを返す
+    pub async fn begin_login(&self, user: &User, client: ClientInfo) -> AppResult<LoginResponse> {
+        if !user.mfa_enabled() {
+            let tokens = self.session_service.start_session(user, client).await?;
+            return Ok(LoginResponse::Authenticated(tokens));
+        }
+
+        Ok(LoginResponse::MfaRequired {
+            mfa_required: true,
+            mfa_token: self.challenge_store.create(user.id).await?,
+            expires_in: MFA_CHALLENGE_TTL.as_secs() as i64,
+        })
+    }
+
+    /// 第2要素を検証してログインを完了
+    ///
+    /// 第2要素の失敗はパスワードの失敗と同じアカウント単位の回数に数え、
+    /// 第2要素の検証に成功するまでリセットしません。パスワードを知っている相手が
+    /// ログインをやり直してチャレンジを取り直しても、コードを総当たりできないようにします。
+    pub async fn complete_login(&self, dto: MfaLoginDto, client: ClientInfo) -> AppResult<TokenResponse> {
+        dto.validate()?;
+
+        let challenge = self
+            .challenge_store
+            .begin_attempt(&dto.mfa_token)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("MFA challenge has expired".into()))?;
+
+        let mut user = self
+            .user_repository
+            .find_by_id(challenge.user_id)
+            .await?
+            .filter(User::mfa_enabled)
+            .ok_or_else(|| AppError::Unauthorized("MFA challenge has expired".into()))?;
+
+        self.login_guard.check(&user.email, &client).await?;
+        if !self.verify_second_factor(&mut user, &dto.code).await? {
+            self.login_guard.record_failure(&user.email, &client).await?;
+            return Err(AppError::Unauthorized("Invalid authentication code".into()));
+        }
+
+        // 同じチャレンジで並行して検証に成功しても、セッションを作成するのは1つだけ
+        if !self.challenge_store.consume(&dto.mfa_token).await? {
+            return Err(AppError::Unauthorized("MFA challenge has expired".into()));
+        }
+        self.login_guard.record_success(&user.email).await?;
+        self.session_service.start_session(&user, client).await
+    }
+
+    /// TOTPの登録を開始し、認証アプリ用のシークレットを返す
+    pub async fn enroll(&self, current: &AuthenticatedUser) -> AppResult<MfaEnrollmentResponse> {
+        current.require_session()?;
+
+        let mut user = self.find_user(current.user_id).await?;
+        if user.mfa_enabled() {
+            return Err(AppError::Conflict("MFA is already enabled".into()));
+        }
+
+        let secret = totp::generate_secret();
+        user.begin_mfa_enrollment(secret.clone());
+        self.user_repository.save(&mut user).await?;
+
+        Ok(MfaEnrollmentResponse {
+            otpauth_uri: totp::otpauth_uri(MFA_ISSUER, &user.email, &secret),
+            secret,
+        })
+    }
+
+    /// 最初のコードで登録を確認し、リカバリーコードを発行（表示は一度のみ）
+    pub async fn confirm(
+        &self,
+        current: &AuthenticatedUser,
+        dto: MfaCodeDto,
+    ) -> AppResult<RecoveryCodesResponse> {
+        current.require_session()?;
+        dto.validate()?;
+
+        let mut user = self.find_user(current.user_id).await?;
+        if user.mfa_enabled() {
+            return Err(AppError::Conflict("MFA is already enabled".into()));
+        }
+        let secret = user
+            .mfa_secret
+            .clone()
+            .ok_or_else(|| AppError::BadRequest("MFA enrollment has not been started".into()))?;
+
+        let step = totp::verify(&secret, &dto.code, Utc::now().timestamp(), None)
+            .ok_or_else(|| AppError::BadRequest("Invalid authentication code".into()))?;
+
+        let (codes, hashes) = Self::generate_recovery_codes().await?;
+        user.enable_mfa(hashes, step);
+        self.user_repository.save(&mut user).await?;
+
+        Ok(RecoveryCodesResponse { recovery_codes: codes })
+    }
+
+    /// リカバリーコードを再発行（既存のコードは無効化）
+    pub async fn regenerate_recovery_codes(
+        &self,
+        current: &AuthenticatedUser,
+        dto: MfaCodeDto,
+    ) -> AppResult<RecoveryCodesResponse> {
+        current.require_session()?;
+        dto.validate()?;
+
+        let mut user = self.find_user(current.user_id).await?;
+        if !user.mfa_enabled() {
+            return Err(AppError::BadRequest("MFA is not enabled".into()));
+        }
+        if !Self::verify_totp(&mut user, &dto.code) {
+            return Err(AppError::Unauthorized("Invalid authentication code".into()));
+        }
+
+        let (codes, hashes) = Self::generate_recovery_codes().await?;
+        user.mfa_recovery_codes = hashes;
+        self.user_repository.save(&mut user).await?;
+
+        Ok(RecoveryCodesResponse { recovery_codes: codes })
+    }
+
+    /// 本人によるMFAの無効化（第2要素と、設定していればパスワードが必要）
+    pub async fn disable(&self, current: &AuthenticatedUser, dto: DisableMfaDto) -> AppResult<()> {
+        current.require_session()?;
+        dto.validate()?;
+
+        let mut user = self.find_user(current.user_id).await?;
+        if !user.mfa_enabled() {
+            return Err(AppError::BadRequest("MFA is not enabled".into()));
+        }
+        if let Some(password) = &dto.password {
+            if !verify_password(password, &user.password_hash).await? {
+                return Err(AppError::Unauthorized("Password is incorrect".into()));
+            }
+        }
+        if !self.verify_second_factor(&mut user, &dto.code).await? {
+            return Err(AppError::Unauthorized("Invalid authentication code".into()));
+        }
+
+        user.disable_mfa();
+        self.user_repository.save(&mut user).await
+    }
+
+    /// 端末を紛失したユーザーのMFAを管理者がリセット
+    pub async fn reset(&self, user_id: Uuid) -> AppResult<()> {
+        let mut user = self.find_user(user_id).await?;
+        user.disable_mfa();
+        self.user_repository.save(&mut user).await?;
+
+        log::info!("MFA was reset for user {}", user_id);
+        Ok(())
+    }
+
+    /// TOTPコードまたはリカバリーコードを検証し、使用済みとして保存
+    ///
+    /// 保存は読み込んだバージョンに対する条件付きの更新なので、並行したリクエストで
+    /// 同じコードを使うと一方は Conflict になり、コードを二度使うことはできません。
+    async fn verify_second_factor(&self, user: &mut User, code: &str) -> AppResult<bool> {
+        let code = code.trim();
+        let verified = if code.len() == 6 && code.bytes().all(|b| b.is_ascii_digit()) {
+            Self::verify_totp(user, code)
+        } else {
+            Self::consume_recovery_code(user, code).await?
+        };
+
+        if verified {
+            self.user_repository.save(user).await?;
+        }
+        Ok(verified)
+    }
+
+    fn verify_totp(user: &mut User, code: &str) -> bool {
+        let Some(secret) = user.mfa_secret.as_deref() else {
+            return false;
+        };
+
+        match totp::verify(secret, code, Utc::now().timestamp(), user.mfa_last_used_step) {
+            Some(step) => {
+                user.record_totp_step(step);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 一致するリカバリーコードを使用済みにする
+    ///
+    /// 最大でコードの数だけハッシュを照合するため、まとめて専用のスレッドで実行します。
+    async fn consume_recovery_code(user: &mut User, code: &str) -> AppResult<bool> {
+        let normalized = Self::normalize_recovery_code(code);
+        let hashes = user.mfa_recovery_codes.clone();
+
+        let matched = run_blocking(move || {
+            for (index, hash) in hashes.iter().enumerate() {
+                if verify_password_blocking(&normalized, hash)? {
+                    return Ok(Some(index));
+                }
+            }
+            Ok(None)
+        })
+        .await?;
+
+        match matched {
+            Some(index) => {
+                user.consume_recovery_code(index);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// 平文のコードとそのハッシュを生成
+    async fn generate_recovery_codes() -> AppResult<(Vec<String>, Vec<String>)> {
+        run_blocking(|| {
+            let mut codes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+            let mut hashes = Vec::with_capacity(RECOVERY_CODE_COUNT);
+
+            for _ in 0..RECOVERY_CODE_COUNT {
+                let raw = generate_token(5);
+                hashes.push(hash_password_blocking(&raw)?);
+                codes.push(format!("{}-{}", &raw[..5], &raw[5..]));
+            }
+
+            Ok((codes, hashes))
+        })
+        .await
+    }
+
+    fn normalize_recovery_code(code: &str) -> String {
+        code.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase()
+    }
+
+    async fn find_user(&self, user_id: Uuid) -> AppResult<User> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+}
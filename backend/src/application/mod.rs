@@ -1 +1,12 @@
+pub mod account;
+pub mod admin;
+pub mod api_key;
+pub mod auth;
+pub mod folder;
 pub mod memo;
+pub mod mfa;
+pub mod oauth;
+pub mod session;
+pub mod tag;
+pub mod user;
+pub mod verification;
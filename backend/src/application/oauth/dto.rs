@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct OAuthCallbackParams {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthorizationUrlResponse {
+    pub authorization_url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OAuthProvidersResponse {
+    pub providers: Vec<String>,
+}
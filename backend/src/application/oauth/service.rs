@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use crate::{
+    application::{
+        admin::bootstrap::AdminBootstrap,
+        mfa::{dto::LoginResponse, service::MfaService},
+        session::dto::ClientInfo,
+    },
+    domain::{
+        identity::{entity::OAuthIdentity, repository::OAuthIdentityRepository},
+        user::{entity::User, repository::UserRepository},
+    },
+    error::{AppError, AppResult},
+    infrastructure::auth::{
+        oauth::{OAuthClient, OAuthUserInfo},
+        password::hash_password,
+        token::generate_token,
+    },
+};
+use super::dto::{AuthorizationUrlResponse, OAuthCallbackParams, OAuthProvidersResponse};
+
+pub struct OAuthService {
+    oauth_client: Arc<OAuthClient>,
+    user_repository: Arc<dyn UserRepository>,
+    identity_repository: Arc<dyn OAuthIdentityRepository>,
+    mfa_service: Arc<MfaService>,
+    admin_bootstrap: Arc<AdminBootstrap>,
+}
+
+impl OAuthService {
+    pub fn new(
+        oauth_client: Arc<OAuthClient>,
+        user_repository: Arc<dyn UserRepository>,
+        identity_repository: Arc<dyn OAuthIdentityRepository>,
+        mfa_service: Arc<MfaService>,
+        admin_bootstrap: Arc<AdminBootstrap>,
+    ) -> Self {
+        Self {
+            oauth_client,
+            user_repository,
+            identity_repository,
+            mfa_service,
+            admin_bootstrap,
+        }
+    }
+
+    pub fn providers(&self) -> OAuthProvidersResponse {
+        OAuthProvidersResponse {
+            providers: self.oauth_client.provider_names(),
+        }
+    }
+
+    /// 認可URLと、認可を開始したブラウザに保存させる値を発行
+    pub async fn authorize(&self, provider: &str) -> AppResult<(AuthorizationUrlResponse, String)> {
+        let request = self.oauth_client.authorization_url(provider).await?;
+        Ok((
+            AuthorizationUrlResponse {
+                authorization_url: request.url,
+            },
+            request.browser_binding,
+        ))
+    }
+
+    /// プロバイダーからのコールバックを処理し、セッションを開始
+    ///
+    /// MFAが有効なユーザーにはパスワードログインと同様にチャレンジを返します。
+    /// `browser_binding` は認可の開始時にブラウザへ保存させた値です。
+    pub async fn callback(
+        &self,
+        provider: &str,
+        params: OAuthCallbackParams,
+        browser_binding: Option<&str>,
+        client: ClientInfo,
+    ) -> AppResult<LoginResponse> {
+        if let Some(error) = params.error {
+            return Err(AppError::BadRequest(format!(
+                "OAuth provider returned an error: {}",
+                params.error_description.unwrap_or(error)
+            )));
+        }
+
+        let (Some(code), Some(state)) = (params.code, params.state) else {
+            return Err(AppError::BadRequest("Missing code or state".into()));
+        };
+
+        let info = self
+            .oauth_client
+            .exchange_code(provider, &code, &state, browser_binding)
+            .await?;
+        let user = self.resolve_user(info).await?;
+        let user = self.admin_bootstrap.apply(user).await?;
+        self.mfa_service.begin_login(&user, client).await
+    }
+
+    /// 外部アカウントに対応するユーザーを解決
+    ///
+    /// 1. 既に紐付け済みならそのユーザー
+    /// 2. 検証済みメールアドレスが既存ユーザーと一致すれば紐付け
+    /// 3. それ以外は検証済みメールアドレスで新規ユーザーを作成
+    async fn resolve_user(&self, info: OAuthUserInfo) -> AppResult<User> {
+        if let Some(identity) = self.identity_repository.find(&info.provider, &info.subject).await? {
+            return self
+                .user_repository
+                .find_by_id(identity.user_id)
+                .await?
+                .filter(|user| !user.is_pending_deletion())
+                .ok_or_else(|| AppError::Unauthorized("Linked user no longer exists".into()));
+        }
+
+        // 未検証のメールアドレスではアカウントの乗っ取りを防げないため紐付けない
+        let email = info
+            .email
+            .clone()
+            .filter(|_| info.email_verified)
+            .ok_or_else(|| {
+                AppError::Unauthorized("OAuth account has no verified email address".into())
+            })?;
+
+        // プロバイダーが確認済みのメールアドレスなので確認済みとして扱う
+        let user = match self.user_repository.find_by_email(&email).await? {
+            Some(user) if user.is_pending_deletion() => {
+                return Err(AppError::Unauthorized("Account is being deleted".into()));
+            }
+            Some(mut user) => {
+                if !user.email_verified() {
+                    user.verify_email();
+                    self.user_repository.save(&mut user).await?;
+                }
+                user
+            }
+            None => {
+                // パスワードログインは不可（推測不能なランダム値のハッシュ）
+                let name = info.name.clone().unwrap_or_else(|| {
+                    email.split('@').next().unwrap_or_default().to_string()
+                });
+                let mut user = User::new(email.clone(), name, hash_password(&generate_token(32)).await?);
+                user.verify_email();
+                self.user_repository.create(&user).await?;
+                user
+            }
+        };
+
+        self.identity_repository
+            .save(&OAuthIdentity::new(info.provider, info.subject, user.id, Some(email)))
+            .await?;
+
+        Ok(user)
+    }
+}
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    application::auth::AuthenticatedUser,
+    domain::{
+        session::{entity::Session, repository::SessionRepository},
+        user::entity::User,
+    },
+    error::{AppError, AppResult},
+    infrastructure::auth::{
+        jwt::{JwtService, TokenSubject},
+        token::{constant_time_eq, generate_token, hash_token},
+    },
+};
+use super::dto::{ClientInfo, RefreshTokenDto, SessionResponse, TokenResponse};
+
+/// 最終アクセス日時を更新する間隔（毎リクエストの書き込みを避ける）
+const LAST_SEEN_UPDATE_INTERVAL_SECS: i64 = 60;
+/// 再認証なしで重要な操作を許可するログインからの時間
+const RECENT_LOGIN_WINDOW_MINS: i64 = 10;
+
+pub struct SessionService {
+    session_repository: Arc<dyn SessionRepository>,
+    jwt_service: Arc<JwtService>,
+}
+
+impl SessionService {
+    pub fn new(session_repository: Arc<dyn SessionRepository>, jwt_service: Arc<JwtService>) -> Self {
+        Self {
+            session_repository,
+            jwt_service,
+        }
+    }
+
+    /// ログイン成功時にセッションを開始し、トークンを発行
+    pub async fn start_session(&self, user: &User, client: ClientInfo) -> AppResult<TokenResponse> {
+        let secret = generate_token(32);
+        let session = Session::new(
+            user.id,
+            user.role,
+            client.device,
+            client.ip,
+            hash_token(&secret),
+            self.jwt_service.refresh_token_ttl(),
+        );
+        self.session_repository.save(&session).await?;
+        self.issue_tokens(&session, &secret)
+    }
+
+    /// リフレッシュトークンをローテーションし、新しいトークンを発行
+    ///
+    /// 既にローテーション済みのトークンが提示された場合は漏洩とみなし、
+    /// セッションごと失効させます。
+    pub async fn refresh(&self, dto: RefreshTokenDto, client: ClientInfo) -> AppResult<TokenResponse> {
+        dto.validate()?;
+
+        let (session_id, secret) = Self::parse_refresh_token(&dto.refresh_token)?;
+        let mut session = self
+            .session_repository
+            .find_by_id(session_id)
+            .await?
+            .filter(|session| !session.is_expired())
+            .ok_or_else(|| AppError::Unauthorized("Session has expired or been revoked".into()))?;
+
+        if !constant_time_eq(&session.refresh_token_hash, &hash_token(secret)) {
+            log::warn!(
+                "Refresh token reuse detected for session {} of user {}",
+                session.id,
+                session.user_id
+            );
+            self.session_repository.delete(session.user_id, session.id).await?;
+            return Err(AppError::Unauthorized("Refresh token has already been used".into()));
+        }
+
+        // 同じトークンによる並行したローテーションは1つだけが成功する
+        let previous_hash = session.refresh_token_hash.clone();
+        let secret = generate_token(32);
+        session.rotate(hash_token(&secret), client.ip, self.jwt_service.refresh_token_ttl());
+        if !self.session_repository.save_if_unchanged(&session, &previous_hash).await? {
+            log::warn!(
+                "Concurrent refresh token reuse detected for session {} of user {}",
+                session.id,
+                session.user_id
+            );
+            self.session_repository.delete(session.user_id, session.id).await?;
+            return Err(AppError::Unauthorized("Refresh token has already been used".into()));
+        }
+        self.issue_tokens(&session, &secret)
+    }
+
+    /// アクセストークンのセッションが有効か確認
+    pub async fn authenticate(&self, subject: TokenSubject) -> AppResult<AuthenticatedUser> {
+        let mut session = self
+            .session_repository
+            .find_by_id(subject.session_id)
+            .await?
+            .filter(|session| session.user_id == subject.user_id && !session.is_expired())
+            .ok_or_else(|| AppError::Unauthorized("Session has expired or been revoked".into()))?;
+
+        if Utc::now() - session.last_seen_at > Duration::seconds(LAST_SEEN_UPDATE_INTERVAL_SECS) {
+            // ローテーション済みのセッションを古い内容で上書きしない
+            session.touch();
+            let refresh_token_hash = session.refresh_token_hash.clone();
+            self.session_repository.save_if_unchanged(&session, &refresh_token_hash).await?;
+        }
+
+        Ok(AuthenticatedUser::new(session.user_id, session.role, Some(session.id)))
+    }
+
+    /// 現在のセッションが直近のログインによるものか確認
+    ///
+    /// パスワードを持たないユーザー（外部アカウントで登録）の本人確認に使います。
+    pub async fn require_recent_login(&self, user: &AuthenticatedUser) -> AppResult<()> {
+        user.require_session()?;
+        let session = match user.session_id {
+            Some(session_id) => self.session_repository.find_by_id(session_id).await?,
+            None => None,
+        }
+            .ok_or_else(|| AppError::Unauthorized("Session has expired or been revoked".into()))?;
+
+        if !session.is_recent_login(Duration::minutes(RECENT_LOGIN_WINDOW_MINS)) {
+            return Err(AppError::Unauthorized(
+                "Please sign in again to confirm this action".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn list_sessions(&self, user: &AuthenticatedUser) -> AppResult<Vec<SessionResponse>> {
+        user.require_session()?;
+
+        let mut sessions = self.session_repository.find_all_by_user_id(user.user_id).await?;
+        sessions.sort_by(|a, b| b.last_seen_at.cmp(&a.last_seen_at));
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionResponse::from_session(session, user.session_id))
+            .collect())
+    }
+
+    pub async fn revoke_session(&self, user: &AuthenticatedUser, id: Uuid) -> AppResult<()> {
+        user.require_session()?;
+
+        let session = self
+            .session_repository
+            .find_by_id(id)
+            .await?
+            .filter(|session| user.owns(session.user_id))
+            .ok_or_else(|| AppError::NotFound("Session not found".into()))?;
+
+        self.session_repository.delete(session.user_id, session.id).await
+    }
+
+    /// 本人による全端末からのログアウト
+    pub async fn logout_everywhere(&self, user: &AuthenticatedUser) -> AppResult<()> {
+        user.require_session()?;
+        self.revoke_all(user.user_id).await
+    }
+
+    /// ユーザーの全セッションを失効
+    pub async fn revoke_all(&self, user_id: Uuid) -> AppResult<()> {
+        self.session_repository.delete_all_by_user_id(user_id).await
+    }
+
+    /// 現在のセッション以外を失効
+    pub async fn revoke_others(&self, user: &AuthenticatedUser) -> AppResult<()> {
+        for session in self.session_repository.find_all_by_user_id(user.user_id).await? {
+            if Some(session.id) != user.session_id {
+                self.session_repository.delete(session.user_id, session.id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn issue_tokens(&self, session: &Session, secret: &str) -> AppResult<TokenResponse> {
+        Ok(TokenResponse {
+            access_token: self.jwt_service.issue_access_token(session.user_id, session.id)?,
+            refresh_token: format!("{}.{}", session.id, secret),
+            token_type: "Bearer".into(),
+            expires_in: self.jwt_service.access_token_ttl().num_seconds(),
+        })
+    }
+
+    /// `{session_id}.{secret}` 形式のリフレッシュトークンを分解
+    fn parse_refresh_token(token: &str) -> AppResult<(Uuid, &str)> {
+        token
+            .split_once('.')
+            .and_then(|(id, secret)| Some((Uuid::parse_str(id).ok()?, secret)))
+            .filter(|(_, secret)| !secret.is_empty())
+            .ok_or_else(|| AppError::Unauthorized("Malformed refresh token".into()))
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use validator::Validate;
+use super::job::{TagJob, TagJobKind, TagJobStatus};
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RenameTagDto {
+    #[validate(length(min = 1, message = "Tag cannot be empty"))]
+    pub to: String,
+}
+
+/// 複数のタグを1つのタグに統合
+#[derive(Debug, Deserialize, Validate)]
+pub struct MergeTagsDto {
+    #[validate(length(min = 1, max = 50, message = "Between 1 and 50 source tags are required"))]
+    pub sources: Vec<String>,
+    #[validate(length(min = 1, message = "Tag cannot be empty"))]
+    pub target: String,
+}
+
+/// ジョブの進捗
+#[derive(Debug, Serialize)]
+pub struct TagJobResponse {
+    pub id: Uuid,
+    pub kind: TagJobKind,
+    pub sources: Vec<String>,
+    pub target: String,
+    pub status: TagJobStatus,
+    pub total: usize,
+    pub processed: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<TagJob> for TagJobResponse {
+    fn from(job: TagJob) -> Self {
+        Self {
+            id: job.id,
+            kind: job.kind,
+            sources: job.sources,
+            target: job.target,
+            status: job.status,
+            total: job.total,
+            processed: job.processed,
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            completed_at: job.completed_at,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// src/application/tag/job.rs
+
+use std::{sync::Arc, time::Duration};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+use crate::{
+    domain::memo::repository::MemoRepository,
+    error::{AppError, AppResult},
+    infrastructure::persistence::redis::RedisCache,
+};
+
+/// 実行待ち・実行中のジョブIDのセット
+const PENDING_KEY: &str = "tag_job:pending";
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// 最後に更新されてからジョブの状態を保持する期間
+const JOB_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const MAX_ATTEMPTS: u32 = 5;
+/// 1件のメモの書き換えが並行した編集と競合したときに読み直す回数
+const MAX_CONFLICT_RETRIES: u32 = 5;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagJobKind {
+    Rename,
+    Merge,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagJobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// タグの名前変更・統合ジョブ
+///
+/// `sources` のタグを持つメモを1件ずつ `target` に書き換えます。
+/// `total` は対象のメモ数、`processed` は処理済みのメモ数です。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagJob {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: TagJobKind,
+    pub sources: Vec<String>,
+    pub target: String,
+    pub status: TagJobStatus,
+    pub total: usize,
+    pub processed: usize,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl TagJob {
+    pub fn new(user_id: Uuid, kind: TagJobKind, sources: Vec<String>, target: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            kind,
+            sources,
+            target,
+            status: TagJobStatus::Pending,
+            total: 0,
+            processed: 0,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+}
+
+/// タグの書き換えジョブのキューとバックグラウンドワーカー
+///
+/// 書き換え済みのメモには元のタグが残らないため、中断したジョブは
+/// 残りのメモを探し直すだけで再開できます。進捗はメモ1件ごとに保存します。
+pub struct TagJobQueue {
+    redis: Arc<RedisCache>,
+    memo_repository: Arc<dyn MemoRepository>,
+}
+
+impl TagJobQueue {
+    pub fn new(redis: Arc<RedisCache>, memo_repository: Arc<dyn MemoRepository>) -> Self {
+        Self {
+            redis,
+            memo_repository,
+        }
+    }
+
+    fn job_key(id: Uuid) -> String {
+        format!("tag_job:{}", id)
+    }
+
+    pub async fn enqueue(&self, job: &TagJob) -> AppResult<()> {
+        self.save(job).await?;
+        self.redis.add_to_set(PENDING_KEY, &job.id.to_string()).await
+    }
+
+    pub async fn find(&self, id: Uuid) -> AppResult<Option<TagJob>> {
+        self.redis.get::<TagJob>(&Self::job_key(id)).await
+    }
+
+    async fn save(&self, job: &TagJob) -> AppResult<()> {
+        self.redis.set(&Self::job_key(job.id), job, Some(JOB_TTL)).await
+    }
+
+    /// 残りの対象メモを書き換えてジョブを完了させる
+    async fn process(&self, job: &mut TagJob) -> AppResult<()> {
+        let remaining: Vec<Uuid> = self
+            .memo_repository
+            .find_all_by_user_id(job.user_id)
+            .await?
+            .into_iter()
+            .filter(|memo| memo.tags.iter().any(|tag| *tag != job.target && job.sources.contains(tag)))
+            .map(|memo| memo.id)
+            .collect();
+
+        // 再開時は処理済みの件数を引き継ぐ
+        if job.status == TagJobStatus::Pending {
+            job.status = TagJobStatus::Running;
+            job.processed = 0;
+        }
+        job.total = job.processed + remaining.len();
+        job.updated_at = Utc::now();
+        self.save(job).await?;
+
+        for id in remaining {
+            self.replace_tags_in(id, job).await?;
+
+            job.processed += 1;
+            job.updated_at = Utc::now();
+            self.save(job).await?;
+        }
+
+        job.status = TagJobStatus::Completed;
+        job.completed_at = Some(Utc::now());
+        job.updated_at = Utc::now();
+        self.save(job).await
+    }
+
+    /// 1件のメモのタグを書き換える
+    ///
+    /// 一覧の取得後に更新されている可能性があるため最新の内容を読み込み、
+    /// 書き換えの間に編集された場合は読み直してやり直します。
+    async fn replace_tags_in(&self, id: Uuid, job: &TagJob) -> AppResult<()> {
+        let mut retries = 0;
+        loop {
+            let Some(mut memo) = self.memo_repository.find_by_id(id).await? else {
+                return Ok(());
+            };
+            if !memo.replace_tags(&job.sources, &job.target) {
+                return Ok(());
+            }
+
+            match self.memo_repository.update(&memo).await {
+                Err(AppError::Conflict(_)) if retries < MAX_CONFLICT_RETRIES => {
+                    retries += 1;
+                    warn!("Memo {} was edited during tag job {}, retrying", id, job.id);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// 実行待ちのジョブをすべて処理
+    pub async fn process_pending(&self) -> AppResult<()> {
+        for member in self.redis.set_members(PENDING_KEY).await? {
+            let Ok(id) = Uuid::parse_str(&member) else {
+                warn!("Dropping invalid tag job entry: {}", member);
+                self.redis.remove_from_set(PENDING_KEY, &member).await?;
+                continue;
+            };
+
+            let Some(mut job) = self.find(id).await? else {
+                self.redis.remove_from_set(PENDING_KEY, &member).await?;
+                continue;
+            };
+
+            match self.process(&mut job).await {
+                Ok(()) => {
+                    self.redis.remove_from_set(PENDING_KEY, &member).await?;
+                    info!("Completed tag job {} ({} memos)", id, job.total);
+                }
+                Err(e) => {
+                    job.attempts += 1;
+                    job.last_error = Some(e.to_string());
+                    job.updated_at = Utc::now();
+
+                    if job.attempts >= MAX_ATTEMPTS {
+                        error!("Giving up on tag job {} after {} attempts: {}", id, job.attempts, e);
+                        job.status = TagJobStatus::Failed;
+                        self.redis.remove_from_set(PENDING_KEY, &member).await?;
+                    } else {
+                        // 次回の実行で再開
+                        warn!("Failed to process tag job {} (attempt {}): {}", id, job.attempts, e);
+                    }
+                    self.save(&job).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 実行待ちのジョブを定期的に処理し続けるワーカー
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.process_pending().await {
+                error!("Failed to process tag jobs: {}", e);
+            }
+        }
+    }
+}
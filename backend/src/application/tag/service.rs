@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    application::{account::dto::TagUsage, auth::AuthenticatedUser},
+    domain::{api_key::entity::ApiScope, memo::repository::MemoRepository, user::role::Permission},
+    error::{AppError, AppResult},
+};
+use super::{
+    dto::{MergeTagsDto, RenameTagDto, TagJobResponse},
+    job::{TagJob, TagJobKind, TagJobQueue},
+};
+
+/// タグの一覧と、メモ全体にわたるタグの名前変更・統合
+pub struct TagService {
+    memo_repository: Arc<dyn MemoRepository>,
+    jobs: Arc<TagJobQueue>,
+}
+
+impl TagService {
+    pub fn new(memo_repository: Arc<dyn MemoRepository>, jobs: Arc<TagJobQueue>) -> Self {
+        Self {
+            memo_repository,
+            jobs,
+        }
+    }
+
+    /// タグごとの使用数（ゴミ箱のメモは数えない）
+    pub async fn list_tags(&self, user: &AuthenticatedUser) -> AppResult<Vec<TagUsage>> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        let counts = self.memo_repository.count_tags(user.user_id).await?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(tag, count)| TagUsage {
+                tag,
+                count: count as usize,
+            })
+            .collect())
+    }
+
+    /// タグの名前変更を開始（メモの書き換えはバックグラウンドで行う）
+    pub async fn rename_tag(
+        &self,
+        name: String,
+        dto: RenameTagDto,
+        user: &AuthenticatedUser,
+    ) -> AppResult<TagJobResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+        dto.validate()?;
+
+        let source = Self::normalize(&name)?;
+        let target = Self::normalize(&dto.to)?;
+        if source == target {
+            return Err(AppError::BadRequest("The new tag name must differ from the current one".into()));
+        }
+
+        self.start(user.user_id, TagJobKind::Rename, vec![source], target).await
+    }
+
+    /// 複数のタグの統合を開始（メモの書き換えはバックグラウンドで行う）
+    pub async fn merge_tags(&self, dto: MergeTagsDto, user: &AuthenticatedUser) -> AppResult<TagJobResponse> {
+        user.require_scope(ApiScope::MemosWrite)?;
+        user.require_permission(Permission::MemosWrite)?;
+        dto.validate()?;
+
+        let target = Self::normalize(&dto.target)?;
+        let mut sources: Vec<String> = Vec::with_capacity(dto.sources.len());
+        for source in &dto.sources {
+            let source = Self::normalize(source)?;
+            if source != target && !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        if sources.is_empty() {
+            return Err(AppError::BadRequest(
+                "At least one source tag different from the target is required".into(),
+            ));
+        }
+
+        self.start(user.user_id, TagJobKind::Merge, sources, target).await
+    }
+
+    /// ジョブの進捗（他人のジョブは存在しないものとして扱う）
+    pub async fn get_job(&self, id: Uuid, user: &AuthenticatedUser) -> AppResult<TagJobResponse> {
+        user.require_scope(ApiScope::MemosRead)?;
+        user.require_permission(Permission::MemosRead)?;
+
+        self.jobs
+            .find(id)
+            .await?
+            .filter(|job| job.user_id == user.user_id)
+            .map(TagJobResponse::from)
+            .ok_or_else(|| AppError::NotFound("Tag job not found".into()))
+    }
+
+    async fn start(
+        &self,
+        user_id: Uuid,
+        kind: TagJobKind,
+        sources: Vec<String>,
+        target: String,
+    ) -> AppResult<TagJobResponse> {
+        let job = TagJob::new(user_id, kind, sources, target);
+        self.jobs.enqueue(&job).await?;
+        Ok(job.into())
+    }
+
+    fn normalize(tag: &str) -> AppResult<String> {
+        let tag = tag.trim();
+        if tag.is_empty() {
+            return Err(AppError::ValidationError("Tag cannot be empty".into()));
+        }
+        Ok(tag.to_string())
+    }
+}
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use validator::Validate;
+use crate::domain::user::role::Role;
 
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateUserDto {
@@ -21,18 +22,29 @@ pub struct UpdateUserDto {
     pub name: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate)]
 pub struct UpdatePasswordDto {
     pub current_password: String,
     #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
     pub new_password: String,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct LoginDto {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    #[validate(length(min = 1, message = "Password cannot be empty"))]
+    pub password: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct UserResponse {
     pub id: Uuid,
     pub email: String,
     pub name: String,
+    pub role: Role,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub mfa_enabled: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -40,9 +52,12 @@ pub struct UserResponse {
 impl From<crate::domain::user::entity::User> for UserResponse {
     fn from(user: crate::domain::user::entity::User) -> Self {
         Self {
+            mfa_enabled: user.mfa_enabled(),
             id: user.id,
             email: user.email,
             name: user.name,
+            role: user.role,
+            email_verified_at: user.email_verified_at,
             created_at: user.created_at,
             updated_at: user.updated_at,
         }
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+use tracing::error;
+use validator::Validate;
+use crate::{
+    application::{
+        admin::bootstrap::AdminBootstrap,
+        auth::{login_guard::LoginGuard, AuthenticatedUser},
+        mfa::{dto::LoginResponse, service::MfaService},
+        session::{dto::ClientInfo, service::SessionService},
+        verification::service::VerificationService,
+    },
+    domain::user::{entity::User, repository::UserRepository},
+    error::{AppError, AppResult},
+    infrastructure::auth::password::{hash_password, verify_password, DUMMY_PASSWORD_HASH},
+};
+use super::dto::{CreateUserDto, LoginDto, UpdatePasswordDto, UpdateUserDto, UserResponse};
+
+pub struct UserService {
+    user_repository: Arc<dyn UserRepository>,
+    session_service: Arc<SessionService>,
+    mfa_service: Arc<MfaService>,
+    login_guard: Arc<LoginGuard>,
+    verification_service: Arc<VerificationService>,
+    admin_bootstrap: Arc<AdminBootstrap>,
+}
+
+impl UserService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        session_service: Arc<SessionService>,
+        mfa_service: Arc<MfaService>,
+        login_guard: Arc<LoginGuard>,
+        verification_service: Arc<VerificationService>,
+        admin_bootstrap: Arc<AdminBootstrap>,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_service,
+            mfa_service,
+            login_guard,
+            verification_service,
+            admin_bootstrap,
+        }
+    }
+
+    pub async fn register(&self, dto: CreateUserDto) -> AppResult<UserResponse> {
+        dto.validate()?;
+
+        let password_hash = hash_password(&dto.password).await?;
+        let user = User::new(dto.email.trim().to_lowercase(), dto.name, password_hash);
+        self.user_repository.create(&user).await?;
+
+        // 確認メールは再送できるため、登録処理自体は失敗させない
+        if let Err(e) = self.verification_service.send_verification_email(&user).await {
+            error!("Failed to queue verification email for {}: {}", user.id, e);
+        }
+
+        Ok(UserResponse::from(user))
+    }
+
+    pub async fn login(&self, dto: LoginDto, client: ClientInfo) -> AppResult<LoginResponse> {
+        dto.validate()?;
+        self.login_guard.check(&dto.email, &client).await?;
+
+        let user = self
+            .user_repository
+            .find_by_email(&dto.email)
+            .await?
+            .filter(|user| !user.is_pending_deletion());
+
+        // 存在しないアカウントでもダミーのハッシュと照合し、応答時間を揃える
+        let password_hash = user
+            .as_ref()
+            .map_or(DUMMY_PASSWORD_HASH, |user| user.password_hash.as_str());
+        let verified = verify_password(&dto.password, password_hash).await?;
+
+        // 存在しないアカウントや削除待ちのアカウントへの試行も失敗として数える
+        let user = match user {
+            Some(user) if verified => user,
+            _ => {
+                self.login_guard.record_failure(&dto.email, &client).await?;
+                return Err(AppError::Unauthorized("Invalid email or password".into()));
+            }
+        };
+
+        // MFAが有効な場合は第2要素の検証に成功するまで失敗回数をリセットしない
+        if !user.mfa_enabled() {
+            self.login_guard.record_success(&dto.email).await?;
+        }
+        let user = self.admin_bootstrap.apply(user).await?;
+        self.mfa_service.begin_login(&user, client).await
+    }
+
+    pub async fn get_profile(&self, user: &AuthenticatedUser) -> AppResult<UserResponse> {
+        let user = self.find_user(user).await?;
+        Ok(UserResponse::from(user))
+    }
+
+    pub async fn update_profile(
+        &self,
+        user: &AuthenticatedUser,
+        dto: UpdateUserDto,
+    ) -> AppResult<UserResponse> {
+        user.require_session()?;
+        dto.validate()?;
+
+        let mut user = self.find_user(user).await?;
+        user.update_profile(dto.name);
+        self.user_repository.save(&mut user).await?;
+        Ok(UserResponse::from(user))
+    }
+
+    pub async fn update_password(
+        &self,
+        current: &AuthenticatedUser,
+        dto: UpdatePasswordDto,
+    ) -> AppResult<()> {
+        current.require_session()?;
+        dto.validate()?;
+
+        let mut user = self.find_user(current).await?;
+        if !verify_password(&dto.current_password, &user.password_hash).await? {
+            return Err(AppError::Unauthorized("Current password is incorrect".into()));
+        }
+
+        user.update_password(hash_password(&dto.new_password).await?);
+        self.user_repository.save(&mut user).await?;
+
+        // 他の端末のセッションは失効させる
+        self.session_service.revoke_others(current).await
+    }
+
+    async fn find_user(&self, user: &AuthenticatedUser) -> AppResult<User> {
+        self.user_repository
+            .find_by_id(user.user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+}
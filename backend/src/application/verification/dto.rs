@@ -0,0 +1,22 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ForgotPasswordDto {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ResetPasswordDto {
+    #[validate(length(min = 1, message = "Token cannot be empty"))]
+    pub token: String,
+    #[validate(length(min = 8, message = "Password must be at least 8 characters long"))]
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct VerifyEmailDto {
+    #[validate(length(min = 1, message = "Token cannot be empty"))]
+    pub token: String,
+}
@@ -0,0 +1,158 @@
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+use crate::{
+    application::{
+        auth::{login_guard::LoginGuard, AuthenticatedUser},
+        session::{dto::ClientInfo, service::SessionService},
+        user::dto::UserResponse,
+    },
+    domain::user::{entity::User, repository::UserRepository},
+    error::{AppError, AppResult},
+    infrastructure::{
+        auth::{
+            action_token::{ActionTokenService, TokenPurpose},
+            password::hash_password,
+        },
+        mail::{outbox::MailOutbox, EmailMessage},
+    },
+};
+use super::dto::{ForgotPasswordDto, ResetPasswordDto, VerifyEmailDto};
+
+/// パスワード再設定とメールアドレス確認
+///
+/// メールはアウトボックス経由で送信するため、送信の失敗はリクエストの失敗になりません。
+pub struct VerificationService {
+    user_repository: Arc<dyn UserRepository>,
+    session_service: Arc<SessionService>,
+    action_tokens: Arc<ActionTokenService>,
+    outbox: Arc<MailOutbox>,
+    login_guard: Arc<LoginGuard>,
+    /// メール内のリンクに使うフロントエンドのURL
+    public_url: String,
+}
+
+impl VerificationService {
+    pub fn new(
+        user_repository: Arc<dyn UserRepository>,
+        session_service: Arc<SessionService>,
+        action_tokens: Arc<ActionTokenService>,
+        outbox: Arc<MailOutbox>,
+        login_guard: Arc<LoginGuard>,
+        public_url: String,
+    ) -> Self {
+        Self {
+            user_repository,
+            session_service,
+            action_tokens,
+            outbox,
+            login_guard,
+            public_url,
+        }
+    }
+
+    /// 確認メールを送信キューに登録
+    pub async fn send_verification_email(&self, user: &User) -> AppResult<()> {
+        let token = self
+            .action_tokens
+            .issue(TokenPurpose::EmailVerification, user.id)
+            .await?;
+        let link = format!("{}/verify-email?token={}", self.public_url, token);
+
+        self.outbox
+            .enqueue(EmailMessage {
+                to: user.email.clone(),
+                subject: "Verify your email address".into(),
+                body: format!(
+                    "Hello {},\n\nPlease confirm your email address by opening the link below.\n\n{}\n\nThis link expires in {} hours.\n",
+                    user.name,
+                    link,
+                    TokenPurpose::EmailVerification.ttl().num_hours(),
+                ),
+            })
+            .await
+    }
+
+    /// 確認メールの再送
+    pub async fn resend_verification_email(&self, current: &AuthenticatedUser) -> AppResult<()> {
+        current.require_session()?;
+
+        let user = self.find_user(current.user_id).await?;
+        if user.email_verified() {
+            return Err(AppError::Conflict("Email is already verified".into()));
+        }
+
+        self.send_verification_email(&user).await
+    }
+
+    pub async fn verify_email(&self, dto: VerifyEmailDto) -> AppResult<UserResponse> {
+        dto.validate()?;
+
+        let user_id = self
+            .action_tokens
+            .consume(TokenPurpose::EmailVerification, &dto.token)
+            .await?;
+        let mut user = self.find_user(user_id).await?;
+
+        if !user.email_verified() {
+            user.verify_email();
+            self.user_repository.save(&mut user).await?;
+        }
+
+        Ok(UserResponse::from(user))
+    }
+
+    /// パスワード再設定メールの送信（登録の有無は応答から判別できないようにする）
+    pub async fn request_password_reset(&self, dto: ForgotPasswordDto, client: ClientInfo) -> AppResult<()> {
+        dto.validate()?;
+        self.login_guard.check_password_reset(&dto.email, &client).await?;
+
+        let Some(user) = self.user_repository.find_by_email(&dto.email).await? else {
+            return Ok(());
+        };
+
+        let token = self
+            .action_tokens
+            .issue(TokenPurpose::PasswordReset, user.id)
+            .await?;
+        let link = format!("{}/reset-password?token={}", self.public_url, token);
+
+        self.outbox
+            .enqueue(EmailMessage {
+                to: user.email.clone(),
+                subject: "Reset your password".into(),
+                body: format!(
+                    "Hello {},\n\nWe received a request to reset your password. Open the link below to choose a new one.\n\n{}\n\nThis link expires in {} minutes. If you did not request this, you can ignore this email.\n",
+                    user.name,
+                    link,
+                    TokenPurpose::PasswordReset.ttl().num_minutes(),
+                ),
+            })
+            .await
+    }
+
+    pub async fn reset_password(&self, dto: ResetPasswordDto) -> AppResult<()> {
+        dto.validate()?;
+
+        let user_id = self
+            .action_tokens
+            .consume(TokenPurpose::PasswordReset, &dto.token)
+            .await?;
+        let mut user = self.find_user(user_id).await?;
+
+        user.update_password(hash_password(&dto.new_password).await?);
+        // 再設定メールを受け取れたことで所有確認も済んでいる
+        user.verify_email();
+        self.user_repository.save(&mut user).await?;
+
+        // 既存のセッションはすべて失効させる
+        self.session_service.revoke_all(user.id).await
+    }
+
+    async fn find_user(&self, user_id: Uuid) -> AppResult<User> {
+        self.user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".into()))
+    }
+}
@@ -0,0 +1,128 @@
+// src/domain/api_key/entity.rs
+
+use std::{fmt, str::FromStr};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// APIキーに付与できる権限
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ApiScope {
+    #[serde(rename = "memos:read")]
+    MemosRead,
+    #[serde(rename = "memos:write")]
+    MemosWrite,
+    #[serde(rename = "search")]
+    Search,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::MemosRead => "memos:read",
+            ApiScope::MemosWrite => "memos:write",
+            ApiScope::Search => "search",
+        }
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "memos:read" => Ok(ApiScope::MemosRead),
+            "memos:write" => Ok(ApiScope::MemosWrite),
+            "search" => Ok(ApiScope::Search),
+            other => Err(format!("Unknown scope: {}", other)),
+        }
+    }
+}
+
+/// スクリプトや外部連携用の個人APIキー
+///
+/// キー本体は発行時に一度だけ表示し、保存するのはハッシュのみです。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// 一覧表示で識別するためのキー先頭部分
+    pub prefix: String,
+    pub key_hash: String,
+    pub scopes: Vec<ApiScope>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn new(
+        user_id: Uuid,
+        name: String,
+        prefix: String,
+        key_hash: String,
+        scopes: Vec<ApiScope>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name,
+            prefix,
+            key_hash,
+            scopes,
+            created_at: Utc::now(),
+            expires_at,
+            last_used_at: None,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    pub fn touch(&mut self) {
+        self.last_used_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_scope_round_trip() {
+        for scope in [ApiScope::MemosRead, ApiScope::MemosWrite, ApiScope::Search] {
+            assert_eq!(scope.as_str().parse::<ApiScope>().unwrap(), scope);
+            assert_eq!(
+                serde_json::to_string(&scope).unwrap(),
+                format!("\"{}\"", scope.as_str())
+            );
+        }
+        assert!("admin".parse::<ApiScope>().is_err());
+    }
+
+    #[test]
+    fn test_api_key_expiry() {
+        let mut key = ApiKey::new(
+            Uuid::new_v4(),
+            "CI".to_string(),
+            "mk_abcd1234".to_string(),
+            "hash".to_string(),
+            vec![ApiScope::MemosRead],
+            None,
+        );
+        assert!(!key.is_expired());
+
+        key.expires_at = Some(Utc::now() - Duration::seconds(1));
+        assert!(key.is_expired());
+    }
+}
@@ -0,0 +1,17 @@
+// src/domain/api_key/repository.rs
+
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::error::AppResult;
+use super::entity::ApiKey;
+
+#[async_trait]
+pub trait ApiKeyRepository: Send + Sync {
+    async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> AppResult<Option<ApiKey>>;
+    async fn find_by_hash(&self, key_hash: &str) -> AppResult<Option<ApiKey>>;
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<ApiKey>>;
+    async fn save(&self, api_key: &ApiKey) -> AppResult<()>;
+    /// 最終使用日時のみを更新（取り消し済みのキーは書き戻さない）
+    async fn touch(&self, api_key: &ApiKey) -> AppResult<()>;
+    async fn delete(&self, api_key: &ApiKey) -> AppResult<()>;
+}
@@ -0,0 +1,75 @@
+// src/domain/audit/entity.rs
+
+use std::{fmt, str::FromStr};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 監査ログに記録するセキュリティイベントの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEventKind {
+    AccountLocked,
+    IpLocked,
+}
+
+impl AuditEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEventKind::AccountLocked => "account_locked",
+            AuditEventKind::IpLocked => "ip_locked",
+        }
+    }
+}
+
+impl fmt::Display for AuditEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditEventKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "account_locked" => Ok(AuditEventKind::AccountLocked),
+            "ip_locked" => Ok(AuditEventKind::IpLocked),
+            other => Err(format!("Unknown audit event kind: {}", other)),
+        }
+    }
+}
+
+/// セキュリティ監査イベント
+///
+/// `subject` はイベントの対象（メールアドレスやIPアドレス）です。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: Uuid,
+    pub kind: AuditEventKind,
+    pub subject: String,
+    pub user_id: Option<Uuid>,
+    pub ip: Option<String>,
+    pub detail: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl AuditEvent {
+    pub fn new(
+        kind: AuditEventKind,
+        subject: String,
+        user_id: Option<Uuid>,
+        ip: Option<String>,
+        detail: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            kind,
+            subject,
+            user_id,
+            ip,
+            detail,
+            occurred_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,10 @@
+// src/domain/audit/repository.rs
+
+use async_trait::async_trait;
+use crate::error::AppResult;
+use super::entity::AuditEvent;
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn save(&self, event: &AuditEvent) -> AppResult<()>;
+}
@@ -0,0 +1,220 @@
+// src/domain/folder/entity.rs
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const MAX_NAME_LENGTH: usize = 100;
+/// フォルダの入れ子の最大の深さ
+pub const MAX_DEPTH: usize = 10;
+
+/// メモを整理するフォルダ（ノートブック）
+///
+/// `parent_id` が `None` のフォルダは最上位に置かれます。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Folder {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Folder {
+    pub fn new(user_id: Uuid, name: String, parent_id: Option<Uuid>) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            parent_id,
+            name,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn rename(&mut self, name: String) {
+        self.name = name;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn move_to(&mut self, parent_id: Option<Uuid>) {
+        self.parent_id = parent_id;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn validate(&self) -> bool {
+        let name = self.name.trim();
+        !name.is_empty() && name.chars().count() <= MAX_NAME_LENGTH && !name.contains('/')
+    }
+}
+
+/// ユーザーのフォルダ階層
+pub struct FolderTree {
+    folders: HashMap<Uuid, Folder>,
+}
+
+impl FolderTree {
+    pub fn new(folders: Vec<Folder>) -> Self {
+        Self {
+            folders: folders.into_iter().map(|folder| (folder.id, folder)).collect(),
+        }
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&Folder> {
+        self.folders.get(&id)
+    }
+
+    /// フォルダを追加（同じIDのフォルダは置き換える）
+    pub fn insert(&mut self, folder: Folder) {
+        self.folders.insert(folder.id, folder);
+    }
+
+    /// 最上位から指定フォルダまでのID（フォルダが存在しない場合は空）
+    pub fn path(&self, id: Uuid) -> Vec<Uuid> {
+        let mut path = Vec::new();
+        let mut current = self.folders.get(&id);
+
+        // 親の循環があっても停止するよう、フォルダ数を上限にたどる
+        while let Some(folder) = current {
+            if path.len() > self.folders.len() {
+                break;
+            }
+            path.push(folder.id);
+            current = folder.parent_id.and_then(|parent_id| self.folders.get(&parent_id));
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// 最上位から指定フォルダまでの名前をつなげたパス（例: `仕事/議事録`）
+    pub fn display_path(&self, id: Uuid) -> String {
+        self.path(id)
+            .iter()
+            .filter_map(|id| self.folders.get(id))
+            .map(|folder| folder.name.as_str())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// 最上位のフォルダ
+    pub fn roots(&self) -> Vec<&Folder> {
+        self.folders
+            .values()
+            .filter(|folder| folder.parent_id.is_none())
+            .collect()
+    }
+
+    pub fn children(&self, id: Uuid) -> Vec<&Folder> {
+        self.folders
+            .values()
+            .filter(|folder| folder.parent_id == Some(id))
+            .collect()
+    }
+
+    /// 指定フォルダとその配下のすべてのフォルダのID
+    pub fn subtree(&self, id: Uuid) -> Vec<Uuid> {
+        let mut subtree = vec![id];
+        let mut index = 0;
+
+        while index < subtree.len() {
+            let children = self.children(subtree[index]);
+            subtree.extend(children.iter().map(|folder| folder.id));
+            index += 1;
+        }
+
+        subtree
+    }
+
+    /// 配下のフォルダを含めた最大の深さ（指定フォルダ自身は1）
+    pub fn height(&self, id: Uuid) -> usize {
+        1 + self
+            .children(id)
+            .iter()
+            .map(|child| self.height(child.id))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// フォルダを新しい親の下へ移動できるか（自身や配下への移動と深さの超過を拒否）
+    pub fn can_move(&self, id: Uuid, parent_id: Option<Uuid>) -> bool {
+        let Some(parent_id) = parent_id else {
+            return self.height(id) <= MAX_DEPTH;
+        };
+        if !self.folders.contains_key(&parent_id) || self.path(parent_id).contains(&id) {
+            return false;
+        }
+
+        self.path(parent_id).len() + self.height(id) <= MAX_DEPTH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(name: &str, parent: Option<&Folder>) -> Folder {
+        Folder::new(Uuid::nil(), name.to_string(), parent.map(|parent| parent.id))
+    }
+
+    #[test]
+    fn test_folder_name_validation() {
+        assert!(folder("仕事", None).validate());
+        assert!(!folder("  ", None).validate());
+        assert!(!folder("a/b", None).validate());
+        assert!(!folder(&"a".repeat(MAX_NAME_LENGTH + 1), None).validate());
+    }
+
+    #[test]
+    fn test_path_and_subtree() {
+        let work = folder("仕事", None);
+        let minutes = folder("議事録", Some(&work));
+        let weekly = folder("週次", Some(&minutes));
+        let private = folder("個人", None);
+        let tree = FolderTree::new(vec![
+            work.clone(),
+            minutes.clone(),
+            weekly.clone(),
+            private.clone(),
+        ]);
+
+        assert_eq!(tree.path(weekly.id), vec![work.id, minutes.id, weekly.id]);
+        assert_eq!(tree.display_path(weekly.id), "仕事/議事録/週次");
+        assert_eq!(tree.subtree(work.id), vec![work.id, minutes.id, weekly.id]);
+        assert_eq!(tree.height(work.id), 3);
+        assert!(tree.path(Uuid::new_v4()).is_empty());
+    }
+
+    #[test]
+    fn test_cannot_move_into_own_subtree() {
+        let work = folder("仕事", None);
+        let minutes = folder("議事録", Some(&work));
+        let private = folder("個人", None);
+        let tree = FolderTree::new(vec![work.clone(), minutes.clone(), private.clone()]);
+
+        assert!(!tree.can_move(work.id, Some(work.id)));
+        assert!(!tree.can_move(work.id, Some(minutes.id)));
+        assert!(!tree.can_move(work.id, Some(Uuid::new_v4())));
+        assert!(tree.can_move(work.id, Some(private.id)));
+        assert!(tree.can_move(minutes.id, None));
+    }
+
+    #[test]
+    fn test_cannot_exceed_max_depth() {
+        let mut folders = vec![folder("0", None)];
+        for depth in 1..MAX_DEPTH {
+            let parent = folders[depth - 1].clone();
+            folders.push(folder(&depth.to_string(), Some(&parent)));
+        }
+        let other = folder("other", None);
+        let deepest = folders[MAX_DEPTH - 1].id;
+        folders.push(other.clone());
+        let tree = FolderTree::new(folders);
+
+        assert_eq!(tree.path(deepest).len(), MAX_DEPTH);
+        assert!(!tree.can_move(other.id, Some(deepest)));
+    }
+}
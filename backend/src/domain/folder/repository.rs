@@ -0,0 +1,14 @@
+// src/domain/folder/repository.rs
+
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::error::AppResult;
+use super::entity::Folder;
+
+#[async_trait]
+pub trait FolderRepository: Send + Sync {
+    async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> AppResult<Option<Folder>>;
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Folder>>;
+    async fn save(&self, folder: &Folder) -> AppResult<()>;
+    async fn delete(&self, folder: &Folder) -> AppResult<()>;
+}
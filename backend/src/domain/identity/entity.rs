@@ -0,0 +1,27 @@
+// src/domain/identity/entity.rs
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 外部プロバイダー（OAuth/OIDC）のアカウントとユーザーの紐付け
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthIdentity {
+    pub provider: String,
+    pub subject: String,
+    pub user_id: Uuid,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl OAuthIdentity {
+    pub fn new(provider: String, subject: String, user_id: Uuid, email: Option<String>) -> Self {
+        Self {
+            provider,
+            subject,
+            user_id,
+            email,
+            created_at: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+// src/domain/identity/repository.rs
+
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::error::AppResult;
+use super::entity::OAuthIdentity;
+
+#[async_trait]
+pub trait OAuthIdentityRepository: Send + Sync {
+    async fn find(&self, provider: &str, subject: &str) -> AppResult<Option<OAuthIdentity>>;
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<OAuthIdentity>>;
+    async fn save(&self, identity: &OAuthIdentity) -> AppResult<()>;
+    async fn delete(&self, identity: &OAuthIdentity) -> AppResult<()>;
+}
@@ -19,6 +19,29 @@ pub struct Memo {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub version: i32,
+    /// ゴミ箱に移動した日時（ゴミ箱にない場合は `None`）
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// 所属フォルダ（最上位の場合は `None`）
+    #[serde(default)]
+    pub folder_id: Option<Uuid>,
+    /// 一覧の先頭に固定
+    #[serde(default)]
+    pub pinned: bool,
+    /// アーカイブ済み（通常の一覧から外して保管）
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub favorite: bool,
+}
+
+/// メモの整理用のフラグ
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoFlag {
+    Pinned,
+    Archived,
+    Favorite,
 }
 
 impl Memo {
@@ -33,6 +56,11 @@ impl Memo {
             created_at: now,
             updated_at: now,
             version: 1,
+            deleted_at: None,
+            folder_id: None,
+            pinned: false,
+            archived: false,
+            favorite: false,
         }
     }
 
@@ -50,6 +78,83 @@ impl Memo {
         self.version += 1;
     }
 
+    /// 過去のバージョンの内容に戻す
+    ///
+    /// 履歴を書き換えず、復元した内容を新しいバージョンとして記録します。
+    pub fn restore(&mut self, snapshot: &MemoVersion) {
+        self.update(
+            Some(snapshot.title.clone()),
+            Some(snapshot.content.clone()),
+            Some(snapshot.tags.clone()),
+        );
+    }
+
+    /// いずれかのタグを `target` に置き換える（置き換えた場合は新しいバージョンになる）
+    ///
+    /// 置き換えで重複したタグは最初の位置のものだけを残します。
+    pub fn replace_tags(&mut self, sources: &[String], target: &str) -> bool {
+        if !self.tags.iter().any(|tag| tag != target && sources.contains(tag)) {
+            return false;
+        }
+
+        let mut tags: Vec<String> = Vec::with_capacity(self.tags.len());
+        for tag in &self.tags {
+            let tag = if sources.contains(tag) { target } else { tag.as_str() };
+            if !tags.iter().any(|existing| existing == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+
+        self.update(None, None, Some(tags));
+        true
+    }
+
+    /// フォルダを移動（内容は変わらないためバージョンは進めない）
+    pub fn move_to_folder(&mut self, folder_id: Option<Uuid>) {
+        self.folder_id = folder_id;
+    }
+
+    pub fn flag(&self, flag: MemoFlag) -> bool {
+        match flag {
+            MemoFlag::Pinned => self.pinned,
+            MemoFlag::Archived => self.archived,
+            MemoFlag::Favorite => self.favorite,
+        }
+    }
+
+    /// フラグを設定（内容は変わらないためバージョンは進めず、更新日時のみ進める）
+    ///
+    /// アーカイブしたメモは固定を解除します。
+    pub fn set_flag(&mut self, flag: MemoFlag, value: bool) {
+        self.updated_at = Utc::now();
+        match flag {
+            MemoFlag::Pinned => self.pinned = value,
+            MemoFlag::Archived => {
+                self.archived = value;
+                if value {
+                    self.pinned = false;
+                }
+            }
+            MemoFlag::Favorite => self.favorite = value,
+        }
+    }
+
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// ゴミ箱に移動（内容は変わらないためバージョンは進めない）
+    pub fn move_to_trash(&mut self) {
+        if self.deleted_at.is_none() {
+            self.deleted_at = Some(Utc::now());
+        }
+    }
+
+    /// ゴミ箱から元に戻す
+    pub fn restore_from_trash(&mut self) {
+        self.deleted_at = None;
+    }
+
     pub fn validate(&self) -> bool {
         !self.title.trim().is_empty() 
             && !self.content.trim().is_empty()
@@ -58,6 +163,31 @@ impl Memo {
     }
 }
 
+/// メモの保存時点のスナップショット（変更不可）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoVersion {
+    pub memo_id: Uuid,
+    pub version: i32,
+    pub title: String,
+    pub content: String,
+    pub tags: Vec<String>,
+    /// このバージョンが保存された日時
+    pub saved_at: DateTime<Utc>,
+}
+
+impl MemoVersion {
+    pub fn snapshot(memo: &Memo) -> Self {
+        Self {
+            memo_id: memo.id,
+            version: memo.version,
+            title: memo.title.clone(),
+            content: memo.content.clone(),
+            tags: memo.tags.clone(),
+            saved_at: memo.updated_at,
+        }
+    }
+}
+
 impl SerializeRow for Memo {
     fn serialize<'b>(
         &self,
@@ -71,6 +201,11 @@ impl SerializeRow for Memo {
         self.created_at.timestamp_millis().serialize(buf)?;
         self.updated_at.timestamp_millis().serialize(buf)?;
         self.version.serialize(buf)?;
+        self.deleted_at.map(|at| at.timestamp_millis()).serialize(buf)?;
+        self.folder_id.serialize(buf)?;
+        self.pinned.serialize(buf)?;
+        self.archived.serialize(buf)?;
+        self.favorite.serialize(buf)?;
         Ok(buf)
     }
 }
@@ -116,4 +251,89 @@ mod tests {
         assert_eq!(memo.version, 2);
         assert!(memo.updated_at > original_updated_at);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_memo_restore_creates_new_version() {
+        let mut memo = Memo::new(
+            "Original Title".to_string(),
+            "Original Content".to_string(),
+            vec!["original".to_string()],
+            Uuid::new_v4(),
+        );
+        let first = MemoVersion::snapshot(&memo);
+
+        memo.update(Some("New Title".to_string()), Some("New Content".to_string()), None);
+        memo.restore(&first);
+
+        assert_eq!(memo.title, "Original Title");
+        assert_eq!(memo.content, "Original Content");
+        assert_eq!(memo.tags, vec!["original"]);
+        assert_eq!(memo.version, 3);
+        assert_eq!(first.version, 1);
+    }
+
+    #[test]
+    fn test_memo_trash_and_restore() {
+        let mut memo = Memo::new(
+            "Title".to_string(),
+            "Content".to_string(),
+            vec![],
+            Uuid::new_v4(),
+        );
+
+        memo.move_to_trash();
+        let deleted_at = memo.deleted_at;
+        assert!(memo.is_trashed());
+
+        // 二度目の移動では日時を更新しない
+        memo.move_to_trash();
+        assert_eq!(memo.deleted_at, deleted_at);
+        assert_eq!(memo.version, 1);
+
+        memo.restore_from_trash();
+        assert!(!memo.is_trashed());
+    }
+
+    #[test]
+    fn test_memo_replace_tags() {
+        let mut memo = Memo::new(
+            "Title".to_string(),
+            "Content".to_string(),
+            vec!["js".to_string(), "rust".to_string(), "javascript".to_string()],
+            Uuid::new_v4(),
+        );
+        let sources = vec!["js".to_string(), "javascript".to_string()];
+
+        assert!(memo.replace_tags(&sources, "javascript"));
+        assert_eq!(memo.tags, vec!["javascript", "rust"]);
+        assert_eq!(memo.version, 2);
+
+        // 置き換える対象がなければ変更しない
+        assert!(!memo.replace_tags(&sources, "javascript"));
+        assert_eq!(memo.version, 2);
+    }
+
+    #[test]
+    fn test_memo_flags() {
+        let mut memo = Memo::new(
+            "Title".to_string(),
+            "Content".to_string(),
+            vec![],
+            Uuid::new_v4(),
+        );
+
+        let created_at = memo.updated_at;
+        memo.set_flag(MemoFlag::Pinned, true);
+        memo.set_flag(MemoFlag::Favorite, true);
+        assert!(memo.updated_at >= created_at);
+        assert!(memo.flag(MemoFlag::Pinned));
+        assert!(memo.flag(MemoFlag::Favorite));
+
+        // アーカイブすると固定は解除される
+        memo.set_flag(MemoFlag::Archived, true);
+        assert!(memo.archived);
+        assert!(!memo.pinned);
+        assert!(memo.favorite);
+        assert_eq!(memo.version, 1);
+    }
+}
@@ -1,14 +1,215 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
 use uuid::Uuid;
 use crate::error::AppResult;
-use super::entity::Memo;
+use super::entity::{Memo, MemoFlag, MemoVersion};
+
+/// メモ一覧の並び順の基準
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoSort {
+    #[default]
+    UpdatedAt,
+    CreatedAt,
+    Title,
+}
+
+impl MemoSort {
+    /// 並び順を指定しなかった場合の向き（日時は新しい順、タイトルは昇順）
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            Self::UpdatedAt | Self::CreatedAt => SortOrder::Desc,
+            Self::Title => SortOrder::Asc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// メモ一覧の1ページ
+#[derive(Debug)]
+pub struct MemoPage {
+    pub memos: Vec<Memo>,
+    /// 次のページのカーソル（最後のページでは `None`）
+    pub next_cursor: Option<String>,
+}
+
+/// メモの検索条件
+#[derive(Debug, Clone)]
+pub struct MemoSearch {
+    pub user_id: Uuid,
+    /// 検索式（`None` の場合は絞り込みのみ）
+    pub query: Option<SearchExpr>,
+    pub tag: Option<String>,
+    /// 指定フォルダとその配下のフォルダに絞り込む
+    pub folder_id: Option<Uuid>,
+    pub paging: SearchPaging,
+    pub highlight: HighlightOptions,
+    /// 絞り込み候補の集計を同じ検索で行う
+    ///
+    /// タグ・フォルダの候補は、それぞれ自身の絞り込みを除いた検索結果から集計します。
+    pub facets: bool,
+}
+
+/// 検索式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchExpr {
+    And(Vec<SearchExpr>),
+    Or(Vec<SearchExpr>),
+    Not(Box<SearchExpr>),
+    /// 語句（`phrase` の場合は語順どおりに連続して一致するもの）
+    Text {
+        field: TextField,
+        text: String,
+        phrase: bool,
+    },
+    Tag(String),
+    /// 日時の範囲（`from` 以上、`until` 未満）
+    Date {
+        field: DateField,
+        from: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    },
+}
+
+/// 語句を探すフィールド
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextField {
+    /// タイトルと本文
+    Any,
+    Title,
+    Content,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// 入力途中の語句に対する補完候補
+#[derive(Debug, Clone, Default)]
+pub struct MemoSuggestions {
+    /// タイトルが前方一致したメモ（ID とタイトル）
+    pub titles: Vec<(Uuid, String)>,
+    /// 前方一致したタグと使用数（使用数の多い順）
+    pub tags: Vec<(String, u64)>,
+}
+
+/// 検索語に一致した箇所の強調表示の設定
+#[derive(Debug, Clone)]
+pub struct HighlightOptions {
+    pub pre_tag: String,
+    pub post_tag: String,
+    /// 本文の断片の最大文字数
+    pub fragment_size: usize,
+}
+
+/// 検索結果のページ指定
+///
+/// `search_after` を指定した場合は `from` を使わず、前のページの最後の結果の続きを返します。
+#[derive(Debug, Clone)]
+pub struct SearchPaging {
+    pub from: usize,
+    pub size: usize,
+    pub search_after: Option<String>,
+}
+
+/// 検索結果の1件
+#[derive(Debug)]
+pub struct MemoSearchHit {
+    pub memo: Memo,
+    /// 一致した箇所を強調したタイトル（一致しなかった場合は空）
+    pub title_highlights: Vec<String>,
+    /// 一致した箇所を含む本文の断片（一致度の高い順）
+    pub content_highlights: Vec<String>,
+}
+
+/// 検索結果の1ページ
+#[derive(Debug)]
+pub struct MemoSearchPage {
+    pub hits: Vec<MemoSearchHit>,
+    /// 条件に一致したメモの総数
+    pub total: u64,
+    /// 次のページを `search_after` で取得するためのカーソル（最後のページでは `None`）
+    pub search_after: Option<String>,
+    /// 絞り込み候補（集計を指定しなかった場合は `None`）
+    pub facets: Option<SearchFacets>,
+}
+
+/// 検索条件に一致したメモ全体の集計
+#[derive(Debug, Clone, Default)]
+pub struct SearchFacets {
+    /// 件数の多いタグ
+    pub tags: Vec<(String, u64)>,
+    /// 作成月（`YYYY-MM`、UTC）ごとのメモ数（古い順）
+    pub created_months: Vec<(String, u64)>,
+    /// フォルダごとのメモ数（配下のフォルダのメモを含む）
+    pub folders: Vec<(Uuid, u64)>,
+    pub pinned: u64,
+    pub archived: u64,
+    pub favorite: u64,
+}
 
 #[async_trait]
 pub trait MemoRepository: Send + Sync {
     async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Memo>>;
     async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Memo>>;
-    async fn save(&self, memo: &Memo) -> AppResult<()>;
+    /// ユーザーのメモを並び順に沿って1ページ分取得
+    ///
+    /// `cursor` は前のページの `next_cursor` です。`filter` に合わないメモは読み飛ばすため、
+    /// 最後のページでなくても `limit` 件に満たない場合があります。
+    /// `filter` には本文を読み込んでいないメモも渡されるため、本文で絞り込むことはできません。
+    async fn find_page_by_user_id(
+        &self,
+        user_id: Uuid,
+        sort: MemoSort,
+        order: SortOrder,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+    ) -> AppResult<MemoPage>;
+    /// `find_page_by_user_id` と同じ1ページ分のメモを、本文を読み込まずに取得（本文は空）
+    async fn find_summary_page_by_user_id(
+        &self,
+        user_id: Uuid,
+        sort: MemoSort,
+        order: SortOrder,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+    ) -> AppResult<MemoPage>;
+    /// 新しいメモを保存し、最初のバージョンのスナップショットを履歴に記録
+    async fn create(&self, memo: &Memo) -> AppResult<()>;
+    /// 内容の変更を保存し、新しいバージョンのスナップショットを履歴に記録
+    ///
+    /// `memo` は読み込んだメモを `Memo::update` で1つ新しいバージョンにしたものです。
+    /// 読み込んだ後に他の保存でバージョンが進んでいれば Conflict を返します。
+    async fn update(&self, memo: &Memo) -> AppResult<()>;
+    /// 所属フォルダとゴミ箱の状態のみを保存（バージョンは進めない）
+    async fn update_metadata(&self, memo: &Memo) -> AppResult<()>;
+    /// 指定したフラグと更新日時のみを保存（バージョンは進めない）
+    async fn update_flag(&self, memo: &Memo, flag: MemoFlag) -> AppResult<()>;
+    /// メモの履歴（新しい順）
+    async fn find_versions(&self, memo_id: Uuid) -> AppResult<Vec<MemoVersion>>;
+    async fn find_version(&self, memo_id: Uuid, version: i32) -> AppResult<Option<MemoVersion>>;
     async fn delete(&self, id: Uuid) -> AppResult<()>;
-    async fn search(&self, query: &str, tag: Option<String>, user_id: Uuid) -> AppResult<Vec<Memo>>;
+    /// ユーザーのメモをすべての保存先から削除（再実行可能）
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> AppResult<()>;
+    /// 検索インデックスのみを更新（所属フォルダの移動などで検索用のパスが変わった場合）
+    ///
+    /// `folder_path` は最上位から所属フォルダまでのフォルダIDです。
+    async fn reindex(&self, memo: &Memo, folder_path: &[Uuid]) -> AppResult<()>;
+    async fn search(&self, search: &MemoSearch) -> AppResult<MemoSearchPage>;
+    /// タイトルとタグの補完候補（それぞれ最大 `limit` 件）
+    async fn suggest(&self, user_id: Uuid, prefix: &str, limit: usize) -> AppResult<MemoSuggestions>;
+    /// ゴミ箱にないメモのタグごとの使用数（使用数の多い順）
+    async fn count_tags(&self, user_id: Uuid) -> AppResult<Vec<(String, u64)>>;
     async fn exists(&self, id: Uuid) -> AppResult<bool>;
 }
\ No newline at end of file
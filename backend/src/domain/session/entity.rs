@@ -0,0 +1,116 @@
+// src/domain/session/entity.rs
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::domain::user::role::Role;
+
+/// ログインセッション
+///
+/// リフレッシュトークンはハッシュのみを保持し、ローテーションのたびに置き換えます。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// 認可に使うユーザーのロール（変更時は全セッションを失効）
+    #[serde(default)]
+    pub role: Role,
+    pub device: Option<String>,
+    pub ip: Option<String>,
+    pub refresh_token_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn new(
+        user_id: Uuid,
+        role: Role,
+        device: Option<String>,
+        ip: Option<String>,
+        refresh_token_hash: String,
+        ttl: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            role,
+            device,
+            ip,
+            refresh_token_hash,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: now + ttl,
+        }
+    }
+
+    /// リフレッシュトークンのローテーション
+    pub fn rotate(&mut self, refresh_token_hash: String, ip: Option<String>, ttl: Duration) {
+        let now = Utc::now();
+        self.refresh_token_hash = refresh_token_hash;
+        if ip.is_some() {
+            self.ip = ip;
+        }
+        self.last_seen_at = now;
+        self.expires_at = now + ttl;
+    }
+
+    pub fn touch(&mut self) {
+        self.last_seen_at = Utc::now();
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+
+    /// ログインから `window` 以内か（ローテーションではログイン日時は変わらない）
+    pub fn is_recent_login(&self, window: Duration) -> bool {
+        Utc::now() - self.created_at <= window
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_session() {
+        let mut session = Session::new(
+            Uuid::new_v4(),
+            Role::Member,
+            Some("Firefox".to_string()),
+            Some("192.0.2.1".to_string()),
+            "first_hash".to_string(),
+            Duration::days(30),
+        );
+        let original_expires_at = session.expires_at;
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        session.rotate("second_hash".to_string(), None, Duration::days(30));
+
+        assert_eq!(session.refresh_token_hash, "second_hash");
+        assert_eq!(session.ip.as_deref(), Some("192.0.2.1"));
+        assert!(session.expires_at > original_expires_at);
+        assert!(!session.is_expired());
+    }
+
+    #[test]
+    fn test_recent_login_survives_rotation() {
+        let mut session = Session::new(
+            Uuid::new_v4(),
+            Role::Member,
+            None,
+            None,
+            "first_hash".to_string(),
+            Duration::days(30),
+        );
+        assert!(session.is_recent_login(Duration::minutes(10)));
+
+        session.created_at = Utc::now() - Duration::hours(1);
+        session.rotate("second_hash".to_string(), None, Duration::days(30));
+
+        assert!(!session.is_recent_login(Duration::minutes(10)));
+    }
+}
@@ -0,0 +1,19 @@
+// src/domain/session/repository.rs
+
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::error::AppResult;
+use super::entity::Session;
+
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Session>>;
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Session>>;
+    async fn save(&self, session: &Session) -> AppResult<()>;
+    /// 保存済みのリフレッシュトークンのハッシュが `refresh_token_hash` のままの場合だけ保存
+    ///
+    /// 保存したかどうかを返します（他の更新が先に行われた場合は `false`）。
+    async fn save_if_unchanged(&self, session: &Session, refresh_token_hash: &str) -> AppResult<bool>;
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> AppResult<()>;
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> AppResult<()>;
+}
@@ -3,6 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use super::role::Role;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -10,8 +11,22 @@ pub struct User {
     pub email: String,
     pub name: String,
     pub password_hash: String,
+    pub role: Role,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// メールアドレスの所有確認が完了した日時
+    pub email_verified_at: Option<DateTime<Utc>>,
+    /// アカウント削除を受け付けた日時（削除処理の完了までログイン不可）
+    pub deletion_requested_at: Option<DateTime<Utc>>,
+    /// TOTPの共有シークレット（登録中または有効時）
+    pub mfa_secret: Option<String>,
+    pub mfa_enabled_at: Option<DateTime<Utc>>,
+    /// リカバリーコードのハッシュ（未使用分のみ）
+    pub mfa_recovery_codes: Vec<String>,
+    /// 最後に受け付けたTOTPのタイムステップ（再利用防止）
+    pub mfa_last_used_step: Option<i64>,
+    /// 保存済みのバージョン（更新のたびに1ずつ増える。導入前のユーザーは 0）
+    pub version: i64,
 }
 
 impl User {
@@ -22,8 +37,16 @@ impl User {
             email,
             name,
             password_hash,
+            role: Role::default(),
             created_at: now,
             updated_at: now,
+            email_verified_at: None,
+            deletion_requested_at: None,
+            mfa_secret: None,
+            mfa_enabled_at: None,
+            mfa_recovery_codes: Vec::new(),
+            mfa_last_used_step: None,
+            version: 1,
         }
     }
 
@@ -34,10 +57,80 @@ impl User {
         }
     }
 
+    pub fn change_role(&mut self, role: Role) {
+        self.role = role;
+        self.updated_at = Utc::now();
+    }
+
     pub fn update_password(&mut self, password_hash: String) {
         self.password_hash = password_hash;
         self.updated_at = Utc::now();
     }
+
+    pub fn email_verified(&self) -> bool {
+        self.email_verified_at.is_some()
+    }
+
+    pub fn verify_email(&mut self) {
+        if self.email_verified_at.is_none() {
+            let now = Utc::now();
+            self.email_verified_at = Some(now);
+            self.updated_at = now;
+        }
+    }
+
+    pub fn is_pending_deletion(&self) -> bool {
+        self.deletion_requested_at.is_some()
+    }
+
+    pub fn request_deletion(&mut self) {
+        if self.deletion_requested_at.is_none() {
+            let now = Utc::now();
+            self.deletion_requested_at = Some(now);
+            self.updated_at = now;
+        }
+    }
+
+    pub fn mfa_enabled(&self) -> bool {
+        self.mfa_enabled_at.is_some()
+    }
+
+    /// TOTPの登録を開始（確認されるまでは無効）
+    pub fn begin_mfa_enrollment(&mut self, secret: String) {
+        self.mfa_secret = Some(secret);
+        self.mfa_enabled_at = None;
+        self.mfa_recovery_codes.clear();
+        self.mfa_last_used_step = None;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn enable_mfa(&mut self, recovery_codes: Vec<String>, verified_step: i64) {
+        let now = Utc::now();
+        self.mfa_enabled_at = Some(now);
+        self.mfa_recovery_codes = recovery_codes;
+        self.mfa_last_used_step = Some(verified_step);
+        self.updated_at = now;
+    }
+
+    pub fn disable_mfa(&mut self) {
+        self.mfa_secret = None;
+        self.mfa_enabled_at = None;
+        self.mfa_recovery_codes.clear();
+        self.mfa_last_used_step = None;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn record_totp_step(&mut self, step: i64) {
+        self.mfa_last_used_step = Some(step);
+    }
+
+    /// 使用したリカバリーコードを削除（一度きり）
+    pub fn consume_recovery_code(&mut self, index: usize) {
+        if index < self.mfa_recovery_codes.len() {
+            self.mfa_recovery_codes.remove(index);
+            self.updated_at = Utc::now();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -55,6 +148,8 @@ mod tests {
         assert_eq!(user.email, email);
         assert_eq!(user.name, name);
         assert_eq!(user.password_hash, password_hash);
+        assert_eq!(user.role, Role::Member);
+        assert_eq!(user.version, 1);
         assert!(user.id != Uuid::nil());
         assert!(user.created_at <= Utc::now());
         assert_eq!(user.created_at, user.updated_at);
@@ -97,4 +192,48 @@ mod tests {
         assert_eq!(user.created_at, original_created_at);
         assert!(user.updated_at > original_updated_at);
     }
+
+    #[test]
+    fn test_verify_email() {
+        let mut user = User::new(
+            "test@example.com".to_string(),
+            "Test User".to_string(),
+            "password_hash".to_string(),
+        );
+        assert!(!user.email_verified());
+
+        user.verify_email();
+        let verified_at = user.email_verified_at;
+        assert!(user.email_verified());
+
+        // 再度確認しても最初の日時を保持する
+        user.verify_email();
+        assert_eq!(user.email_verified_at, verified_at);
+    }
+
+    #[test]
+    fn test_mfa_lifecycle() {
+        let mut user = User::new(
+            "test@example.com".to_string(),
+            "Test User".to_string(),
+            "password_hash".to_string(),
+        );
+        assert!(!user.mfa_enabled());
+
+        user.begin_mfa_enrollment("SECRET".to_string());
+        assert!(!user.mfa_enabled());
+        assert_eq!(user.mfa_secret.as_deref(), Some("SECRET"));
+
+        user.enable_mfa(vec!["hash1".to_string(), "hash2".to_string()], 42);
+        assert!(user.mfa_enabled());
+        assert_eq!(user.mfa_last_used_step, Some(42));
+
+        user.consume_recovery_code(0);
+        assert_eq!(user.mfa_recovery_codes, vec!["hash2"]);
+
+        user.disable_mfa();
+        assert!(!user.mfa_enabled());
+        assert!(user.mfa_secret.is_none());
+        assert!(user.mfa_recovery_codes.is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod entity;
+pub mod repository;
+pub mod role;
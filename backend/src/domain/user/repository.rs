@@ -9,7 +9,13 @@ use super::entity::User;
 pub trait UserRepository: Send + Sync {
     async fn find_by_id(&self, id: Uuid) -> AppResult<Option<User>>;
     async fn find_by_email(&self, email: &str) -> AppResult<Option<User>>;
-    async fn save(&self, user: &User) -> AppResult<()>;
+    /// 新規ユーザーの保存（メールアドレスが登録済みなら Conflict）
+    async fn create(&self, user: &User) -> AppResult<()>;
+    /// 既存ユーザーの更新
+    ///
+    /// 読み込んだ後に他の更新が行われていれば Conflict を返します。
+    /// 保存に成功すると `user.version` を保存したバージョンに進めます。
+    async fn save(&self, user: &mut User) -> AppResult<()>;
     async fn delete(&self, id: Uuid) -> AppResult<()>;
     async fn exists(&self, id: Uuid) -> AppResult<bool>;
     async fn exists_by_email(&self, email: &str) -> AppResult<bool>;
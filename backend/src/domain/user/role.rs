@@ -0,0 +1,117 @@
+// src/domain/user/role.rs
+
+use std::{fmt, str::FromStr};
+use serde::{Deserialize, Serialize};
+
+/// ユーザーのロール
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// 全メモの閲覧・モデレーションとユーザー管理が可能
+    Admin,
+    /// 自分のメモの閲覧・作成・編集が可能
+    #[default]
+    Member,
+    /// 自分のメモの閲覧のみ可能
+    ReadOnly,
+}
+
+/// ロールに付与される操作権限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Permission {
+    /// 自分のメモの閲覧・検索
+    MemosRead,
+    /// 自分のメモの作成・編集・削除
+    MemosWrite,
+    /// 他のユーザーのメモの閲覧
+    MemosReadAny,
+    /// 他のユーザーのメモの編集・削除
+    MemosModerate,
+    /// ロールの変更やMFAのリセットなどのユーザー管理
+    UsersManage,
+}
+
+impl Role {
+    pub fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Role::Admin => &[
+                Permission::MemosRead,
+                Permission::MemosWrite,
+                Permission::MemosReadAny,
+                Permission::MemosModerate,
+                Permission::UsersManage,
+            ],
+            Role::Member => &[Permission::MemosRead, Permission::MemosWrite],
+            Role::ReadOnly => &[Permission::MemosRead],
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Admin => "admin",
+            Role::Member => "member",
+            Role::ReadOnly => "read_only",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(Role::Admin),
+            "member" => Ok(Role::Member),
+            "read_only" => Ok(Role::ReadOnly),
+            other => Err(format!("Unknown role: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Permission::MemosRead => "memos:read",
+            Permission::MemosWrite => "memos:write",
+            Permission::MemosReadAny => "memos:read_any",
+            Permission::MemosModerate => "memos:moderate",
+            Permission::UsersManage => "users:manage",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(Role::Admin.has_permission(Permission::MemosModerate));
+        assert!(Role::Admin.has_permission(Permission::UsersManage));
+
+        assert!(Role::Member.has_permission(Permission::MemosWrite));
+        assert!(!Role::Member.has_permission(Permission::MemosReadAny));
+
+        assert!(Role::ReadOnly.has_permission(Permission::MemosRead));
+        assert!(!Role::ReadOnly.has_permission(Permission::MemosWrite));
+    }
+
+    #[test]
+    fn test_role_round_trip() {
+        for role in [Role::Admin, Role::Member, Role::ReadOnly] {
+            assert_eq!(role.as_str().parse::<Role>(), Ok(role));
+        }
+        assert!("owner".parse::<Role>().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use actix_web::{HttpResponse, ResponseError};
+use actix_web::{http::header, HttpResponse, ResponseError};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -21,8 +21,18 @@ pub enum AppError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    /// 自動で解決できなかった編集の競合（解決に必要な情報を本文に含める）
+    #[error("Conflict: {message}")]
+    MergeConflict { message: String, details: serde_json::Value },
+
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Too Many Requests: {message}")]
+    TooManyRequests { message: String, retry_after_secs: u64 },
 }
 
 impl ResponseError for AppError {
@@ -44,10 +54,25 @@ impl ResponseError for AppError {
                 error: "Unauthorized".into(),
                 message: msg.clone(),
             }),
+            AppError::Forbidden(msg) => HttpResponse::Forbidden().json(ErrorResponse {
+                error: "Forbidden".into(),
+                message: msg.clone(),
+            }),
             AppError::Conflict(msg) => HttpResponse::Conflict().json(ErrorResponse {
                 error: "Conflict".into(),
                 message: msg.clone(),
             }),
+            AppError::MergeConflict { message, details } => HttpResponse::Conflict().json(serde_json::json!({
+                "error": "Conflict",
+                "message": message,
+                "merge": details,
+            })),
+            AppError::TooManyRequests { message, retry_after_secs } => HttpResponse::TooManyRequests()
+                .insert_header((header::RETRY_AFTER, retry_after_secs.to_string()))
+                .json(ErrorResponse {
+                    error: "Too Many Requests".into(),
+                    message: message.clone(),
+                }),
             _ => HttpResponse::InternalServerError().json(ErrorResponse {
                 error: "Internal Server Error".into(),
                 message: "An unexpected error occurred".into(),
@@ -56,6 +81,12 @@ impl ResponseError for AppError {
     }
 }
 
+impl From<validator::ValidationErrors> for AppError {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        AppError::ValidationError(errors.to_string())
+    }
+}
+
 #[derive(serde::Serialize)]
 struct ErrorResponse {
     error: String,
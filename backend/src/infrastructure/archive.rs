@@ -0,0 +1,50 @@
+// src/infrastructure/archive.rs
+
+use std::io::{Cursor, Write};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+use crate::error::{AppError, AppResult};
+
+/// ファイル名と内容の組からZIPアーカイブを作成
+pub fn build_zip(files: &[(String, Vec<u8>)]) -> AppResult<Vec<u8>> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, content) in files {
+        writer
+            .start_file(name.as_str(), options)
+            .and_then(|_| writer.write_all(content).map_err(Into::into))
+            .map_err(|e| AppError::InternalServerError(format!("Failed to write archive: {}", e)))?;
+    }
+
+    writer
+        .finish()
+        .map(Cursor::into_inner)
+        .map_err(|e| AppError::InternalServerError(format!("Failed to finish archive: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_build_zip_round_trip() {
+        let files = vec![
+            ("profile.json".to_string(), b"{\"name\":\"test\"}".to_vec()),
+            ("memos.json".to_string(), b"[]".to_vec()),
+        ];
+
+        let bytes = build_zip(&files).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 2);
+
+        let mut content = String::new();
+        archive
+            .by_name("profile.json")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "{\"name\":\"test\"}");
+    }
+}
@@ -0,0 +1,88 @@
+// src/infrastructure/auth/action_token.rs
+
+use std::sync::Arc;
+use chrono::Duration;
+use uuid::Uuid;
+use crate::{
+    error::{AppError, AppResult},
+    infrastructure::{
+        auth::{jwt::JwtService, token::generate_token},
+        persistence::redis::RedisCache,
+    },
+};
+
+/// 用途を限定したトークンの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenPurpose {
+    PasswordReset,
+    EmailVerification,
+}
+
+impl TokenPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenPurpose::PasswordReset => "password_reset",
+            TokenPurpose::EmailVerification => "email_verification",
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        match self {
+            TokenPurpose::PasswordReset => Duration::hours(1),
+            TokenPurpose::EmailVerification => Duration::hours(24),
+        }
+    }
+}
+
+/// 署名付きの使い捨てトークン
+///
+/// 署名と有効期限はJWTで検証し、使用済みかどうかはRedisに保存した
+/// トークンIDの有無で判定します。
+pub struct ActionTokenService {
+    jwt_service: Arc<JwtService>,
+    redis: Arc<RedisCache>,
+}
+
+impl ActionTokenService {
+    pub fn new(jwt_service: Arc<JwtService>, redis: Arc<RedisCache>) -> Self {
+        Self { jwt_service, redis }
+    }
+
+    fn token_key(purpose: TokenPurpose, jti: &str) -> String {
+        format!("action_token:{}:{}", purpose.as_str(), jti)
+    }
+
+    /// トークンを発行
+    pub async fn issue(&self, purpose: TokenPurpose, user_id: Uuid) -> AppResult<String> {
+        let jti = generate_token(16);
+        let ttl = purpose.ttl();
+
+        self.redis
+            .set(
+                &Self::token_key(purpose, &jti),
+                &user_id,
+                Some(ttl.to_std().unwrap_or_default()),
+            )
+            .await?;
+
+        self.jwt_service
+            .issue_action_token(user_id, purpose.as_str(), &jti, ttl)
+    }
+
+    /// トークンを検証して消費し、対象のユーザーIDを返す
+    pub async fn consume(&self, purpose: TokenPurpose, token: &str) -> AppResult<Uuid> {
+        let claims = self.jwt_service.verify_action_token(token, purpose.as_str())?;
+
+        let user_id = self
+            .redis
+            .take::<Uuid>(&Self::token_key(purpose, &claims.jti))
+            .await?
+            .ok_or_else(|| AppError::BadRequest("Token has already been used".into()))?;
+
+        if user_id.to_string() != claims.sub {
+            return Err(AppError::BadRequest("Invalid or expired token".into()));
+        }
+
+        Ok(user_id)
+    }
+}
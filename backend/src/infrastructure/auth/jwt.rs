@@ -0,0 +1,319 @@
+// src/infrastructure/auth/jwt.rs
+
+use chrono::{Duration, Utc};
+use jsonwebtoken::{
+    decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::error::{AppError, AppResult};
+
+const DEFAULT_ACCESS_TOKEN_TTL_SECS: i64 = 15 * 60; // 15分
+const DEFAULT_REFRESH_TOKEN_TTL_SECS: i64 = 30 * 24 * 60 * 60; // 30日
+
+/// アクセストークンのクレーム
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// ユーザーID
+    pub sub: String,
+    /// セッションID
+    pub sid: Uuid,
+    pub iat: i64,
+    pub exp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+}
+
+/// メール確認やパスワード再設定など、用途を限定したトークンのクレーム
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionClaims {
+    /// ユーザーID
+    pub sub: String,
+    /// トークンの用途
+    pub purpose: String,
+    /// 使い捨てを判定するためのトークンID
+    pub jti: String,
+    pub iat: i64,
+    pub exp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+}
+
+/// 検証済みトークンの主体
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenSubject {
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+}
+
+/// JWTの署名・検証設定
+///
+/// HS256（共有シークレット）とRS256（PEM形式の鍵ペア）に対応します。
+/// RS256で秘密鍵を指定しない場合は検証専用となります。
+pub struct JwtConfig {
+    pub algorithm: Algorithm,
+    pub encoding_key: Option<EncodingKey>,
+    pub decoding_key: DecodingKey,
+    pub issuer: Option<String>,
+    pub access_token_ttl: Duration,
+    pub refresh_token_ttl: Duration,
+}
+
+impl JwtConfig {
+    /// HS256用の設定を生成
+    pub fn hs256(secret: &[u8]) -> Self {
+        Self {
+            algorithm: Algorithm::HS256,
+            encoding_key: Some(EncodingKey::from_secret(secret)),
+            decoding_key: DecodingKey::from_secret(secret),
+            issuer: None,
+            access_token_ttl: Duration::seconds(DEFAULT_ACCESS_TOKEN_TTL_SECS),
+            refresh_token_ttl: Duration::seconds(DEFAULT_REFRESH_TOKEN_TTL_SECS),
+        }
+    }
+
+    /// RS256用の設定を生成
+    ///
+    /// # Arguments
+    /// * `public_key_pem` - 検証用の公開鍵（PEM）
+    /// * `private_key_pem` - 署名用の秘密鍵（PEM、省略時は検証専用）
+    pub fn rs256(public_key_pem: &[u8], private_key_pem: Option<&[u8]>) -> AppResult<Self> {
+        let decoding_key = DecodingKey::from_rsa_pem(public_key_pem).map_err(|e| {
+            AppError::InternalServerError(format!("Invalid RSA public key: {}", e))
+        })?;
+        let encoding_key = private_key_pem
+            .map(EncodingKey::from_rsa_pem)
+            .transpose()
+            .map_err(|e| AppError::InternalServerError(format!("Invalid RSA private key: {}", e)))?;
+
+        Ok(Self {
+            algorithm: Algorithm::RS256,
+            encoding_key,
+            decoding_key,
+            issuer: None,
+            access_token_ttl: Duration::seconds(DEFAULT_ACCESS_TOKEN_TTL_SECS),
+            refresh_token_ttl: Duration::seconds(DEFAULT_REFRESH_TOKEN_TTL_SECS),
+        })
+    }
+
+    /// 環境変数から設定を読み込む
+    ///
+    /// * `JWT_ALGORITHM` - `HS256`（既定）または `RS256`
+    /// * `JWT_SECRET` - HS256のシークレット
+    /// * `JWT_PUBLIC_KEY_PATH` / `JWT_PRIVATE_KEY_PATH` - RS256の鍵ファイル
+    /// * `JWT_ISSUER` - 発行者（任意）
+    /// * `JWT_ACCESS_TOKEN_TTL_SECS` - アクセストークンの有効期間（秒）
+    /// * `JWT_REFRESH_TOKEN_TTL_SECS` - リフレッシュトークン（セッション）の有効期間（秒）
+    pub fn from_env() -> AppResult<Self> {
+        let algorithm = std::env::var("JWT_ALGORITHM").unwrap_or_else(|_| "HS256".to_string());
+
+        let mut config = match algorithm.to_uppercase().as_str() {
+            "HS256" => {
+                let secret = std::env::var("JWT_SECRET").map_err(|_| {
+                    AppError::InternalServerError("JWT_SECRET must be set for HS256".into())
+                })?;
+                Self::hs256(secret.as_bytes())
+            }
+            "RS256" => {
+                let public_key_path = std::env::var("JWT_PUBLIC_KEY_PATH").map_err(|_| {
+                    AppError::InternalServerError("JWT_PUBLIC_KEY_PATH must be set for RS256".into())
+                })?;
+                let public_key = std::fs::read(&public_key_path).map_err(|e| {
+                    AppError::InternalServerError(format!("Failed to read {}: {}", public_key_path, e))
+                })?;
+                let private_key = match std::env::var("JWT_PRIVATE_KEY_PATH") {
+                    Ok(path) => Some(std::fs::read(&path).map_err(|e| {
+                        AppError::InternalServerError(format!("Failed to read {}: {}", path, e))
+                    })?),
+                    Err(_) => None,
+                };
+                Self::rs256(&public_key, private_key.as_deref())?
+            }
+            other => {
+                return Err(AppError::InternalServerError(format!(
+                    "Unsupported JWT_ALGORITHM: {}",
+                    other
+                )))
+            }
+        };
+
+        config.issuer = std::env::var("JWT_ISSUER").ok();
+        if let Ok(ttl) = std::env::var("JWT_ACCESS_TOKEN_TTL_SECS") {
+            let secs = ttl.parse::<i64>().map_err(|e| {
+                AppError::InternalServerError(format!("Invalid JWT_ACCESS_TOKEN_TTL_SECS: {}", e))
+            })?;
+            config.access_token_ttl = Duration::seconds(secs);
+        }
+        if let Ok(ttl) = std::env::var("JWT_REFRESH_TOKEN_TTL_SECS") {
+            let secs = ttl.parse::<i64>().map_err(|e| {
+                AppError::InternalServerError(format!("Invalid JWT_REFRESH_TOKEN_TTL_SECS: {}", e))
+            })?;
+            config.refresh_token_ttl = Duration::seconds(secs);
+        }
+
+        Ok(config)
+    }
+}
+
+/// アクセストークンの発行と検証を行うサービス
+pub struct JwtService {
+    config: JwtConfig,
+}
+
+impl JwtService {
+    pub fn new(config: JwtConfig) -> Self {
+        Self { config }
+    }
+
+    /// アクセストークンの有効期間
+    pub fn access_token_ttl(&self) -> Duration {
+        self.config.access_token_ttl
+    }
+
+    /// リフレッシュトークンの有効期間
+    pub fn refresh_token_ttl(&self) -> Duration {
+        self.config.refresh_token_ttl
+    }
+
+    /// セッションに紐づくアクセストークンを発行
+    pub fn issue_access_token(&self, user_id: Uuid, session_id: Uuid) -> AppResult<String> {
+        let encoding_key = self.config.encoding_key.as_ref().ok_or_else(|| {
+            AppError::InternalServerError("JWT signing key is not configured".into())
+        })?;
+
+        let now = Utc::now();
+        let claims = Claims {
+            sub: user_id.to_string(),
+            sid: session_id,
+            iat: now.timestamp(),
+            exp: (now + self.config.access_token_ttl).timestamp(),
+            iss: self.config.issuer.clone(),
+        };
+
+        encode(&Header::new(self.config.algorithm), &claims, encoding_key)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to sign token: {}", e)))
+    }
+
+    /// 用途を限定したトークンを発行
+    pub fn issue_action_token(
+        &self,
+        user_id: Uuid,
+        purpose: &str,
+        jti: &str,
+        ttl: Duration,
+    ) -> AppResult<String> {
+        let encoding_key = self.config.encoding_key.as_ref().ok_or_else(|| {
+            AppError::InternalServerError("JWT signing key is not configured".into())
+        })?;
+
+        let now = Utc::now();
+        let claims = ActionClaims {
+            sub: user_id.to_string(),
+            purpose: purpose.to_string(),
+            jti: jti.to_string(),
+            iat: now.timestamp(),
+            exp: (now + ttl).timestamp(),
+            iss: self.config.issuer.clone(),
+        };
+
+        encode(&Header::new(self.config.algorithm), &claims, encoding_key)
+            .map_err(|e| AppError::InternalServerError(format!("Failed to sign token: {}", e)))
+    }
+
+    /// 用途を限定したトークンの署名・有効期限・用途を検証
+    pub fn verify_action_token(&self, token: &str, purpose: &str) -> AppResult<ActionClaims> {
+        let mut validation = Validation::new(self.config.algorithm);
+        if let Some(issuer) = &self.config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+
+        let claims = decode::<ActionClaims>(token, &self.config.decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|_| AppError::BadRequest("Invalid or expired token".into()))?;
+
+        if claims.purpose != purpose {
+            return Err(AppError::BadRequest("Invalid or expired token".into()));
+        }
+
+        Ok(claims)
+    }
+
+    /// トークンの署名と有効期限を検証し、クレームを返す
+    pub fn verify(&self, token: &str) -> AppResult<Claims> {
+        let mut validation = Validation::new(self.config.algorithm);
+        if let Some(issuer) = &self.config.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+
+        decode::<Claims>(token, &self.config.decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid token: {}", e)))
+    }
+
+    /// トークンを検証し、ユーザーIDとセッションIDを取り出す
+    pub fn authenticate(&self, token: &str) -> AppResult<TokenSubject> {
+        let claims = self.verify(token)?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid token subject".into()))?;
+
+        Ok(TokenSubject {
+            user_id,
+            session_id: claims.sid,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_and_authenticate() {
+        let service = JwtService::new(JwtConfig::hs256(b"test-secret"));
+        let user_id = Uuid::new_v4();
+        let session_id = Uuid::new_v4();
+
+        let token = service.issue_access_token(user_id, session_id).unwrap();
+        let subject = service.authenticate(&token).unwrap();
+
+        assert_eq!(subject.user_id, user_id);
+        assert_eq!(subject.session_id, session_id);
+    }
+
+    #[test]
+    fn test_reject_token_signed_with_other_key() {
+        let issuer = JwtService::new(JwtConfig::hs256(b"other-secret"));
+        let service = JwtService::new(JwtConfig::hs256(b"test-secret"));
+
+        let token = issuer.issue_access_token(Uuid::new_v4(), Uuid::new_v4()).unwrap();
+
+        assert!(matches!(service.authenticate(&token), Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_action_token_is_bound_to_purpose() {
+        let service = JwtService::new(JwtConfig::hs256(b"test-secret"));
+        let user_id = Uuid::new_v4();
+
+        let token = service
+            .issue_action_token(user_id, "password_reset", "jti", Duration::minutes(10))
+            .unwrap();
+
+        let claims = service.verify_action_token(&token, "password_reset").unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+        assert!(service.verify_action_token(&token, "email_verification").is_err());
+        // アクセストークンとしては使えない
+        assert!(service.authenticate(&token).is_err());
+    }
+
+    #[test]
+    fn test_reject_expired_token() {
+        let mut config = JwtConfig::hs256(b"test-secret");
+        config.access_token_ttl = Duration::seconds(-120);
+        let service = JwtService::new(config);
+
+        let token = service.issue_access_token(Uuid::new_v4(), Uuid::new_v4()).unwrap();
+
+        assert!(matches!(service.authenticate(&token), Err(AppError::Unauthorized(_))));
+    }
+}
@@ -0,0 +1,67 @@
+// src/infrastructure/auth/mfa.rs
+
+use std::{sync::Arc, time::Duration};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use crate::{
+    error::AppResult,
+    infrastructure::{auth::token::generate_token, persistence::redis::RedisCache},
+};
+
+/// 第2要素の入力を待つチャレンジの有効期間
+pub const MFA_CHALLENGE_TTL: Duration = Duration::from_secs(300); // 5分
+/// チャレンジごとの入力試行回数の上限
+const MAX_CHALLENGE_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaChallenge {
+    pub user_id: Uuid,
+    pub attempts: u32,
+}
+
+/// パスワード認証を通過し、第2要素の検証待ちであることを示すチャレンジ
+///
+/// トークンは使い捨てで、検証に成功した時点で削除されます。
+/// 試行回数の記録と削除はそれぞれ1回の操作で行うため、並行したリクエストでも
+/// 上限を超えて試行したり、1つのチャレンジから複数のセッションを作成したりはできません。
+pub struct MfaChallengeStore {
+    redis: Arc<RedisCache>,
+}
+
+impl MfaChallengeStore {
+    pub fn new(redis: Arc<RedisCache>) -> Self {
+        Self { redis }
+    }
+
+    fn challenge_key(token: &str) -> String {
+        format!("mfa_challenge:{}", token)
+    }
+
+    /// チャレンジを作成し、クライアントに返すトークンを生成
+    pub async fn create(&self, user_id: Uuid) -> AppResult<String> {
+        let token = generate_token(32);
+        let challenge = MfaChallenge { user_id, attempts: 0 };
+        self.redis
+            .set(&Self::challenge_key(&token), &challenge, Some(MFA_CHALLENGE_TTL))
+            .await?;
+        Ok(token)
+    }
+
+    /// 検証の前に試行を記録してチャレンジを取得
+    ///
+    /// 期限切れのチャレンジと、試行回数の上限を超えたチャレンジ（この時点で破棄）は `None` です。
+    pub async fn begin_attempt(&self, token: &str) -> AppResult<Option<MfaChallenge>> {
+        self.redis
+            .increment_field(&Self::challenge_key(token), "attempts", MAX_CHALLENGE_ATTEMPTS.into())
+            .await
+    }
+
+    /// 検証に成功したチャレンジを削除（他のリクエストが先に削除していれば `false`）
+    pub async fn consume(&self, token: &str) -> AppResult<bool> {
+        Ok(self
+            .redis
+            .take::<MfaChallenge>(&Self::challenge_key(token))
+            .await?
+            .is_some())
+    }
+}
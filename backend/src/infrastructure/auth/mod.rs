@@ -0,0 +1,9 @@
+//src/infrastructure/auth/mod.rs
+pub mod action_token;
+pub mod jwt;
+pub mod mfa;
+pub mod oauth;
+pub mod password;
+pub mod throttle;
+pub mod token;
+pub mod totp;
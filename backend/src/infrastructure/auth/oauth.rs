@@ -0,0 +1,573 @@
+// src/infrastructure/auth/oauth.rs
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use reqwest::{header, Client, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::error;
+use crate::{
+    error::{AppError, AppResult},
+    infrastructure::{
+        auth::token::{constant_time_eq, generate_token, hash_token},
+        persistence::redis::RedisCache,
+    },
+};
+
+/// 認可リクエストの有効期間（ブラウザに紐付けるクッキーの有効期間も同じ）
+pub const PENDING_AUTHORIZATION_TTL: Duration = Duration::from_secs(600); // 10分
+
+/// OAuth 2.0 / OpenID Connect プロバイダーの設定
+#[derive(Debug, Clone)]
+pub struct OAuthProviderConfig {
+    pub name: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub authorize_url: String,
+    pub token_url: String,
+    pub userinfo_url: String,
+    /// GitHub形式のメールアドレス一覧エンドポイント（検証済みメールの取得に使用）
+    pub emails_url: Option<String>,
+    /// IDトークンの発行者（OpenID Connect のプロバイダーのみ。IDトークンを検証する）
+    pub issuer: Option<String>,
+    /// IDトークンの署名鍵の一覧
+    pub jwks_uri: Option<String>,
+    pub scopes: Vec<String>,
+    pub redirect_uri: String,
+}
+
+/// 既知プロバイダーのエンドポイント
+struct ProviderPreset {
+    authorize_url: &'static str,
+    token_url: &'static str,
+    userinfo_url: &'static str,
+    emails_url: Option<&'static str>,
+    issuer: Option<&'static str>,
+    jwks_uri: Option<&'static str>,
+    scopes: &'static str,
+}
+
+fn preset(name: &str) -> Option<ProviderPreset> {
+    match name {
+        "github" => Some(ProviderPreset {
+            authorize_url: "https://github.com/login/oauth/authorize",
+            token_url: "https://github.com/login/oauth/access_token",
+            userinfo_url: "https://api.github.com/user",
+            emails_url: Some("https://api.github.com/user/emails"),
+            issuer: None,
+            jwks_uri: None,
+            scopes: "read:user user:email",
+        }),
+        "google" => Some(ProviderPreset {
+            authorize_url: "https://accounts.google.com/o/oauth2/v2/auth",
+            token_url: "https://oauth2.googleapis.com/token",
+            userinfo_url: "https://openidconnect.googleapis.com/v1/userinfo",
+            emails_url: None,
+            issuer: Some("https://accounts.google.com"),
+            jwks_uri: Some("https://www.googleapis.com/oauth2/v3/certs"),
+            scopes: "openid email profile",
+        }),
+        _ => None,
+    }
+}
+
+/// OpenID Connect Discovery の応答（必要な項目のみ）
+#[derive(Debug, Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: String,
+    jwks_uri: String,
+}
+
+impl OAuthProviderConfig {
+    /// 環境変数からプロバイダー設定を読み込む
+    ///
+    /// `OAUTH_{NAME}_CLIENT_ID` / `_CLIENT_SECRET` / `_REDIRECT_URI` は必須（シークレットは任意）。
+    /// エンドポイントは `_ISSUER` によるDiscovery、既知プロバイダーのプリセット、
+    /// `_AUTHORIZE_URL` / `_TOKEN_URL` / `_USERINFO_URL` / `_EMAILS_URL` / `_JWKS_URI` の
+    /// 個別指定の順に解決され、個別指定が優先されます。
+    async fn from_env(name: &str, http: &Client) -> AppResult<Self> {
+        let prefix = format!("OAUTH_{}", name.to_uppercase());
+        let var = |key: &str| std::env::var(format!("{}_{}", prefix, key)).ok();
+        let required = |key: &str| {
+            var(key).ok_or_else(|| {
+                AppError::InternalServerError(format!("{}_{} must be set", prefix, key))
+            })
+        };
+
+        let mut authorize_url = None;
+        let mut token_url = None;
+        let mut userinfo_url = None;
+        let mut emails_url = None;
+        let mut issuer = None;
+        let mut jwks_uri = None;
+        let mut scopes = None;
+
+        if let Some(preset) = preset(name) {
+            authorize_url = Some(preset.authorize_url.to_string());
+            token_url = Some(preset.token_url.to_string());
+            userinfo_url = Some(preset.userinfo_url.to_string());
+            emails_url = preset.emails_url.map(String::from);
+            issuer = preset.issuer.map(String::from);
+            jwks_uri = preset.jwks_uri.map(String::from);
+            scopes = Some(preset.scopes.to_string());
+        }
+
+        if let Some(issuer_url) = var("ISSUER") {
+            let discovery = Self::discover(http, &issuer_url).await?;
+            authorize_url = Some(discovery.authorization_endpoint);
+            token_url = Some(discovery.token_endpoint);
+            userinfo_url = Some(discovery.userinfo_endpoint);
+            issuer = Some(discovery.issuer);
+            jwks_uri = Some(discovery.jwks_uri);
+            scopes = scopes.or_else(|| Some("openid email profile".to_string()));
+        }
+
+        let missing = |key: &str| AppError::InternalServerError(format!("{}_{} must be set", prefix, key));
+
+        let jwks_uri = var("JWKS_URI").or(jwks_uri);
+        if issuer.is_some() && jwks_uri.is_none() {
+            return Err(missing("JWKS_URI"));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            client_id: required("CLIENT_ID")?,
+            client_secret: var("CLIENT_SECRET"),
+            authorize_url: var("AUTHORIZE_URL").or(authorize_url).ok_or_else(|| missing("AUTHORIZE_URL"))?,
+            token_url: var("TOKEN_URL").or(token_url).ok_or_else(|| missing("TOKEN_URL"))?,
+            userinfo_url: var("USERINFO_URL").or(userinfo_url).ok_or_else(|| missing("USERINFO_URL"))?,
+            emails_url: var("EMAILS_URL").or(emails_url),
+            issuer,
+            jwks_uri,
+            scopes: var("SCOPES")
+                .or(scopes)
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            redirect_uri: required("REDIRECT_URI")?,
+        })
+    }
+
+    async fn discover(http: &Client, issuer: &str) -> AppResult<DiscoveryDocument> {
+        let url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+        http.get(&url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::InternalServerError(format!("OIDC discovery failed for {}: {}", issuer, e)))?
+            .json::<DiscoveryDocument>()
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Invalid OIDC discovery document: {}", e)))
+    }
+}
+
+/// 有効なプロバイダーの一覧
+pub struct OAuthConfig {
+    pub providers: Vec<OAuthProviderConfig>,
+}
+
+impl OAuthConfig {
+    /// `OAUTH_PROVIDERS`（カンマ区切り、例: `github,google`）に列挙されたプロバイダーを読み込む
+    ///
+    /// 設定の不足や Discovery の失敗で読み込めないプロバイダーは、
+    /// エラーを記録して無効にします（他のログイン手段は使えるままにする）。
+    pub async fn from_env() -> Self {
+        let http = Client::new();
+        let mut providers = Vec::new();
+
+        let names = std::env::var("OAUTH_PROVIDERS").unwrap_or_default();
+        for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            match OAuthProviderConfig::from_env(&name.to_lowercase(), &http).await {
+                Ok(provider) => providers.push(provider),
+                Err(e) => error!("Disabling OAuth provider '{}': {}", name, e),
+            }
+        }
+
+        Self { providers }
+    }
+}
+
+/// プロバイダーから取得したユーザー情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthUserInfo {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+/// 認可リクエストの開始時に発行する値
+pub struct AuthorizationRequest {
+    pub url: String,
+    /// 認可を開始したブラウザに保存させる値（コールバックで照合する）
+    pub browser_binding: String,
+}
+
+/// 認可コード交換まで保持する情報
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingAuthorization {
+    provider: String,
+    code_verifier: String,
+    nonce: String,
+    /// ブラウザに保存させた値のハッシュ
+    browser_binding_hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenEndpointResponse {
+    access_token: String,
+    id_token: Option<String>,
+}
+
+/// IDトークンのクレーム（必要な項目のみ。発行者・対象・有効期限は署名と併せて検証）
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProviderEmail {
+    email: String,
+    #[serde(default)]
+    primary: bool,
+    #[serde(default)]
+    verified: bool,
+}
+
+/// 認可コード + PKCE フローのクライアント
+pub struct OAuthClient {
+    http: Client,
+    redis: Arc<RedisCache>,
+    providers: HashMap<String, OAuthProviderConfig>,
+}
+
+impl OAuthClient {
+    pub fn new(config: OAuthConfig, redis: Arc<RedisCache>) -> Self {
+        Self {
+            http: Client::new(),
+            redis,
+            providers: config
+                .providers
+                .into_iter()
+                .map(|provider| (provider.name.clone(), provider))
+                .collect(),
+        }
+    }
+
+    fn state_key(state: &str) -> String {
+        format!("oauth_state:{}", state)
+    }
+
+    fn provider(&self, name: &str) -> AppResult<&OAuthProviderConfig> {
+        self.providers
+            .get(name)
+            .ok_or_else(|| AppError::NotFound(format!("OAuth provider '{}' is not configured", name)))
+    }
+
+    /// 有効なプロバイダー名
+    pub fn provider_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.providers.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// 認可URLを生成し、stateとcode_verifierを保存
+    ///
+    /// state は認可を開始したブラウザに保存させる値と組で保存し、
+    /// 別のブラウザで開始した認可のコールバック（ログインCSRF）を拒否します。
+    pub async fn authorization_url(&self, provider_name: &str) -> AppResult<AuthorizationRequest> {
+        let provider = self.provider(provider_name)?;
+        let state = generate_token(16);
+        let code_verifier = generate_token(32);
+        let nonce = generate_token(16);
+        let browser_binding = generate_token(32);
+
+        self.redis
+            .set(
+                &Self::state_key(&state),
+                &PendingAuthorization {
+                    provider: provider.name.clone(),
+                    code_verifier: code_verifier.clone(),
+                    nonce: nonce.clone(),
+                    browser_binding_hash: hash_token(&browser_binding),
+                },
+                Some(PENDING_AUTHORIZATION_TTL),
+            )
+            .await?;
+
+        let mut url = Url::parse(&provider.authorize_url).map_err(|e| {
+            AppError::InternalServerError(format!("Invalid authorize URL for {}: {}", provider.name, e))
+        })?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &provider.client_id)
+            .append_pair("redirect_uri", &provider.redirect_uri)
+            .append_pair("scope", &provider.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &pkce_challenge(&code_verifier))
+            .append_pair("code_challenge_method", "S256");
+        if provider.issuer.is_some() {
+            url.query_pairs_mut().append_pair("nonce", &nonce);
+        }
+
+        Ok(AuthorizationRequest {
+            url: url.into(),
+            browser_binding,
+        })
+    }
+
+    /// 認可コードをアクセストークンに交換し、ユーザー情報を取得
+    ///
+    /// `browser_binding` は認可の開始時にブラウザへ保存させた値です。
+    /// OpenID Connect のプロバイダーでは IDトークンの署名・発行者・対象・nonce を検証し、
+    /// ユーザー情報の `sub` と一致することを確認します。
+    pub async fn exchange_code(
+        &self,
+        provider_name: &str,
+        code: &str,
+        state: &str,
+        browser_binding: Option<&str>,
+    ) -> AppResult<OAuthUserInfo> {
+        let provider = self.provider(provider_name)?;
+
+        // stateは一度しか使用できない
+        let pending = self
+            .redis
+            .take::<PendingAuthorization>(&Self::state_key(state))
+            .await?
+            .filter(|pending| pending.provider == provider.name)
+            .ok_or_else(|| AppError::BadRequest("Invalid or expired OAuth state".into()))?;
+
+        let bound = browser_binding.is_some_and(|binding| {
+            constant_time_eq(&hash_token(binding), &pending.browser_binding_hash)
+        });
+        if !bound {
+            return Err(AppError::BadRequest(
+                "OAuth state was not issued to this browser".into(),
+            ));
+        }
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", provider.redirect_uri.as_str()),
+            ("client_id", provider.client_id.as_str()),
+            ("code_verifier", pending.code_verifier.as_str()),
+        ];
+        if let Some(secret) = &provider.client_secret {
+            form.push(("client_secret", secret.as_str()));
+        }
+
+        let token = self
+            .http
+            .post(&provider.token_url)
+            .header(header::ACCEPT, "application/json")
+            .form(&form)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Unauthorized(format!("OAuth code exchange failed: {}", e)))?
+            .json::<TokenEndpointResponse>()
+            .await
+            .map_err(|e| AppError::Unauthorized(format!("Invalid OAuth token response: {}", e)))?;
+
+        let id_token_subject = match (&provider.issuer, &token.id_token) {
+            (None, _) => None,
+            (Some(_), None) => {
+                return Err(AppError::Unauthorized("OAuth token response has no ID token".into()));
+            }
+            (Some(_), Some(id_token)) => {
+                Some(self.verify_id_token(provider, id_token, &pending.nonce).await?.sub)
+            }
+        };
+
+        let userinfo = self.get_json::<Value>(&provider.userinfo_url, &token.access_token).await?;
+        let mut info = parse_userinfo(&provider.name, &userinfo)
+            .ok_or_else(|| AppError::Unauthorized("OAuth userinfo has no subject".into()))?;
+        if id_token_subject.is_some_and(|subject| subject != info.subject) {
+            return Err(AppError::Unauthorized("OAuth userinfo does not match the ID token".into()));
+        }
+
+        if let Some(emails_url) = &provider.emails_url {
+            let emails = self
+                .get_json::<Vec<ProviderEmail>>(emails_url, &token.access_token)
+                .await?;
+            if let Some(email) = select_verified_email(emails) {
+                info.email = Some(email);
+                info.email_verified = true;
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// IDトークンをプロバイダーの公開鍵で検証
+    async fn verify_id_token(
+        &self,
+        provider: &OAuthProviderConfig,
+        id_token: &str,
+        nonce: &str,
+    ) -> AppResult<IdTokenClaims> {
+        let (Some(issuer), Some(jwks_uri)) = (&provider.issuer, &provider.jwks_uri) else {
+            return Err(AppError::InternalServerError(format!(
+                "OAuth provider '{}' has no ID token issuer",
+                provider.name
+            )));
+        };
+        let invalid = |e: String| AppError::Unauthorized(format!("Invalid ID token: {}", e));
+
+        let header = decode_header(id_token).map_err(|e| invalid(e.to_string()))?;
+        // 公開鍵で署名されたトークンのみ受け付ける
+        if matches!(header.alg, Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512) {
+            return Err(invalid(format!("unsupported algorithm {:?}", header.alg)));
+        }
+
+        let jwks = self
+            .http
+            .get(jwks_uri)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Unauthorized(format!("Failed to fetch OAuth signing keys: {}", e)))?
+            .json::<JwkSet>()
+            .await
+            .map_err(|e| AppError::Unauthorized(format!("Invalid OAuth signing keys: {}", e)))?;
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            None => jwks.keys.first(),
+        }
+        .ok_or_else(|| invalid("no matching signing key".into()))?;
+        let key = DecodingKey::from_jwk(jwk).map_err(|e| invalid(e.to_string()))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[&provider.client_id]);
+        validation.set_issuer(&[issuer]);
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|e| invalid(e.to_string()))?
+            .claims;
+
+        if !claims.nonce.as_deref().is_some_and(|claimed| constant_time_eq(claimed, nonce)) {
+            return Err(invalid("nonce mismatch".into()));
+        }
+
+        Ok(claims)
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str, access_token: &str) -> AppResult<T> {
+        self.http
+            .get(url)
+            .bearer_auth(access_token)
+            .header(header::ACCEPT, "application/json")
+            .header(header::USER_AGENT, "memo_app_backend")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError::Unauthorized(format!("OAuth userinfo request failed: {}", e)))?
+            .json::<T>()
+            .await
+            .map_err(|e| AppError::Unauthorized(format!("Invalid OAuth userinfo response: {}", e)))
+    }
+}
+
+/// PKCE の code_challenge（S256）
+pub fn pkce_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// OIDC（`sub`）とGitHub形式（`id`/`login`）のユーザー情報を正規化
+fn parse_userinfo(provider: &str, value: &Value) -> Option<OAuthUserInfo> {
+    let subject = match (&value["sub"], &value["id"]) {
+        (Value::String(sub), _) => sub.clone(),
+        (_, Value::Number(id)) => id.to_string(),
+        (_, Value::String(id)) => id.clone(),
+        _ => return None,
+    };
+
+    let email_verified = match &value["email_verified"] {
+        Value::Bool(verified) => *verified,
+        Value::String(verified) => verified == "true",
+        _ => false,
+    };
+
+    Some(OAuthUserInfo {
+        provider: provider.to_string(),
+        subject,
+        email: value["email"].as_str().map(|email| email.trim().to_lowercase()),
+        email_verified,
+        name: value["name"]
+            .as_str()
+            .or_else(|| value["login"].as_str())
+            .map(String::from),
+    })
+}
+
+/// 検証済みのメールアドレスを優先度順（プライマリ優先）に選択
+fn select_verified_email(emails: Vec<ProviderEmail>) -> Option<String> {
+    emails
+        .into_iter()
+        .filter(|email| email.verified)
+        .max_by_key(|email| email.primary)
+        .map(|email| email.email.trim().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_pkce_challenge_matches_rfc7636_example() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mtG1l4nYNqBGdVwUhXmsjvsKkCiM7"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_parse_oidc_userinfo() {
+        let info = parse_userinfo(
+            "google",
+            &json!({
+                "sub": "1234567890",
+                "email": "Alice@Example.com",
+                "email_verified": true,
+                "name": "Alice"
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(info.subject, "1234567890");
+        assert_eq!(info.email.as_deref(), Some("alice@example.com"));
+        assert!(info.email_verified);
+        assert_eq!(info.name.as_deref(), Some("Alice"));
+    }
+
+    #[test]
+    fn test_parse_github_userinfo() {
+        let info = parse_userinfo(
+            "github",
+            &json!({ "id": 42, "login": "octocat", "email": null }),
+        )
+        .unwrap();
+
+        assert_eq!(info.subject, "42");
+        assert_eq!(info.email, None);
+        assert!(!info.email_verified);
+        assert_eq!(info.name.as_deref(), Some("octocat"));
+
+        let email = select_verified_email(vec![
+            ProviderEmail { email: "unverified@example.com".into(), primary: true, verified: false },
+            ProviderEmail { email: "secondary@example.com".into(), primary: false, verified: true },
+            ProviderEmail { email: "primary@example.com".into(), primary: true, verified: true },
+        ]);
+        assert_eq!(email.as_deref(), Some("primary@example.com"));
+    }
+}
@@ -0,0 +1,80 @@
+// src/infrastructure/auth/password.rs
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use crate::error::{AppError, AppResult};
+
+/// 存在しないアカウントへのログインで照合するハッシュ（既定のパラメータ）
+///
+/// 登録済みのアカウントと同じ時間をかけて照合し、応答時間から登録の有無を判別できないようにします。
+pub const DUMMY_PASSWORD_HASH: &str =
+    "$argon2id$v=19$m=19456,t=2,p=1$c29tZXNhbHR2YWx1ZWZvcmR1bW15$8U4PTe77V+9nwYH/q179FA0AGgzYnivviv9+oY4JUbk";
+
+/// パスワードをArgon2idでハッシュ化（PHC文字列形式）
+pub async fn hash_password(password: &str) -> AppResult<String> {
+    let password = password.to_owned();
+    run_blocking(move || hash_password_blocking(&password)).await
+}
+
+/// パスワードとハッシュを照合
+pub async fn verify_password(password: &str, password_hash: &str) -> AppResult<bool> {
+    let password = password.to_owned();
+    let password_hash = password_hash.to_owned();
+    run_blocking(move || verify_password_blocking(&password, &password_hash)).await
+}
+
+/// `hash_password` の同期版（`run_blocking` の中で使用）
+pub fn hash_password_blocking(password: &str) -> AppResult<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AppError::InternalServerError(format!("Failed to hash password: {}", e)))
+}
+
+/// `verify_password` の同期版（`run_blocking` の中で使用）
+pub fn verify_password_blocking(password: &str, password_hash: &str) -> AppResult<bool> {
+    let parsed = PasswordHash::new(password_hash)
+        .map_err(|e| AppError::InternalServerError(format!("Invalid password hash: {}", e)))?;
+
+    Ok(Argon2::default()
+        .verify_password(password.as_bytes(), &parsed)
+        .is_ok())
+}
+
+/// ハッシュ計算を非同期ランタイムのワーカーを塞がない専用のスレッドで実行
+pub async fn run_blocking<T, F>(task: F) -> AppResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> AppResult<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Password hashing task failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_hash_and_verify_password() {
+        let hash = hash_password("correct horse battery staple").await.unwrap();
+
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("correct horse battery staple", &hash).await.unwrap());
+        assert!(!verify_password("wrong password", &hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_dummy_hash_uses_default_parameters() {
+        let hash = hash_password("password").await.unwrap();
+        let params = |hash: &str| hash.rsplitn(3, '$').nth(2).unwrap().to_string();
+
+        // 実際のハッシュと同じコストで照合され、どのパスワードとも一致しない
+        assert_eq!(params(DUMMY_PASSWORD_HASH), params(&hash));
+        assert!(!verify_password("password", DUMMY_PASSWORD_HASH).await.unwrap());
+    }
+}
@@ -0,0 +1,214 @@
+// src/infrastructure/auth/throttle.rs
+
+use std::{sync::Arc, time::Duration};
+use crate::{
+    error::{AppError, AppResult},
+    infrastructure::persistence::redis::RedisCache,
+};
+
+/// 失敗回数に応じた待機時間の方針
+#[derive(Debug, Clone, Copy)]
+pub struct ThrottlePolicy {
+    /// 待機なしで許容する失敗回数
+    pub free_attempts: i64,
+    /// この回数に達するとロックアウト
+    pub max_attempts: i64,
+    pub base_delay_secs: u64,
+    pub max_delay_secs: u64,
+    pub lockout_secs: u64,
+    /// 失敗回数を数える期間
+    pub window_secs: u64,
+}
+
+impl ThrottlePolicy {
+    /// アカウント単位の方針
+    pub const ACCOUNT: Self = Self {
+        free_attempts: 3,
+        max_attempts: 10,
+        base_delay_secs: 1,
+        max_delay_secs: 60,
+        lockout_secs: 15 * 60,
+        window_secs: 15 * 60,
+    };
+
+    /// 接続元IP単位の方針（NAT配下の複数ユーザーを考慮して緩め）
+    pub const IP: Self = Self {
+        free_attempts: 10,
+        max_attempts: 50,
+        base_delay_secs: 1,
+        max_delay_secs: 60,
+        lockout_secs: 60 * 60,
+        window_secs: 60 * 60,
+    };
+
+    /// 失敗回数に対する次の試行までの待機時間（指数バックオフ）
+    pub fn delay_for(&self, failures: i64) -> Option<Duration> {
+        if failures >= self.max_attempts {
+            return Some(Duration::from_secs(self.lockout_secs));
+        }
+        if failures <= self.free_attempts {
+            return None;
+        }
+
+        let exponent = (failures - self.free_attempts - 1).min(32) as u32;
+        let delay = self
+            .base_delay_secs
+            .saturating_mul(2u64.saturating_pow(exponent))
+            .min(self.max_delay_secs);
+        Some(Duration::from_secs(delay))
+    }
+
+    pub fn is_lockout(&self, failures: i64) -> bool {
+        failures == self.max_attempts
+    }
+}
+
+/// 一定期間内のリクエスト数の上限
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    pub max_requests: i64,
+    pub window_secs: u64,
+}
+
+impl RateLimit {
+    /// パスワード再設定メールのアカウント単位の上限
+    pub const PASSWORD_RESET_ACCOUNT: Self = Self {
+        max_requests: 3,
+        window_secs: 60 * 60,
+    };
+
+    /// パスワード再設定メールの接続元IP単位の上限
+    pub const PASSWORD_RESET_IP: Self = Self {
+        max_requests: 20,
+        window_secs: 60 * 60,
+    };
+}
+
+/// スロットリングの対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleTarget<'a> {
+    Account(&'a str),
+    Ip(&'a str),
+}
+
+impl ThrottleTarget<'_> {
+    fn policy(&self) -> ThrottlePolicy {
+        match self {
+            ThrottleTarget::Account(_) => ThrottlePolicy::ACCOUNT,
+            ThrottleTarget::Ip(_) => ThrottlePolicy::IP,
+        }
+    }
+
+    fn key(&self, kind: &str) -> String {
+        match self {
+            ThrottleTarget::Account(email) => format!("{}:account:{}", kind, email.trim().to_lowercase()),
+            ThrottleTarget::Ip(ip) => format!("{}:ip:{}", kind, ip),
+        }
+    }
+}
+
+/// Redisのカウンターによるログイン試行の制限
+pub struct LoginThrottle {
+    redis: Arc<RedisCache>,
+}
+
+impl LoginThrottle {
+    pub fn new(redis: Arc<RedisCache>) -> Self {
+        Self { redis }
+    }
+
+    /// 待機中・ロック中の対象があれば 429 を返す
+    pub async fn check(&self, targets: &[ThrottleTarget<'_>]) -> AppResult<()> {
+        let mut retry_after = Duration::ZERO;
+        for target in targets {
+            if let Some(ttl) = self.redis.time_to_live(&target.key("login_lock")).await? {
+                retry_after = retry_after.max(ttl);
+            }
+        }
+
+        if retry_after.is_zero() {
+            return Ok(());
+        }
+
+        Err(AppError::TooManyRequests {
+            message: "Too many failed login attempts. Please try again later".into(),
+            retry_after_secs: retry_after.as_secs().max(1),
+        })
+    }
+
+    /// 失敗を記録し、今回ロックアウトに達した対象を返す
+    pub async fn record_failure<'a>(
+        &self,
+        targets: &[ThrottleTarget<'a>],
+    ) -> AppResult<Vec<ThrottleTarget<'a>>> {
+        let mut locked = Vec::new();
+
+        for target in targets {
+            let policy = target.policy();
+            let failures = self
+                .redis
+                .increment(&target.key("login_failures"), Duration::from_secs(policy.window_secs))
+                .await?;
+
+            if let Some(delay) = policy.delay_for(failures) {
+                self.redis
+                    .set(&target.key("login_lock"), &failures, Some(delay))
+                    .await?;
+            }
+            if policy.is_lockout(failures) {
+                locked.push(*target);
+            }
+        }
+
+        Ok(locked)
+    }
+
+    /// リクエストを数え、期間内の上限を超えていれば 429 を返す
+    pub async fn limit(&self, kind: &str, target: ThrottleTarget<'_>, limit: RateLimit) -> AppResult<()> {
+        let key = target.key(kind);
+        let window = Duration::from_secs(limit.window_secs);
+        let requests = self.redis.increment(&key, window).await?;
+        if requests <= limit.max_requests {
+            return Ok(());
+        }
+
+        let retry_after = self.redis.time_to_live(&key).await?.unwrap_or(window);
+        Err(AppError::TooManyRequests {
+            message: "Too many requests. Please try again later".into(),
+            retry_after_secs: retry_after.as_secs().max(1),
+        })
+    }
+
+    /// ログイン成功時に失敗回数をリセット
+    pub async fn record_success(&self, target: ThrottleTarget<'_>) -> AppResult<()> {
+        self.redis.delete(&target.key("login_failures")).await?;
+        self.redis.delete(&target.key("login_lock")).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = ThrottlePolicy::ACCOUNT;
+
+        assert_eq!(policy.delay_for(1), None);
+        assert_eq!(policy.delay_for(3), None);
+        assert_eq!(policy.delay_for(4), Some(Duration::from_secs(1)));
+        assert_eq!(policy.delay_for(5), Some(Duration::from_secs(2)));
+        assert_eq!(policy.delay_for(6), Some(Duration::from_secs(4)));
+        assert_eq!(policy.delay_for(9), Some(Duration::from_secs(32)));
+    }
+
+    #[test]
+    fn test_lockout_after_max_attempts() {
+        let policy = ThrottlePolicy::ACCOUNT;
+
+        assert!(!policy.is_lockout(9));
+        assert!(policy.is_lockout(10));
+        assert_eq!(policy.delay_for(10), Some(Duration::from_secs(15 * 60)));
+        assert_eq!(policy.delay_for(11), Some(Duration::from_secs(15 * 60)));
+    }
+}
@@ -0,0 +1,41 @@
+// src/infrastructure/auth/token.rs
+
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+/// 推測不能なランダムトークンを生成（16進文字列）
+pub fn generate_token(bytes: usize) -> String {
+    let mut buf = vec![0u8; bytes];
+    OsRng.fill_bytes(&mut buf);
+    hex::encode(buf)
+}
+
+/// 保存用のトークンハッシュ（SHA-256）
+///
+/// 十分なエントロピーを持つトークン専用です。パスワードには使用しないでください。
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// タイミング攻撃を避けるための定数時間比較
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_and_hash_token() {
+        let token = generate_token(32);
+        let other = generate_token(32);
+
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, other);
+        assert_eq!(hash_token(&token), hash_token(&token));
+        assert!(constant_time_eq(&hash_token(&token), &hash_token(&token)));
+        assert!(!constant_time_eq(&hash_token(&token), &hash_token(&other)));
+    }
+}
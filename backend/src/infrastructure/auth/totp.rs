@@ -0,0 +1,116 @@
+// src/infrastructure/auth/totp.rs
+
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, RngCore};
+use sha1::Sha1;
+
+/// RFC 6238 の既定値（認証アプリの互換性のため固定）
+const DIGITS: u32 = 6;
+const STEP_SECS: i64 = 30;
+/// 時刻ずれを許容するステップ数（前後）
+const ALLOWED_SKEW_STEPS: i64 = 1;
+
+/// 160ビットの共有シークレットを生成（Base32）
+pub fn generate_secret() -> String {
+    let mut buf = [0u8; 20];
+    OsRng.fill_bytes(&mut buf);
+    BASE32_NOPAD.encode(&buf)
+}
+
+/// 認証アプリ登録用の `otpauth://` URI（QRコードのペイロード）
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    let label = format!("{}:{}", issuer, account);
+    format!(
+        "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        urlencode(&label),
+        secret,
+        urlencode(issuer),
+        DIGITS,
+        STEP_SECS
+    )
+}
+
+fn urlencode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 指定ステップのワンタイムコード（HOTP, RFC 4226）
+fn hotp(key: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    binary % 10u32.pow(DIGITS)
+}
+
+/// コードを検証し、一致したタイムステップを返す
+///
+/// 同じステップのコードの再利用を防ぐため、呼び出し側は返却値を保存し
+/// `last_used_step` として次回に渡してください。
+pub fn verify(secret: &str, code: &str, unix_time: i64, last_used_step: Option<i64>) -> Option<i64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    let key = BASE32_NOPAD.decode(secret.as_bytes()).ok()?;
+
+    let current = unix_time.div_euclid(STEP_SECS);
+    (current - ALLOWED_SKEW_STEPS..=current + ALLOWED_SKEW_STEPS)
+        .filter(|step| *step >= 0 && last_used_step.is_none_or(|last| *step > last))
+        .find(|step| hotp(&key, *step as u64) == code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_hotp_matches_rfc6238_vectors() {
+        // RFC 6238 Appendix B（SHA1, 8桁）の下6桁
+        assert_eq!(hotp(RFC_SECRET, 59 / 30), 287082);
+        assert_eq!(hotp(RFC_SECRET, 1111111109 / 30), 81804);
+        assert_eq!(hotp(RFC_SECRET, 1234567890 / 30), 5924);
+    }
+
+    #[test]
+    fn test_verify_with_skew_and_replay() {
+        let secret = BASE32_NOPAD.encode(RFC_SECRET);
+
+        let step = verify(&secret, "287082", 59, None);
+        assert_eq!(step, Some(1));
+        // 1ステップ後でも許容
+        assert_eq!(verify(&secret, "287082", 89, None), Some(1));
+        // 使用済みステップは拒否
+        assert_eq!(verify(&secret, "287082", 59, step), None);
+        assert_eq!(verify(&secret, "000000", 59, None), None);
+        assert_eq!(verify(&secret, "28708", 59, None), None);
+    }
+
+    #[test]
+    fn test_otpauth_uri() {
+        let uri = otpauth_uri("Memo App", "alice@example.com", "JBSWY3DPEHPK3PXP");
+
+        assert!(uri.starts_with("otpauth://totp/Memo%20App%3Aalice%40example.com?"));
+        assert!(uri.contains("secret=JBSWY3DPEHPK3PXP"));
+        assert!(uri.contains("issuer=Memo%20App"));
+    }
+}
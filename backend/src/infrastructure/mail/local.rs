@@ -0,0 +1,64 @@
+// src/infrastructure/mail/local.rs
+
+use std::path::PathBuf;
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::info;
+use uuid::Uuid;
+use crate::error::{AppError, AppResult};
+use super::{EmailMessage, Mailer};
+
+/// メールを `.eml` ファイルとして書き出す（ローカル開発用）
+pub struct FileMailer {
+    dir: PathBuf,
+    from: String,
+}
+
+impl FileMailer {
+    pub fn new(dir: PathBuf, from: String) -> Self {
+        Self { dir, from }
+    }
+}
+
+#[async_trait]
+impl Mailer for FileMailer {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to create mail directory: {}", e))
+        })?;
+
+        let now = Utc::now();
+        let path = self
+            .dir
+            .join(format!("{}-{}.eml", now.format("%Y%m%dT%H%M%S"), Uuid::new_v4()));
+        let content = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}",
+            self.from,
+            message.to,
+            message.subject,
+            now.to_rfc2822(),
+            message.body,
+        );
+
+        tokio::fs::write(&path, content).await.map_err(|e| {
+            AppError::InternalServerError(format!("Failed to write email: {}", e))
+        })?;
+
+        info!("Email to {} written to {}", message.to, path.display());
+        Ok(())
+    }
+}
+
+/// メールをログに出力するのみ（ローカル開発用）
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        info!(
+            "Email to {}: {}\n{}",
+            message.to, message.subject, message.body
+        );
+        Ok(())
+    }
+}
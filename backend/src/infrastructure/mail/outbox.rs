@@ -0,0 +1,156 @@
+// src/infrastructure/mail/outbox.rs
+
+use std::{sync::Arc, time::Duration};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+use uuid::Uuid;
+use crate::{
+    error::AppResult,
+    infrastructure::persistence::redis::RedisCache,
+};
+use super::{EmailMessage, Mailer};
+
+/// 送信予定時刻（UNIX秒）をスコアとするキュー
+const QUEUE_KEY: &str = "mail_outbox:queue";
+/// 送信を諦めるまでの試行回数
+const MAX_ATTEMPTS: u32 = 8;
+/// 処理中のメールを他のワーカーから隠す時間（処理中に停止した場合はこの後に再送）
+const LEASE: Duration = Duration::from_secs(5 * 60);
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const BATCH_SIZE: isize = 20;
+const ENTRY_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60); // 7日
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OutboxEntry {
+    id: Uuid,
+    message: EmailMessage,
+    attempts: u32,
+    last_error: Option<String>,
+}
+
+/// 失敗時に再試行するメール送信キュー
+///
+/// HTTPリクエストではキューへの登録のみを行い、
+/// 実際の送信はバックグラウンドのワーカーが行います。
+pub struct MailOutbox {
+    redis: Arc<RedisCache>,
+    mailer: Arc<dyn Mailer>,
+}
+
+impl MailOutbox {
+    pub fn new(redis: Arc<RedisCache>, mailer: Arc<dyn Mailer>) -> Self {
+        Self { redis, mailer }
+    }
+
+    fn entry_key(id: &str) -> String {
+        format!("mail_outbox:entry:{}", id)
+    }
+
+    /// メールをキューに登録
+    pub async fn enqueue(&self, message: EmailMessage) -> AppResult<()> {
+        let entry = OutboxEntry {
+            id: Uuid::new_v4(),
+            message,
+            attempts: 0,
+            last_error: None,
+        };
+        let id = entry.id.to_string();
+
+        self.redis
+            .set(&Self::entry_key(&id), &entry, Some(ENTRY_TTL))
+            .await?;
+        self.redis
+            .add_to_sorted_set(QUEUE_KEY, &id, Utc::now().timestamp())
+            .await
+    }
+
+    /// 送信予定時刻を過ぎたメールを送信し、処理した件数を返す
+    pub async fn dispatch_due(&self) -> AppResult<usize> {
+        let now = Utc::now().timestamp();
+        let ids = self
+            .redis
+            .sorted_set_range_by_score(QUEUE_KEY, now, BATCH_SIZE)
+            .await?;
+
+        let mut processed = 0;
+        for id in ids {
+            // 他のワーカーが先に取得したものは飛ばす
+            if !self.redis.remove_from_sorted_set(QUEUE_KEY, &id).await? {
+                continue;
+            }
+            self.redis
+                .add_to_sorted_set(QUEUE_KEY, &id, now + LEASE.as_secs() as i64)
+                .await?;
+
+            self.deliver(&id).await?;
+            processed += 1;
+        }
+
+        Ok(processed)
+    }
+
+    async fn deliver(&self, id: &str) -> AppResult<()> {
+        let key = Self::entry_key(id);
+        let Some(mut entry) = self.redis.get::<OutboxEntry>(&key).await? else {
+            self.redis.remove_from_sorted_set(QUEUE_KEY, id).await?;
+            return Ok(());
+        };
+
+        match self.mailer.send(&entry.message).await {
+            Ok(()) => {
+                self.redis.remove_from_sorted_set(QUEUE_KEY, id).await?;
+                self.redis.delete(&key).await
+            }
+            Err(e) => {
+                entry.attempts += 1;
+                entry.last_error = Some(e.to_string());
+
+                if entry.attempts >= MAX_ATTEMPTS {
+                    error!(
+                        "Giving up on email {} to {} after {} attempts: {}",
+                        id, entry.message.to, entry.attempts, e
+                    );
+                    self.redis.remove_from_sorted_set(QUEUE_KEY, id).await?;
+                    return self.redis.delete(&key).await;
+                }
+
+                warn!("Failed to send email {} (attempt {}): {}", id, entry.attempts, e);
+                let next_attempt = Utc::now().timestamp() + retry_delay(entry.attempts).as_secs() as i64;
+                self.redis.set(&key, &entry, Some(ENTRY_TTL)).await?;
+                self.redis.add_to_sorted_set(QUEUE_KEY, id, next_attempt).await
+            }
+        }
+    }
+
+    /// キューを定期的に処理し続けるワーカー
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.dispatch_due().await {
+                error!("Failed to dispatch mail outbox: {}", e);
+            }
+        }
+    }
+}
+
+/// 失敗回数に対する再送までの待機時間（30秒から倍々で最大1時間）
+fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    Duration::from_secs((30u64 << exponent).min(60 * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_exponentially() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_secs(60));
+        assert_eq!(retry_delay(3), Duration::from_secs(120));
+        assert_eq!(retry_delay(7), Duration::from_secs(1920));
+        assert_eq!(retry_delay(10), Duration::from_secs(60 * 60));
+    }
+}
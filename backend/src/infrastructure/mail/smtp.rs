@@ -0,0 +1,56 @@
+// src/infrastructure/mail/smtp.rs
+
+use async_trait::async_trait;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use crate::error::{AppError, AppResult};
+use super::{EmailMessage, Mailer};
+
+/// SMTPによるメール送信
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// # Arguments
+    /// * `url` - SMTPサーバーのURL（`smtp://`、`smtps://`、`smtp://...?tls=required`）
+    /// * `from` - 差出人
+    pub fn new(url: &str, from: &str) -> AppResult<Self> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::from_url(url)
+            .map_err(|e| AppError::InternalServerError(format!("Invalid SMTP_URL: {}", e)))?
+            .build();
+        let from = from
+            .parse::<Mailbox>()
+            .map_err(|e| AppError::InternalServerError(format!("Invalid MAIL_FROM: {}", e)))?;
+
+        Ok(Self { transport, from })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: &EmailMessage) -> AppResult<()> {
+        let to = message
+            .to
+            .parse::<Mailbox>()
+            .map_err(|e| AppError::BadRequest(format!("Invalid recipient address: {}", e)))?;
+
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&message.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(message.body.clone())
+            .map_err(|e| AppError::InternalServerError(format!("Failed to build email: {}", e)))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to send email: {}", e)))?;
+
+        Ok(())
+    }
+}
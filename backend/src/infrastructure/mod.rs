@@ -1,2 +1,5 @@
 pub mod persistence;
 pub mod repositories;
+pub mod archive;
+pub mod auth;
+pub mod mail;
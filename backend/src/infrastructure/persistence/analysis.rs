@@ -0,0 +1,218 @@
+// src/infrastructure/persistence/analysis.rs
+
+use serde_json::{json, Value};
+use crate::error::{AppError, AppResult};
+
+/// インデックスのマッピングの版（マッピングや解析の定義を変えたら上げる）
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// 本文の解析方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextAnalysis {
+    /// kuromoji プラグインによる形態素解析
+    Kuromoji,
+    /// プラグインを使わない CJK の bigram 解析
+    Bigram,
+}
+
+impl TextAnalysis {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Kuromoji => "kuromoji",
+            Self::Bigram => "bigram",
+        }
+    }
+}
+
+/// 解析方法の選び方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyzerMode {
+    /// kuromoji プラグインがあれば使い、なければ bigram にする
+    Auto,
+    Fixed(TextAnalysis),
+}
+
+pub struct SearchConfig {
+    pub analyzer: AnalyzerMode,
+}
+
+impl SearchConfig {
+    /// 環境変数から設定を読み込む
+    ///
+    /// * `SEARCH_ANALYZER` - `auto`（既定）、`kuromoji`、`bigram`
+    pub fn from_env() -> AppResult<Self> {
+        let analyzer = match std::env::var("SEARCH_ANALYZER")
+            .unwrap_or_else(|_| "auto".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "auto" => AnalyzerMode::Auto,
+            "kuromoji" => AnalyzerMode::Fixed(TextAnalysis::Kuromoji),
+            "bigram" => AnalyzerMode::Fixed(TextAnalysis::Bigram),
+            other => {
+                return Err(AppError::InternalServerError(format!(
+                    "Invalid SEARCH_ANALYZER: {}",
+                    other
+                )))
+            }
+        };
+
+        Ok(Self { analyzer })
+    }
+}
+
+/// メモのインデックスの設定とマッピング
+///
+/// `title` と `content` は日本語向けの解析（`ja_text`）を主とし、
+/// 部分一致用の n-gram（`ngram` サブフィールド）を併せて持ちます。
+/// 入力途中の補完には `title.suggest`（`search_as_you_type`）を使います。
+/// 作成時の定義の版と解析方法は `_meta` に記録します。
+pub fn memo_index_definition(analysis: TextAnalysis) -> Value {
+    let tokenizer = match analysis {
+        TextAnalysis::Kuromoji => "ja_kuromoji",
+        TextAnalysis::Bigram => "standard",
+    };
+
+    let ja_text = match analysis {
+        TextAnalysis::Kuromoji => json!({
+            "type": "custom",
+            "char_filter": ["ja_normalize"],
+            "tokenizer": tokenizer,
+            "filter": [
+                "kuromoji_baseform",
+                "kuromoji_part_of_speech",
+                "cjk_width",
+                "ja_stop",
+                "kuromoji_stemmer",
+                "lowercase"
+            ]
+        }),
+        TextAnalysis::Bigram => json!({
+            "type": "custom",
+            "char_filter": ["ja_normalize"],
+            "tokenizer": tokenizer,
+            "filter": ["cjk_width", "lowercase", "cjk_bigram"]
+        }),
+    };
+
+    let mut tokenizers = json!({
+        "ja_ngram": {
+            "type": "ngram",
+            "min_gram": 2,
+            "max_gram": 3,
+            "token_chars": ["letter", "digit"]
+        }
+    });
+    if analysis == TextAnalysis::Kuromoji {
+        tokenizers["ja_kuromoji"] = json!({
+            "type": "kuromoji_tokenizer",
+            "mode": "search"
+        });
+    }
+
+    let text_field = json!({
+        "type": "text",
+        "analyzer": "ja_text",
+        "fields": {
+            "ngram": { "type": "text", "analyzer": "ja_ngram" }
+        }
+    });
+    let mut title_field = text_field.clone();
+    title_field["fields"]["keyword"] = json!({ "type": "keyword" });
+    title_field["fields"]["suggest"] = json!({
+        "type": "search_as_you_type",
+        "analyzer": "ja_suggest"
+    });
+
+    json!({
+        "settings": {
+            "number_of_shards": 1,
+            "number_of_replicas": 1,
+            "analysis": {
+                "char_filter": {
+                    // 全角スペースを区切りとして扱う（英数字・カナの幅は cjk_width でそろえる）
+                    "ja_normalize": {
+                        "type": "mapping",
+                        "mappings": ["\u{3000} => \u{0020}"]
+                    }
+                },
+                "tokenizer": tokenizers,
+                "analyzer": {
+                    "ja_text": ja_text,
+                    "ja_ngram": {
+                        "type": "custom",
+                        "tokenizer": "ja_ngram",
+                        "filter": ["cjk_width", "lowercase"]
+                    },
+                    // 補完では語形を変えずに前方一致させる
+                    "ja_suggest": {
+                        "type": "custom",
+                        "char_filter": ["ja_normalize"],
+                        "tokenizer": tokenizer,
+                        "filter": ["cjk_width", "lowercase"]
+                    }
+                }
+            }
+        },
+        "mappings": {
+            "_meta": {
+                "schema_version": SCHEMA_VERSION,
+                "analysis": analysis.name()
+            },
+            "properties": {
+                "id": { "type": "keyword" },
+                "title": title_field,
+                "content": text_field,
+                "tags": { "type": "keyword" },
+                "user_id": { "type": "keyword" },
+                "folder_id": { "type": "keyword" },
+                "folder_path": { "type": "keyword" },
+                "pinned": { "type": "boolean" },
+                "archived": { "type": "boolean" },
+                "favorite": { "type": "boolean" },
+                "created_at": { "type": "date" },
+                "updated_at": { "type": "date" },
+                "version": { "type": "integer" }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kuromoji_definition_uses_plugin_tokenizer() {
+        let definition = memo_index_definition(TextAnalysis::Kuromoji);
+        let analysis = &definition["settings"]["analysis"];
+
+        assert_eq!(analysis["analyzer"]["ja_text"]["tokenizer"], "ja_kuromoji");
+        assert_eq!(analysis["tokenizer"]["ja_kuromoji"]["type"], "kuromoji_tokenizer");
+        assert_eq!(definition["mappings"]["_meta"]["analysis"], "kuromoji");
+        assert_eq!(definition["mappings"]["_meta"]["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_bigram_definition_does_not_require_plugin() {
+        let definition = memo_index_definition(TextAnalysis::Bigram);
+        let analysis = &definition["settings"]["analysis"];
+
+        assert!(!definition.to_string().contains("kuromoji"));
+        assert!(analysis["analyzer"]["ja_text"]["filter"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("cjk_bigram")));
+    }
+
+    #[test]
+    fn test_text_fields_have_ngram_subfields() {
+        let definition = memo_index_definition(TextAnalysis::Bigram);
+        let properties = &definition["mappings"]["properties"];
+
+        assert_eq!(properties["title"]["fields"]["ngram"]["analyzer"], "ja_ngram");
+        assert_eq!(properties["title"]["fields"]["keyword"]["type"], "keyword");
+        assert_eq!(properties["content"]["fields"]["ngram"]["analyzer"], "ja_ngram");
+        assert_eq!(properties["title"]["fields"]["suggest"]["type"], "search_as_you_type");
+    }
+}
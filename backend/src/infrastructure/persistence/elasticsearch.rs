@@ -1,99 +1,440 @@
 // src/infrastructure/persistence/elasticsearch.rs
 
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 use elasticsearch::{
     Elasticsearch,
-    http::transport::Transport,
+    http::{request::JsonBody, transport::Transport, StatusCode},
+    indices::{
+        IndicesCreateParts, IndicesDeleteParts, IndicesExistsAliasParts, IndicesExistsParts,
+        IndicesGetAliasParts, IndicesGetMappingParts, IndicesGetParts, IndicesRefreshParts,
+    },
+    BulkParts,
     SearchParts,
     DeleteByQueryParts,
     IndexParts,
     params::Refresh,
 };
+use tracing::info;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use serde_json::{json, Value};
 use uuid::Uuid;
 use crate::error::{AppError, AppResult};
-use crate::domain::memo::entity::Memo;
+use super::analysis::{memo_index_definition, AnalyzerMode, SearchConfig, TextAnalysis, SCHEMA_VERSION};
+use crate::domain::memo::{
+    entity::Memo,
+    repository::{
+        DateField, MemoSearch, MemoSearchHit, MemoSearchPage, MemoSuggestions, SearchExpr, SearchFacets,
+        TextField,
+    },
+};
 
+/// 読み書きに使うエイリアス名
 const INDEX_NAME: &str = "memos";
+/// 再インデックス中に作成しているインデックスを指すエイリアス名
+const NEXT_ALIAS: &str = "memos_next";
+/// 版ごとのインデックス名の接頭辞（`memos_v1`、`memos_v2`、…）
+const INDEX_PREFIX: &str = "memos_v";
+/// 再インデックス中かどうかを問い合わせ直すまでの時間
+pub const REINDEX_STATE_TTL: Duration = Duration::from_secs(5);
+/// タグ集計で返す最大のタグ数
+const MAX_TAG_BUCKETS: usize = 1000;
+/// 検索結果の絞り込み候補で返すタグ・フォルダの最大数
+const MAX_FACET_BUCKETS: usize = 20;
+
+/// エイリアス `memos` の状態
+enum AliasState {
+    /// エイリアスもインデックスも存在しない
+    Missing,
+    /// エイリアス導入前に作成した `memos` インデックスが存在する
+    Legacy,
+    /// エイリアスが指しているインデックス
+    Alias(Vec<String>),
+}
 
 pub struct ElasticsearchClient {
     client: Elasticsearch,
+    analysis: TextAnalysis,
+    /// 再インデックス中かどうかと、それを確認した時刻
+    reindexing: Mutex<Option<(Instant, bool)>>,
 }
 
 impl ElasticsearchClient {
-    pub async fn new(uri: &str) -> AppResult<Self> {
+    pub async fn new(uri: &str, config: &SearchConfig) -> AppResult<Self> {
         let transport = Transport::single_node(uri).map_err(|e| {
             AppError::DatabaseError(format!("Failed to create Elasticsearch transport: {}", e))
         })?;
         let client = Elasticsearch::new(transport);
 
+        let analysis = match config.analyzer {
+            AnalyzerMode::Fixed(analysis) => analysis,
+            AnalyzerMode::Auto if Self::has_kuromoji(&client).await? => TextAnalysis::Kuromoji,
+            AnalyzerMode::Auto => TextAnalysis::Bigram,
+        };
+
         // インデックスの初期化
-        Self::initialize_index(&client).await?;
+        Self::initialize_index(&client, analysis).await?;
+
+        Ok(Self {
+            client,
+            analysis,
+            reindexing: Mutex::new(None),
+        })
+    }
+
+    /// エイリアス `memos` がなければ、最初の版のインデックスを作成して向ける
+    ///
+    /// 既存のインデックスの定義が古い場合は作り直さず、再インデックスのジョブに任せます。
+    async fn initialize_index(client: &Elasticsearch, analysis: TextAnalysis) -> AppResult<()> {
+        if !matches!(Self::alias_state(client).await?, AliasState::Missing) {
+            return Ok(());
+        }
+
+        let mut definition = memo_index_definition(analysis);
+        definition["aliases"] = json!({ INDEX_NAME: {} });
+
+        client
+            .indices()
+            .create(IndicesCreateParts::Index(&Self::versioned_index_name(1)))
+            .body(definition)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create index: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn versioned_index_name(version: u32) -> String {
+        format!("{}{}", INDEX_PREFIX, version)
+    }
 
-        Ok(Self { client })
+    /// kuromoji プラグインがクラスタに入っているか
+    async fn has_kuromoji(client: &Elasticsearch) -> AppResult<bool> {
+        let plugins: Value = client
+            .cat()
+            .plugins()
+            .format("json")
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to list plugins: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse plugin list: {}", e)))?;
+
+        Ok(plugins.as_array().is_some_and(|plugins| {
+            plugins
+                .iter()
+                .any(|plugin| plugin["component"].as_str() == Some("analysis-kuromoji"))
+        }))
     }
 
-    async fn initialize_index(client: &Elasticsearch) -> AppResult<()> {
-        let exists = client
+    async fn index_exists(client: &Elasticsearch, index: &str) -> AppResult<bool> {
+        Ok(client
             .indices()
-            .exists(elasticsearch::indices::IndicesExistsParts::Index(&[INDEX_NAME]))
+            .exists(IndicesExistsParts::Index(&[index]))
             .send()
             .await
             .map_err(|e| AppError::DatabaseError(format!("Failed to check index existence: {}", e)))?
             .status_code()
-            .is_success();
+            .is_success())
+    }
 
-        if !exists {
-            let mapping = json!({
-                "mappings": {
-                    "properties": {
-                        "id": { "type": "keyword" },
-                        "title": { 
-                            "type": "text",
-                            "analyzer": "standard",
-                            "fields": {
-                                "keyword": { "type": "keyword" }
-                            }
-                        },
-                        "content": { 
-                            "type": "text",
-                            "analyzer": "standard"
-                        },
-                        "tags": { "type": "keyword" },
-                        "user_id": { "type": "keyword" },
-                        "created_at": { "type": "date" },
-                        "updated_at": { "type": "date" },
-                        "version": { "type": "integer" }
-                    }
-                },
-                "settings": {
-                    "number_of_shards": 1,
-                    "number_of_replicas": 1
+    /// エイリアスが指しているインデックス（存在しなければ `None`）
+    async fn alias_indices(client: &Elasticsearch, alias: &str) -> AppResult<Option<Vec<String>>> {
+        let response = client
+            .indices()
+            .get_alias(IndicesGetAliasParts::Name(&[alias]))
+            .send()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to get index alias: {}", e)))?;
+
+        if !response.status_code().is_success() {
+            return Ok(None);
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse index alias: {}", e)))?;
+        let mut indices: Vec<String> = body
+            .as_object()
+            .map(|indices| indices.keys().cloned().collect())
+            .unwrap_or_default();
+        indices.sort();
+        Ok(Some(indices))
+    }
+
+    /// `memos` がエイリアスとしてどのインデックスを指しているか
+    async fn alias_state(client: &Elasticsearch) -> AppResult<AliasState> {
+        if let Some(indices) = Self::alias_indices(client, INDEX_NAME).await? {
+            return Ok(AliasState::Alias(indices));
+        }
+
+        if Self::index_exists(client, INDEX_NAME).await? {
+            Ok(AliasState::Legacy)
+        } else {
+            Ok(AliasState::Missing)
+        }
+    }
+
+    /// 読み書きしているインデックスが現在の定義（版と解析方法）で作られているか
+    pub async fn index_up_to_date(&self) -> AppResult<bool> {
+        let mappings: Value = self.client
+            .indices()
+            .get_mapping(IndicesGetMappingParts::Index(&[INDEX_NAME]))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to get index mapping: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse index mapping: {}", e)))?;
+
+        let Some(indices) = mappings.as_object() else {
+            return Ok(false);
+        };
+
+        Ok(indices.len() == 1
+            && indices.values().all(|index| {
+                let meta = &index["mappings"]["_meta"];
+                meta["schema_version"].as_u64() == Some(SCHEMA_VERSION as u64)
+                    && meta["analysis"].as_str() == Some(self.analysis.name())
+            }))
+    }
+
+    /// 再インデックス先のインデックスを用意する
+    ///
+    /// 新しい版のインデックスを作成してエイリアス `memos_next` を向けます。
+    /// 以降のメモの書き込みは `memos` と `memos_next` の両方に行われます。
+    /// 中断した再インデックスがある場合は、そのインデックスを返します。
+    pub async fn begin_reindex(&self) -> AppResult<String> {
+        if let Some(index) = Self::alias_indices(&self.client, NEXT_ALIAS)
+            .await?
+            .and_then(|indices| indices.into_iter().next())
+        {
+            return Ok(index);
+        }
+
+        let existing: Value = self.client
+            .indices()
+            .get(IndicesGetParts::Index(&[&format!("{}*", INDEX_PREFIX)]))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to list indices: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse index list: {}", e)))?;
+
+        let latest = existing
+            .as_object()
+            .into_iter()
+            .flat_map(|indices| indices.keys())
+            .filter_map(|name| name.strip_prefix(INDEX_PREFIX)?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0);
+        let index = Self::versioned_index_name(latest + 1);
+
+        let mut definition = memo_index_definition(self.analysis);
+        definition["aliases"] = json!({ NEXT_ALIAS: {} });
+
+        self.client
+            .indices()
+            .create(IndicesCreateParts::Index(&index))
+            .body(definition)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create index: {}", e)))?;
+
+        *self.reindexing.lock().unwrap() = None;
+        info!("Created search index {} for reindexing", index);
+        Ok(index)
+    }
+
+    /// 再インデックス先にメモをまとめて追加
+    ///
+    /// 二重書き込みで既に追加されたメモのほうが新しいため、上書きはしません。
+    /// 追加後に行われる二重書き込みの削除で見つかるよう、すぐに検索可能にします。
+    pub async fn create_memos(&self, index: &str, memos: &[(Memo, Vec<Uuid>)]) -> AppResult<()> {
+        if memos.is_empty() {
+            return Ok(());
+        }
+
+        let mut body: Vec<JsonBody<Value>> = Vec::with_capacity(memos.len() * 2);
+        for (memo, folder_path) in memos {
+            body.push(json!({ "create": { "_id": memo.id.to_string() } }).into());
+            body.push(memo_document(memo, folder_path).into());
+        }
+
+        let result: Value = self.client
+            .bulk(BulkParts::Index(index))
+            .body(body)
+            .refresh(Refresh::True)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to bulk index memos: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse bulk result: {}", e)))?;
+
+        if result["errors"].as_bool() == Some(true) {
+            let failure = result["items"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|item| &item["create"])
+                .find(|item| item["error"].is_object() && item["status"].as_u64() != Some(409));
+            if let Some(failure) = failure {
+                return Err(AppError::DatabaseError(format!(
+                    "Failed to bulk index memos: {}",
+                    failure["error"]
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 再インデックス先からメモをまとめて削除（存在しないものは無視）
+    pub async fn delete_memos_from(&self, index: &str, ids: &[Uuid]) -> AppResult<()> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        let body: Vec<JsonBody<Value>> = ids
+            .iter()
+            .map(|id| json!({ "delete": { "_id": id.to_string() } }).into())
+            .collect();
+
+        let result: Value = self.client
+            .bulk(BulkParts::Index(index))
+            .body(body)
+            .refresh(Refresh::True)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to bulk delete memos: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse bulk result: {}", e)))?;
+
+        if result["errors"].as_bool() == Some(true) {
+            let failure = result["items"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|item| &item["delete"])
+                .find(|item| item["error"].is_object() && item["status"].as_u64() != Some(404));
+            if let Some(failure) = failure {
+                return Err(AppError::DatabaseError(format!(
+                    "Failed to bulk delete memos: {}",
+                    failure["error"]
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// エイリアス `memos` を再インデックス先に切り替え、古いインデックスを削除
+    pub async fn finish_reindex(&self, index: &str) -> AppResult<()> {
+        self.client
+            .indices()
+            .refresh(IndicesRefreshParts::Index(&[index]))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to refresh index: {}", e)))?;
+
+        let current = Self::alias_state(&self.client).await?;
+        let previous: Vec<String> = match &current {
+            AliasState::Alias(indices) => indices.iter().filter(|name| *name != index).cloned().collect(),
+            _ => vec![],
+        };
+
+        let mut actions = vec![
+            json!({ "add": { "index": index, "alias": INDEX_NAME } }),
+            json!({ "remove": { "index": index, "alias": NEXT_ALIAS } }),
+        ];
+        match &current {
+            AliasState::Missing => {}
+            // エイリアス導入前のインデックスの削除とエイリアスの追加を1回の操作で行う
+            AliasState::Legacy => actions.push(json!({ "remove_index": { "index": INDEX_NAME } })),
+            AliasState::Alias(_) => {
+                for name in &previous {
+                    actions.push(json!({ "remove": { "index": name, "alias": INDEX_NAME } }));
                 }
-            });
+            }
+        }
 
-            client
+        let swapped = self.client
+            .indices()
+            .update_aliases()
+            .body(json!({ "actions": actions }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code());
+
+        if let Err(e) = swapped {
+            // 別のインスタンスが先に切り替えた場合は成功として扱う
+            match Self::alias_state(&self.client).await? {
+                AliasState::Alias(indices) if indices == [index.to_string()] => {}
+                _ => return Err(AppError::DatabaseError(format!("Failed to switch index alias: {}", e))),
+            }
+        }
+        *self.reindexing.lock().unwrap() = None;
+        info!("Switched search index alias to {}", index);
+
+        if !previous.is_empty() {
+            let previous: Vec<&str> = previous.iter().map(String::as_str).collect();
+            self.client
                 .indices()
-                .create(elasticsearch::indices::IndicesCreateParts::Index(INDEX_NAME))
-                .body(mapping)
+                .delete(IndicesDeleteParts::Index(&previous))
+                .ignore_unavailable(true)
                 .send()
                 .await
-                .map_err(|e| AppError::DatabaseError(format!("Failed to create index: {}", e)))?;
+                .and_then(|response| response.error_for_status_code())
+                .map_err(|e| AppError::DatabaseError(format!("Failed to delete old index: {}", e)))?;
         }
 
         Ok(())
     }
 
-    pub async fn index_memo(&self, memo: &Memo) -> AppResult<()> {
-        let doc = json!({
-            "id": memo.id.to_string(),
-            "title": memo.title,
-            "content": memo.content,
-            "tags": memo.tags,
-            "user_id": memo.user_id.to_string(),
-            "created_at": memo.created_at,
-            "updated_at": memo.updated_at,
-            "version": memo.version
-        });
+    /// 再インデックス中（`memos_next` が存在する）か
+    ///
+    /// 書き込みのたびに問い合わせないよう、`REINDEX_STATE_TTL` の間は結果を使い回します。
+    async fn reindexing(&self) -> AppResult<bool> {
+        if let Some((checked_at, reindexing)) = *self.reindexing.lock().unwrap() {
+            if checked_at.elapsed() < REINDEX_STATE_TTL {
+                return Ok(reindexing);
+            }
+        }
+
+        let reindexing = self.client
+            .indices()
+            .exists_alias(IndicesExistsAliasParts::Name(&[NEXT_ALIAS]))
+            .send()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to check index alias: {}", e)))?
+            .status_code()
+            .is_success();
+
+        *self.reindexing.lock().unwrap() = Some((Instant::now(), reindexing));
+        Ok(reindexing)
+    }
+
+    /// メモをインデックス
+    ///
+    /// `folder_path` は最上位から所属フォルダまでのフォルダIDで、
+    /// フォルダ配下のメモをまとめて絞り込むために使用します。
+    pub async fn index_memo(&self, memo: &Memo, folder_path: &[Uuid]) -> AppResult<()> {
+        let doc = memo_document(memo, folder_path);
 
         self.client
             .index(IndexParts::Index(INDEX_NAME))
@@ -104,71 +445,109 @@ impl ElasticsearchClient {
             .await
             .map_err(|e| AppError::DatabaseError(format!("Failed to index memo: {}", e)))?;
 
+        // 再インデックス中は作成中のインデックスにも書き込む
+        if self.reindexing().await? {
+            let response = self.client
+                .index(IndexParts::Index(NEXT_ALIAS))
+                .id(memo.id.to_string())
+                .document(&doc)
+                .require_alias(true)
+                .send()
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to index memo: {}", e)))?;
+
+            // 直前に切り替えが完了してエイリアスがなくなっている場合は不要
+            if response.status_code() != StatusCode::NOT_FOUND {
+                response
+                    .error_for_status_code()
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to index memo: {}", e)))?;
+            }
+        }
+
         Ok(())
     }
 
-    pub async fn search_memos(
-        &self,
-        query: &str,
-        tag: Option<String>,
-        user_id: Uuid,
-    ) -> AppResult<Vec<Memo>> {
-        let mut should_clauses: Vec<Value> = vec![];
-        
-        if !query.is_empty() {
-            should_clauses.extend(vec![
-                json!({
-                    "match": {
-                        "title": {
-                            "query": query,
-                            "boost": 2.0
-                        }
-                    }
-                }),
-                json!({
-                    "match": {
-                        "content": query
-                    }
-                })
-            ]);
-        }
+    /// メモの検索
+    ///
+    /// 結果は更新日時の新しい順（同時刻はID順）で、`hits.total` を正確に数えます。
+    /// 強調表示の断片はHTMLエスケープした本文に指定のタグを挿入したものです。
+    pub async fn search_memos(&self, search: &MemoSearch) -> AppResult<MemoSearchPage> {
+        let paging = &search.paging;
 
         let mut must_clauses = vec![
             json!({
                 "term": {
-                    "user_id": user_id.to_string()
+                    "user_id": search.user_id.to_string()
                 }
             })
         ];
 
-        if let Some(tag_value) = tag {
-            must_clauses.push(json!({
-                "term": {
-                    "tags": tag_value
-                }
-            }));
+        // タグ・フォルダによる絞り込み
+        let tag_filter = search.tag.as_ref().map(|tag_value| json!({
+            "term": {
+                "tags": tag_value
+            }
+        }));
+        let folder_filter = search.folder_id.map(folder_drill_down);
+
+        // 絞り込み候補を集計する場合は、絞り込みで候補が減らないよう post_filter で適用する
+        if !search.facets {
+            must_clauses.extend(tag_filter.iter().chain(folder_filter.iter()).cloned());
         }
 
-        let query_body = json!({
+        if let Some(expr) = &search.query {
+            must_clauses.push(expr_query(expr));
+        }
+
+        let mut query_body = json!({
             "query": {
                 "bool": {
-                    "must": must_clauses,
-                    "should": should_clauses,
-                    "minimum_should_match": if query.is_empty() { 0 } else { 1 }
+                    "must": must_clauses
                 }
             },
+            // search_after で続きを取得できるよう、一意なIDで順序を確定させる
             "sort": [
-                { "updated_at": { "order": "desc" } }
-            ]
+                { "updated_at": { "order": "desc" } },
+                { "id": { "order": "asc" } }
+            ],
+            "size": paging.size,
+            "track_total_hits": true,
+            "highlight": {
+                "pre_tags": [search.highlight.pre_tag],
+                "post_tags": [search.highlight.post_tag],
+                "encoder": "html",
+                "fields": {
+                    // タイトルは断片に分けず全体を返す
+                    "title": { "number_of_fragments": 0 },
+                    "content": {
+                        "fragment_size": search.highlight.fragment_size,
+                        "number_of_fragments": 3,
+                        "order": "score"
+                    }
+                }
+            }
         });
 
+        if search.facets {
+            let drill_downs: Vec<&Value> = tag_filter.iter().chain(folder_filter.iter()).collect();
+            if !drill_downs.is_empty() {
+                query_body["post_filter"] = json!({ "bool": { "filter": drill_downs } });
+            }
+            query_body["aggs"] = facet_aggregations(tag_filter.as_ref(), folder_filter.as_ref());
+        }
+
+        match &paging.search_after {
+            Some(cursor) => query_body["search_after"] = decode_search_after(cursor)?,
+            None => query_body["from"] = json!(paging.from),
+        }
+
         let response = self.client
             .search(SearchParts::Index(&[INDEX_NAME]))
             .body(query_body)
-            .size(100)
             .send()
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to execute search: {}", e)))?;
+            .and_then(|response| response.error_for_status_code())
+            .map_err(search_error)?;
 
         let search_hits = response.json::<Value>().await.map_err(|e| {
             AppError::DatabaseError(format!("Failed to parse search response: {}", e))
@@ -178,35 +557,158 @@ impl ElasticsearchClient {
             .as_array()
             .ok_or_else(|| AppError::DatabaseError("Invalid search response format".to_string()))?;
 
-        let memos = hits
-            .iter()
+        let total = search_hits["hits"]["total"]["value"]
+            .as_u64()
+            .ok_or_else(|| AppError::DatabaseError("Invalid search response format".to_string()))?;
+
+        // ページが埋まっていれば続きがある可能性があるため、最後の結果の位置を返す
+        let search_after = match hits.last() {
+            Some(last) if hits.len() >= paging.size => Some(encode_search_after(&last["sort"])),
+            _ => None,
+        };
+
+        Ok(MemoSearchPage {
+            hits: hits.iter().filter_map(search_hit).collect(),
+            total,
+            search_after,
+            facets: search.facets.then(|| search_facets(&search_hits["aggregations"])),
+        })
+    }
+
+    /// タイトルとタグの補完候補を1回の検索で取得
+    ///
+    /// タイトルは `title.suggest` に対する前方一致、タグはユーザーの全メモのタグのうち
+    /// `prefix` で始まるものを集計します。
+    pub async fn suggest(&self, user_id: Uuid, prefix: &str, limit: usize) -> AppResult<MemoSuggestions> {
+        let body = json!({
+            "size": limit,
+            "_source": ["id", "title"],
+            "track_total_hits": false,
+            "query": {
+                "bool": {
+                    "filter": [
+                        { "term": { "user_id": user_id.to_string() } }
+                    ],
+                    "must": [
+                        {
+                            "multi_match": {
+                                "query": prefix,
+                                "type": "bool_prefix",
+                                "fields": [
+                                    "title.suggest",
+                                    "title.suggest._2gram",
+                                    "title.suggest._3gram"
+                                ]
+                            }
+                        }
+                    ]
+                }
+            },
+            // タグは検索条件に関係なくユーザーの全メモから集計する
+            "aggs": {
+                "all_memos": {
+                    "global": {},
+                    "aggs": {
+                        "user_memos": {
+                            "filter": { "term": { "user_id": user_id.to_string() } },
+                            "aggs": {
+                                "tags": {
+                                    "terms": {
+                                        "field": "tags",
+                                        "include": format!("{}.*", escape_regex(prefix)),
+                                        "size": limit,
+                                        "order": [
+                                            { "_count": "desc" },
+                                            { "_key": "asc" }
+                                        ]
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let response = self.client
+            .search(SearchParts::Index(&[INDEX_NAME]))
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status_code())
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch suggestions: {}", e)))?;
+
+        let response_body = response.json::<Value>()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse response: {}", e)))?;
+
+        let titles = response_body["hits"]["hits"]
+            .as_array()
+            .into_iter()
+            .flatten()
             .filter_map(|hit| {
-                let source = hit["_source"].as_object()?;
-                let id = Uuid::parse_str(source["id"].as_str()?).ok()?;
-                let user_id = Uuid::parse_str(source["user_id"].as_str()?).ok()?;
-
-                Some(Memo {
-                    id,
-                    title: source["title"].as_str()?.to_string(),
-                    content: source["content"].as_str()?.to_string(),
-                    tags: source["tags"]
-                        .as_array()?
-                        .iter()
-                        .filter_map(|t| t.as_str().map(String::from))
-                        .collect(),
-                    user_id,
-                    created_at: chrono::DateTime::parse_from_rfc3339(
-                        source["created_at"].as_str()?
-                    ).ok()?.with_timezone(&chrono::Utc),
-                    updated_at: chrono::DateTime::parse_from_rfc3339(
-                        source["updated_at"].as_str()?
-                    ).ok()?.with_timezone(&chrono::Utc),
-                    version: source["version"].as_i64()? as i32,
-                })
+                let source = &hit["_source"];
+                Some((
+                    Uuid::parse_str(source["id"].as_str()?).ok()?,
+                    source["title"].as_str()?.to_string(),
+                ))
             })
             .collect();
 
-        Ok(memos)
+        let tags = response_body["aggregations"]["all_memos"]["user_memos"]["tags"]["buckets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|bucket| Some((bucket["key"].as_str()?.to_string(), bucket["doc_count"].as_u64()?)))
+            .collect();
+
+        Ok(MemoSuggestions { titles, tags })
+    }
+
+    /// ユーザーのタグごとのメモ数（使用数の多い順）
+    pub async fn tag_counts(&self, user_id: Uuid) -> AppResult<Vec<(String, u64)>> {
+        let query_body = json!({
+            "size": 0,
+            "query": {
+                "term": {
+                    "user_id": user_id.to_string()
+                }
+            },
+            "aggs": {
+                "tags": {
+                    "terms": {
+                        "field": "tags",
+                        "size": MAX_TAG_BUCKETS,
+                        "order": [
+                            { "_count": "desc" },
+                            { "_key": "asc" }
+                        ]
+                    }
+                }
+            }
+        });
+
+        let response = self.client
+            .search(SearchParts::Index(&[INDEX_NAME]))
+            .body(query_body)
+            .send()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to aggregate tags: {}", e)))?;
+
+        let body = response.json::<Value>().await.map_err(|e| {
+            AppError::DatabaseError(format!("Failed to parse aggregation response: {}", e))
+        })?;
+
+        let buckets = body["aggregations"]["tags"]["buckets"]
+            .as_array()
+            .ok_or_else(|| AppError::DatabaseError("Invalid aggregation response format".to_string()))?;
+
+        Ok(buckets
+            .iter()
+            .filter_map(|bucket| {
+                Some((bucket["key"].as_str()?.to_string(), bucket["doc_count"].as_u64()?))
+            })
+            .collect())
     }
 
     pub async fn delete_memo(&self, id: Uuid) -> AppResult<()> {
@@ -219,8 +721,9 @@ impl ElasticsearchClient {
         });
 
         self.client
-            .delete_by_query(DeleteByQueryParts::Index(&[INDEX_NAME]))
+            .delete_by_query(DeleteByQueryParts::Index(&[INDEX_NAME, NEXT_ALIAS]))
             .body(query_body)
+            .ignore_unavailable(true)
             .refresh(true)
             .send()
             .await
@@ -229,6 +732,28 @@ impl ElasticsearchClient {
         Ok(())
     }
 
+    /// ユーザーのメモをすべて削除
+    pub async fn delete_memos_by_user_id(&self, user_id: Uuid) -> AppResult<()> {
+        let query_body = json!({
+            "query": {
+                "term": {
+                    "user_id": user_id.to_string()
+                }
+            }
+        });
+
+        self.client
+            .delete_by_query(DeleteByQueryParts::Index(&[INDEX_NAME, NEXT_ALIAS]))
+            .body(query_body)
+            .ignore_unavailable(true)
+            .refresh(true)
+            .send()
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete memos: {}", e)))?;
+
+        Ok(())
+    }
+
     pub async fn health_check(&self) -> AppResult<bool> {
         let response = self.client
             .cat()
@@ -239,4 +764,344 @@ impl ElasticsearchClient {
 
         Ok(response.status_code().is_success())
     }
-}
\ No newline at end of file
+}
+
+/// 検索条件に一致したメモ全体に対する絞り込み候補の集計
+///
+/// 集計は post_filter の前に行われるため、タグ・フォルダの絞り込みは
+/// それぞれ自身以外の候補の集計にだけ適用します（選択中のタグ以外のタグも候補に残る）。
+fn facet_aggregations(tag_filter: Option<&Value>, folder_filter: Option<&Value>) -> Value {
+    let facets = json!({
+        "tags": {
+            "terms": {
+                "field": "tags",
+                "size": MAX_FACET_BUCKETS,
+                "order": [
+                    { "_count": "desc" },
+                    { "_key": "asc" }
+                ]
+            }
+        },
+        "created_months": {
+            "date_histogram": {
+                "field": "created_at",
+                "calendar_interval": "month",
+                "format": "yyyy-MM",
+                "min_doc_count": 1
+            }
+        },
+        // 絞り込みと同じく、配下のフォルダのメモも含めて数える
+        "folders": {
+            "terms": {
+                "field": "folder_path",
+                "size": MAX_FACET_BUCKETS
+            }
+        },
+        "pinned": { "filter": { "term": { "pinned": true } } },
+        "archived": { "filter": { "term": { "archived": true } } },
+        "favorite": { "filter": { "term": { "favorite": true } } }
+    });
+
+    let Value::Object(facets) = facets else {
+        unreachable!();
+    };
+    facets
+        .into_iter()
+        .map(|(name, facet)| {
+            let filters: Vec<&Value> = match name.as_str() {
+                "tags" => folder_filter.into_iter().collect(),
+                "folders" => tag_filter.into_iter().collect(),
+                _ => tag_filter.into_iter().chain(folder_filter).collect(),
+            };
+            let scoped = json!({
+                "filter": { "bool": { "filter": filters } },
+                "aggs": { "facet": facet }
+            });
+            (name, scoped)
+        })
+        .collect()
+}
+
+/// 検索の失敗を変換
+///
+/// 不正な `search_after` や検索できる範囲を超えるページの指定など、
+/// Elasticsearch が要求を拒否した場合（400）は BadRequest とします。
+fn search_error(e: elasticsearch::Error) -> AppError {
+    match e.status_code() {
+        Some(StatusCode::BAD_REQUEST) => AppError::BadRequest(format!("Invalid search request: {}", e)),
+        _ => AppError::DatabaseError(format!("Failed to execute search: {}", e)),
+    }
+}
+
+/// 指定フォルダとその配下のフォルダのメモに絞り込む条件
+fn folder_drill_down(folder_id: Uuid) -> Value {
+    json!({
+        "term": {
+            "folder_path": folder_id.to_string()
+        }
+    })
+}
+
+/// 集計結果を絞り込み候補に変換
+fn search_facets(aggregations: &Value) -> SearchFacets {
+    let facet = |name: &str| &aggregations[name]["facet"];
+    let buckets = |name: &str| -> Vec<Value> {
+        facet(name)["buckets"].as_array().cloned().unwrap_or_default()
+    };
+
+    SearchFacets {
+        tags: buckets("tags")
+            .iter()
+            .filter_map(|bucket| Some((bucket["key"].as_str()?.to_string(), bucket["doc_count"].as_u64()?)))
+            .collect(),
+        created_months: buckets("created_months")
+            .iter()
+            .filter_map(|bucket| {
+                Some((bucket["key_as_string"].as_str()?.to_string(), bucket["doc_count"].as_u64()?))
+            })
+            .collect(),
+        folders: buckets("folders")
+            .iter()
+            .filter_map(|bucket| {
+                Some((Uuid::parse_str(bucket["key"].as_str()?).ok()?, bucket["doc_count"].as_u64()?))
+            })
+            .collect(),
+        pinned: facet("pinned")["doc_count"].as_u64().unwrap_or_default(),
+        archived: facet("archived")["doc_count"].as_u64().unwrap_or_default(),
+        favorite: facet("favorite")["doc_count"].as_u64().unwrap_or_default(),
+    }
+}
+
+/// 検索式を Elasticsearch のクエリに変換
+fn expr_query(expr: &SearchExpr) -> Value {
+    match expr {
+        SearchExpr::And(operands) => json!({
+            "bool": { "must": operands.iter().map(expr_query).collect::<Vec<_>>() }
+        }),
+        SearchExpr::Or(operands) => json!({
+            "bool": {
+                "should": operands.iter().map(expr_query).collect::<Vec<_>>(),
+                "minimum_should_match": 1
+            }
+        }),
+        SearchExpr::Not(operand) => json!({
+            "bool": { "must_not": [expr_query(operand)] }
+        }),
+        SearchExpr::Text { field, text, phrase } => {
+            let fields: &[(&str, f64)] = match field {
+                TextField::Any => &[("title", 2.0), ("content", 1.0)],
+                TextField::Title => &[("title", 1.0)],
+                TextField::Content => &[("content", 1.0)],
+            };
+
+            let mut should = Vec::new();
+            for &(name, boost) in fields {
+                if *phrase {
+                    should.push(json!({
+                        "match_phrase": { name: { "query": text, "boost": boost } }
+                    }));
+                } else {
+                    should.push(json!({
+                        "match": { name: { "query": text, "operator": "and", "boost": boost } }
+                    }));
+                    // 形態素解析で区切れない語句も部分一致で拾う（スコアは控えめにする）
+                    should.push(json!({
+                        "match": {
+                            format!("{}.ngram", name): {
+                                "query": text,
+                                "minimum_should_match": "75%",
+                                "boost": boost * 0.25
+                            }
+                        }
+                    }));
+                }
+            }
+
+            json!({ "bool": { "should": should, "minimum_should_match": 1 } })
+        }
+        SearchExpr::Tag(tag) => json!({ "term": { "tags": tag } }),
+        SearchExpr::Date { field, from, until } => {
+            let name = match field {
+                DateField::CreatedAt => "created_at",
+                DateField::UpdatedAt => "updated_at",
+            };
+            let mut range = json!({});
+            if let Some(from) = from {
+                range["gte"] = json!(from);
+            }
+            if let Some(until) = until {
+                range["lt"] = json!(until);
+            }
+            json!({ "range": { name: range } })
+        }
+    }
+}
+
+/// Lucene の正規表現で特別な意味を持つ文字をエスケープ
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if ".?+*|{}[]()\"#@&<>~\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// インデックスに格納するメモのドキュメント
+fn memo_document(memo: &Memo, folder_path: &[Uuid]) -> Value {
+    json!({
+        "id": memo.id.to_string(),
+        "title": memo.title,
+        "content": memo.content,
+        "tags": memo.tags,
+        "user_id": memo.user_id.to_string(),
+        "folder_id": memo.folder_id.map(|id| id.to_string()),
+        "folder_path": folder_path.iter().map(Uuid::to_string).collect::<Vec<_>>(),
+        "pinned": memo.pinned,
+        "archived": memo.archived,
+        "favorite": memo.favorite,
+        "created_at": memo.created_at,
+        "updated_at": memo.updated_at,
+        "version": memo.version
+    })
+}
+
+/// 検索結果の1件を強調表示の断片とともに変換
+fn search_hit(hit: &Value) -> Option<MemoSearchHit> {
+    let fragments = |field: &str| -> Vec<String> {
+        hit["highlight"][field]
+            .as_array()
+            .map(|fragments| {
+                fragments
+                    .iter()
+                    .filter_map(|fragment| fragment.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    Some(MemoSearchHit {
+        memo: memo_from_hit(hit)?,
+        title_highlights: fragments("title"),
+        content_highlights: fragments("content"),
+    })
+}
+
+/// 検索結果の1件をメモに変換
+fn memo_from_hit(hit: &Value) -> Option<Memo> {
+    let source = hit["_source"].as_object()?;
+    let id = Uuid::parse_str(source["id"].as_str()?).ok()?;
+    let user_id = Uuid::parse_str(source["user_id"].as_str()?).ok()?;
+
+    Some(Memo {
+        id,
+        title: source["title"].as_str()?.to_string(),
+        content: source["content"].as_str()?.to_string(),
+        tags: source["tags"]
+            .as_array()?
+            .iter()
+            .filter_map(|t| t.as_str().map(String::from))
+            .collect(),
+        user_id,
+        created_at: chrono::DateTime::parse_from_rfc3339(
+            source["created_at"].as_str()?
+        ).ok()?.with_timezone(&chrono::Utc),
+        updated_at: chrono::DateTime::parse_from_rfc3339(
+            source["updated_at"].as_str()?
+        ).ok()?.with_timezone(&chrono::Utc),
+        version: source["version"].as_i64()? as i32,
+        // ゴミ箱のメモはインデックスから除外している
+        deleted_at: None,
+        folder_id: source["folder_id"]
+            .as_str()
+            .and_then(|id| Uuid::parse_str(id).ok()),
+        pinned: source["pinned"].as_bool().unwrap_or_default(),
+        archived: source["archived"].as_bool().unwrap_or_default(),
+        favorite: source["favorite"].as_bool().unwrap_or_default(),
+    })
+}
+
+/// 最後の結果のソート値を不透明なカーソルに変換
+fn encode_search_after(sort_values: &Value) -> String {
+    URL_SAFE_NO_PAD.encode(sort_values.to_string())
+}
+
+fn decode_search_after(cursor: &str) -> AppResult<Value> {
+    let invalid = || AppError::BadRequest("Invalid search_after cursor".into());
+    let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+    let sort_values: Value = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+    if !sort_values.as_array().is_some_and(|values| values.len() == 2) {
+        return Err(invalid());
+    }
+    Ok(sort_values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_after_round_trip() {
+        let sort_values = json!([1700000000000i64, "3f2b6c1e-0000-0000-0000-000000000000"]);
+        let cursor = encode_search_after(&sort_values);
+
+        assert_eq!(decode_search_after(&cursor).unwrap(), sort_values);
+        assert!(matches!(decode_search_after("invalid!"), Err(AppError::BadRequest(_))));
+        assert!(matches!(
+            decode_search_after(&encode_search_after(&json!({}))),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_escape_regex() {
+        assert_eq!(escape_regex("c++"), "c\\+\\+");
+        assert_eq!(escape_regex("a.b(c)"), "a\\.b\\(c\\)");
+        assert_eq!(escape_regex("日本語"), "日本語");
+    }
+
+    #[test]
+    fn test_search_facets_from_aggregations() {
+        let folder_id = Uuid::new_v4();
+        let aggregations = json!({
+            "tags": { "doc_count": 4, "facet": { "buckets": [{ "key": "rust", "doc_count": 3 }, { "key": "db", "doc_count": 1 }] } },
+            "created_months": { "doc_count": 4, "facet": { "buckets": [{ "key": 1717200000000i64, "key_as_string": "2024-06", "doc_count": 4 }] } },
+            "folders": { "doc_count": 4, "facet": { "buckets": [{ "key": folder_id.to_string(), "doc_count": 2 }] } },
+            "pinned": { "doc_count": 4, "facet": { "doc_count": 1 } },
+            "archived": { "doc_count": 4, "facet": { "doc_count": 0 } },
+            "favorite": { "doc_count": 4, "facet": { "doc_count": 2 } }
+        });
+
+        let facets = search_facets(&aggregations);
+
+        assert_eq!(facets.tags, vec![("rust".to_string(), 3), ("db".to_string(), 1)]);
+        assert_eq!(facets.created_months, vec![("2024-06".to_string(), 4)]);
+        assert_eq!(facets.folders, vec![(folder_id, 2)]);
+        assert_eq!((facets.pinned, facets.archived, facets.favorite), (1, 0, 2));
+    }
+
+    #[test]
+    fn test_facet_aggregations_exclude_own_filter() {
+        let tag_filter = json!({ "term": { "tags": "rust" } });
+        let folder_filter = folder_drill_down(Uuid::new_v4());
+
+        let aggregations = facet_aggregations(Some(&tag_filter), Some(&folder_filter));
+        let filters = |name: &str| aggregations[name]["filter"]["bool"]["filter"].clone();
+
+        assert_eq!(filters("tags"), json!([folder_filter]));
+        assert_eq!(filters("folders"), json!([tag_filter]));
+        assert_eq!(filters("created_months"), json!([tag_filter, folder_filter]));
+        assert_eq!(filters("pinned"), json!([tag_filter, folder_filter]));
+        assert_eq!(aggregations["tags"]["aggs"]["facet"]["terms"]["field"], "tags");
+
+        // フォルダの候補の件数は、そのフォルダで絞り込んだときの件数と同じ範囲で数える
+        let folder_field = aggregations["folders"]["aggs"]["facet"]["terms"]["field"].as_str().unwrap();
+        assert!(folder_filter["term"].get(folder_field).is_some());
+
+        let unfiltered = facet_aggregations(None, None);
+        assert_eq!(unfiltered["folders"]["filter"]["bool"]["filter"], json!([]));
+    }
+}
@@ -1,4 +1,5 @@
 //src/infrastructure/persistence/mod.rs
+pub mod analysis;
 pub mod elasticsearch;
 pub mod redis;
 pub mod scylla;
@@ -1,6 +1,6 @@
 //src/infrastructure/persistence/redis.rs
 use std::time::Duration;
-use redis::{Client, AsyncCommands, RedisError};
+use redis::{Client, AsyncCommands, RedisError, Script};
 use serde::{Serialize, de::DeserializeOwned};
 use tracing::error;
 use crate::error::{AppError, AppResult};
@@ -81,13 +81,13 @@ impl RedisCache {
             Some(exp) => {
                 // TTLの単位を秒に変換（u64として扱う）
                 let seconds: u64 = exp.as_secs();
-                conn.set_ex(key, serialized, seconds).await.map_err(|e| {
+                let _: () = conn.set_ex(key, serialized, seconds).await.map_err(|e| {
                     error!("Failed to set value in Redis with expiration: {}", e);
                     AppError::DatabaseError(e.to_string())
                 })?;
             }
             None => {
-                conn.set(key, serialized).await.map_err(|e| {
+                let _: () = conn.set(key, serialized).await.map_err(|e| {
                     error!("Failed to set value in Redis: {}", e);
                     AppError::DatabaseError(e.to_string())
                 })?;
@@ -104,7 +104,7 @@ impl RedisCache {
             AppError::DatabaseError(e.to_string())
         })?;
 
-        conn.del(key).await.map_err(|e| {
+        let _: () = conn.del(key).await.map_err(|e| {
             error!("Failed to delete key from Redis: {}", e);
             AppError::DatabaseError(e.to_string())
         })?;
@@ -112,6 +112,246 @@ impl RedisCache {
         Ok(())
     }
 
+    /// キーに対応する値を取得し、同時に削除（使い捨ての値向け）
+    pub async fn take<T: DeserializeOwned>(&self, key: &str) -> AppResult<Option<T>> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let result: Option<String> = conn.get_del(key).await.map_err(|e| {
+            error!("Failed to take value from Redis: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        result
+            .map(|value| {
+                serde_json::from_str(&value).map_err(|e| {
+                    error!("Failed to parse Redis value: {}", e);
+                    AppError::DatabaseError(e.to_string())
+                })
+            })
+            .transpose()
+    }
+
+    /// JSON で保存した値の数値の `field` を1増やし、更新後の値を返す
+    ///
+    /// 読み込みから書き込みまでを Lua スクリプトで1回の操作として行い、有効期限は保ちます。
+    /// キーがない場合と、増やした値が `limit` を超えてキーを削除した場合は `None` を返します。
+    pub async fn increment_field<T: DeserializeOwned>(
+        &self,
+        key: &str,
+        field: &str,
+        limit: i64,
+    ) -> AppResult<Option<T>> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let script = Script::new(
+            r"
+            local current = redis.call('GET', KEYS[1])
+            if not current then
+                return false
+            end
+            local value = cjson.decode(current)
+            value[ARGV[1]] = (value[ARGV[1]] or 0) + 1
+            if value[ARGV[1]] > tonumber(ARGV[2]) then
+                redis.call('DEL', KEYS[1])
+                return false
+            end
+            local updated = cjson.encode(value)
+            redis.call('SET', KEYS[1], updated, 'KEEPTTL')
+            return updated
+            ",
+        );
+        let result: Option<String> = script
+            .key(key)
+            .arg(field)
+            .arg(limit)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Failed to increment field in Redis: {}", e);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        result
+            .map(|value| {
+                serde_json::from_str(&value).map_err(|e| {
+                    error!("Failed to parse Redis value: {}", e);
+                    AppError::DatabaseError(e.to_string())
+                })
+            })
+            .transpose()
+    }
+
+    /// JSON で保存した値の `field` が `expected` のままの場合だけ値を置き換える
+    ///
+    /// 読み込みから書き込みまでを Lua スクリプトで1回の操作として行い、
+    /// 置き換えたかどうかを返します（キーがなければ `false`）。
+    pub async fn compare_and_set<T: Serialize>(
+        &self,
+        key: &str,
+        field: &str,
+        expected: &str,
+        value: &T,
+        expiration: Duration,
+    ) -> AppResult<bool> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let serialized = serde_json::to_string(value).map_err(|e| {
+            error!("Failed to serialize value: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let script = Script::new(
+            r"
+            local current = redis.call('GET', KEYS[1])
+            if not current or cjson.decode(current)[ARGV[1]] ~= ARGV[2] then
+                return 0
+            end
+            redis.call('SET', KEYS[1], ARGV[3], 'EX', ARGV[4])
+            return 1
+            ",
+        );
+        let replaced: i32 = script
+            .key(key)
+            .arg(field)
+            .arg(expected)
+            .arg(serialized)
+            .arg(expiration.as_secs().max(1))
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Failed to compare and set value in Redis: {}", e);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        Ok(replaced == 1)
+    }
+
+    /// JSON で保存した値の `field` が `blocked` のいずれかでなければ値を置き換える
+    ///
+    /// 読み込みから書き込みまでを Lua スクリプトで1回の操作として行い、
+    /// 置き換えたかどうかを返します（キーがなければ保存して `true`）。
+    pub async fn set_unless<T: Serialize>(
+        &self,
+        key: &str,
+        field: &str,
+        blocked: &[&str],
+        value: &T,
+        expiration: Duration,
+    ) -> AppResult<bool> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let serialized = serde_json::to_string(value).map_err(|e| {
+            error!("Failed to serialize value: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let script = Script::new(
+            r"
+            local current = redis.call('GET', KEYS[1])
+            if current then
+                local state = cjson.decode(current)[ARGV[3]]
+                for i = 4, #ARGV do
+                    if state == ARGV[i] then
+                        return 0
+                    end
+                end
+            end
+            redis.call('SET', KEYS[1], ARGV[1], 'EX', ARGV[2])
+            return 1
+            ",
+        );
+        let mut invocation = script.key(key);
+        invocation
+            .arg(serialized)
+            .arg(expiration.as_secs().max(1))
+            .arg(field);
+        for state in blocked {
+            invocation.arg(*state);
+        }
+        let replaced: i32 = invocation.invoke_async(&mut conn).await.map_err(|e| {
+            error!("Failed to set value in Redis: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(replaced == 1)
+    }
+
+    /// `owner` としてリースを取得または延長
+    ///
+    /// 他の所有者が保持している間は取得できず `false` を返します。
+    /// 延長しないまま `ttl` が経過したリースは自動的に解放されます。
+    pub async fn acquire_lease(&self, key: &str, owner: &str, ttl: Duration) -> AppResult<bool> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let script = Script::new(
+            r"
+            if redis.call('GET', KEYS[1]) == ARGV[1] then
+                redis.call('PEXPIRE', KEYS[1], ARGV[2])
+                return 1
+            end
+            if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+                return 1
+            end
+            return 0
+            ",
+        );
+        let acquired: i32 = script
+            .key(key)
+            .arg(owner)
+            .arg(ttl.as_millis().max(1) as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Failed to acquire lease in Redis: {}", e);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        Ok(acquired == 1)
+    }
+
+    /// `owner` が保持しているリースを解放
+    pub async fn release_lease(&self, key: &str, owner: &str) -> AppResult<()> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let script = Script::new(
+            r"
+            if redis.call('GET', KEYS[1]) == ARGV[1] then
+                return redis.call('DEL', KEYS[1])
+            end
+            return 0
+            ",
+        );
+        let _: i32 = script
+            .key(key)
+            .arg(owner)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Failed to release lease in Redis: {}", e);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        Ok(())
+    }
+
     /// キーの存在確認
     pub async fn exists(&self, key: &str) -> AppResult<bool> {
         let mut conn = self.client.get_async_connection().await.map_err(|e| {
@@ -126,6 +366,232 @@ impl RedisCache {
 
         Ok(exists)
     }
+
+    /// キーの有効期限を設定
+    pub async fn expire(&self, key: &str, expiration: Duration) -> AppResult<()> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let _: () = conn.expire(key, expiration.as_secs() as i64).await.map_err(|e| {
+            error!("Failed to set expiration in Redis: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(())
+    }
+
+    /// カウンターを1増やし、増加後の値を返す（初回作成時に有効期限を設定）
+    pub async fn increment(&self, key: &str, expiration: Duration) -> AppResult<i64> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        // 加算と有効期限の設定を1回の操作で行い、期限のないキーが残らないようにする
+        let script = Script::new(
+            r"
+            local value = redis.call('INCR', KEYS[1])
+            if value == 1 or redis.call('TTL', KEYS[1]) == -1 then
+                redis.call('EXPIRE', KEYS[1], ARGV[1])
+            end
+            return value
+            ",
+        );
+        let value: i64 = script
+            .key(key)
+            .arg(expiration.as_secs().max(1))
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Failed to increment value in Redis: {}", e);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        Ok(value)
+    }
+
+    /// キーの残り有効期間（キーが存在しない、または期限なしの場合は `None`）
+    pub async fn time_to_live(&self, key: &str) -> AppResult<Option<Duration>> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let ttl: i64 = conn.ttl(key).await.map_err(|e| {
+            error!("Failed to get TTL from Redis: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok((ttl > 0).then(|| Duration::from_secs(ttl as u64)))
+    }
+
+    /// セットにメンバーを追加
+    pub async fn add_to_set(&self, key: &str, member: &str) -> AppResult<()> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let _: () = conn.sadd(key, member).await.map_err(|e| {
+            error!("Failed to add member to Redis set: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(())
+    }
+
+    /// セットにメンバーを追加し、キーの残り時間が `expiration` より短ければ延長
+    ///
+    /// 残り時間を短くすることはありません。追加と延長は1回の操作として行います。
+    pub async fn add_to_set_extending_ttl(
+        &self,
+        key: &str,
+        member: &str,
+        expiration: Duration,
+    ) -> AppResult<()> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        // TTL のないキー（-1）も延長の対象にする
+        let script = Script::new(
+            r"
+            redis.call('SADD', KEYS[1], ARGV[1])
+            if redis.call('TTL', KEYS[1]) < tonumber(ARGV[2]) then
+                redis.call('EXPIRE', KEYS[1], ARGV[2])
+            end
+            return 1
+            ",
+        );
+        let _: i32 = script
+            .key(key)
+            .arg(member)
+            .arg(expiration.as_secs().max(1))
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| {
+                error!("Failed to add member to Redis set: {}", e);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        Ok(())
+    }
+
+    /// セットからメンバーを削除
+    pub async fn remove_from_set(&self, key: &str, member: &str) -> AppResult<()> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let _: () = conn.srem(key, member).await.map_err(|e| {
+            error!("Failed to remove member from Redis set: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(())
+    }
+
+    /// セットの全メンバーを取得
+    pub async fn set_members(&self, key: &str) -> AppResult<Vec<String>> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let members: Vec<String> = conn.smembers(key).await.map_err(|e| {
+            error!("Failed to get Redis set members: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(members)
+    }
+
+    /// ソート済みセットにスコア付きでメンバーを追加（既存メンバーはスコアを更新）
+    pub async fn add_to_sorted_set(&self, key: &str, member: &str, score: i64) -> AppResult<()> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let _: () = conn.zadd(key, member, score).await.map_err(|e| {
+            error!("Failed to add member to Redis sorted set: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(())
+    }
+
+    /// スコアが `max_score` 以下のメンバーをスコア順に最大 `limit` 件取得
+    pub async fn sorted_set_range_by_score(
+        &self,
+        key: &str,
+        max_score: i64,
+        limit: isize,
+    ) -> AppResult<Vec<String>> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let members: Vec<String> = conn
+            .zrangebyscore_limit(key, "-inf", max_score, 0, limit)
+            .await
+            .map_err(|e| {
+                error!("Failed to get Redis sorted set range: {}", e);
+                AppError::DatabaseError(e.to_string())
+            })?;
+
+        Ok(members)
+    }
+
+    /// スコアの大きい順に最大 `limit` 件のメンバーを取得
+    pub async fn sorted_set_latest(&self, key: &str, limit: isize) -> AppResult<Vec<String>> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let members: Vec<String> = conn.zrevrange(key, 0, limit - 1).await.map_err(|e| {
+            error!("Failed to get Redis sorted set range: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(members)
+    }
+
+    /// スコアの大きい順に `keep` 件を残し、それ以外のメンバーを削除
+    pub async fn trim_sorted_set(&self, key: &str, keep: isize) -> AppResult<()> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let _: () = conn.zremrangebyrank(key, 0, -(keep + 1)).await.map_err(|e| {
+            error!("Failed to trim Redis sorted set: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(())
+    }
+
+    /// ソート済みセットからメンバーを削除し、削除できたかを返す
+    pub async fn remove_from_sorted_set(&self, key: &str, member: &str) -> AppResult<bool> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| {
+            error!("Failed to get Redis connection: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        let removed: i64 = conn.zrem(key, member).await.map_err(|e| {
+            error!("Failed to remove member from Redis sorted set: {}", e);
+            AppError::DatabaseError(e.to_string())
+        })?;
+
+        Ok(removed > 0)
+    }
 }
 
 /// ヘルスチェック用の関数
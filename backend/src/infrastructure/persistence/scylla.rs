@@ -4,16 +4,28 @@ use scylla::{
     Session, SessionBuilder,
     statement::batch::{Batch, BatchType},
     statement::prepared_statement::PreparedStatement,
-    transport::errors::QueryError,
+    statement::PagingState,
     transport::session::TypedRowIter,
     statement::Consistency,
     query::Query,
+    QueryResult,
 };
-use std::sync::Arc;
+use std::{collections::HashMap, ops::ControlFlow, sync::Arc};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use crate::{
-    domain::memo::entity::Memo,
+    domain::{
+        api_key::entity::{ApiKey, ApiScope},
+        audit::entity::AuditEvent,
+        folder::entity::Folder,
+        identity::entity::OAuthIdentity,
+        memo::{
+            entity::{Memo, MemoFlag, MemoVersion},
+            repository::{MemoPage, MemoSort, SortOrder},
+        },
+        user::{entity::User, role::Role},
+    },
     error::{AppError, AppResult},
 };
 
@@ -31,224 +43,1791 @@ pub struct ScyllaDB {
 /// 頻繁に使用されるクエリを事前にコンパイルします。
 struct PreparedStatements {
     find_by_id: PreparedStatement,
+    find_memo_owner: PreparedStatement,
     find_all_by_user_id: PreparedStatement,
-    save_memo: PreparedStatement,
+    /// 並び順ごとのメモ一覧の先頭（`MEMO_LIST_ORDERS` と同じ順）
+    list_memos: Vec<PreparedStatement>,
+    /// 並び順ごとのメモ一覧の指定した行より後（`MEMO_LIST_ORDERS` と同じ順）
+    list_memos_after: Vec<PreparedStatement>,
+    find_memos_by_ids: PreparedStatement,
+    scan_memos: PreparedStatement,
+    insert_memo: PreparedStatement,
+    update_memo_content: PreparedStatement,
+    update_memo_metadata: PreparedStatement,
+    update_memo_pinned: PreparedStatement,
+    update_memo_archived: PreparedStatement,
+    archive_memo: PreparedStatement,
+    update_memo_favorite: PreparedStatement,
+    save_memo_owner: PreparedStatement,
     delete_memo: PreparedStatement,
-    exists: PreparedStatement,
+    delete_memo_owner: PreparedStatement,
+    save_memo_version: PreparedStatement,
+    find_memo_versions: PreparedStatement,
+    find_memo_version: PreparedStatement,
+    delete_memo_versions: PreparedStatement,
+    find_user_by_id: PreparedStatement,
+    find_user_id_by_email: PreparedStatement,
+    insert_user: PreparedStatement,
+    update_user: PreparedStatement,
+    claim_user_email: PreparedStatement,
+    delete_user: PreparedStatement,
+    release_user_email: PreparedStatement,
+    find_oauth_identity: PreparedStatement,
+    find_oauth_identities_by_user_id: PreparedStatement,
+    save_oauth_identity: PreparedStatement,
+    delete_oauth_identity: PreparedStatement,
+    find_api_key: PreparedStatement,
+    find_api_keys_by_user_id: PreparedStatement,
+    find_api_key_id_by_hash: PreparedStatement,
+    save_api_key: PreparedStatement,
+    save_api_key_hash: PreparedStatement,
+    touch_api_key: PreparedStatement,
+    delete_api_key: PreparedStatement,
+    delete_api_key_hash: PreparedStatement,
+    save_audit_event: PreparedStatement,
+    find_folder: PreparedStatement,
+    find_folders_by_user_id: PreparedStatement,
+    save_folder: PreparedStatement,
+    delete_folder: PreparedStatement,
 }
 
+/// APIキーの行（scopesはテキストのリストとして保存）
+type ApiKeyRow = (
+    Uuid, Uuid, String, String, String, Option<Vec<String>>,
+    DateTime<Utc>, Option<DateTime<Utc>>, Option<DateTime<Utc>>,
+);
+
+/// メモの行
+type MemoRow = (
+    Uuid, String, String, Option<Vec<String>>, Uuid, DateTime<Utc>, DateTime<Utc>, i32,
+    Option<DateTime<Utc>>, Option<Uuid>, Option<bool>, Option<bool>, Option<bool>,
+);
+
+const MEMO_COLUMNS: &str = "id, title, content, tags, user_id, created_at, updated_at, version, \
+    deleted_at, folder_id, pinned, archived, favorite";
+
+/// メモ一覧で指定できる並び順（カーソルにはこの配列内の位置を記録する）
+const MEMO_LIST_ORDERS: [(MemoSort, SortOrder); 6] = [
+    (MemoSort::UpdatedAt, SortOrder::Desc),
+    (MemoSort::UpdatedAt, SortOrder::Asc),
+    (MemoSort::CreatedAt, SortOrder::Desc),
+    (MemoSort::CreatedAt, SortOrder::Asc),
+    (MemoSort::Title, SortOrder::Desc),
+    (MemoSort::Title, SortOrder::Asc),
+];
+/// メモ一覧を読み込むときの1回の行数
+const MEMO_LIST_PAGE_SIZE: usize = 100;
+/// 1回の一覧取得で読み飛ばした行も含めて読み込む最大行数
+const MEMO_LIST_MAX_SCANNED_ROWS: usize = 1000;
+/// 全ユーザーのメモを順に読み込むときのページの行数
+const MEMO_SCAN_PAGE_SIZE: i32 = 500;
+/// IDを指定してメモを読み込むときの1回のIDの数
+const MEMO_IDS_PER_QUERY: usize = 100;
+
+/// メモ一覧のビューの行（本文を除く）
+type MemoListRow = (
+    Uuid, String, Option<Vec<String>>, Uuid, DateTime<Utc>, DateTime<Utc>, i32,
+    Option<DateTime<Utc>>, Option<Uuid>, Option<bool>, Option<bool>, Option<bool>,
+);
+
+/// メモ一覧のビューに含めるカラム（一覧の並び替えと絞り込みに使うもの）
+const MEMO_LIST_COLUMNS: &str = "id, title, tags, user_id, created_at, updated_at, version, \
+    deleted_at, folder_id, pinned, archived, favorite";
+
+/// メモ一覧の並び順のキーの値
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum MemoSortKey {
+    Time(DateTime<Utc>),
+    Title(String),
+}
+
+impl MemoSortKey {
+    fn of(memo: &Memo, sort: MemoSort) -> Self {
+        match sort {
+            MemoSort::UpdatedAt => Self::Time(memo.updated_at),
+            MemoSort::CreatedAt => Self::Time(memo.created_at),
+            MemoSort::Title => Self::Title(memo.title.clone()),
+        }
+    }
+}
+
+/// メモ一覧のカーソル
+///
+/// 前のページで最後に読んだ行の並び順のキーとIDを記録し、その次の行から読み込みます。
+/// 読み込みの合間にメモが追加・更新されても、読み終えた位置はずれません。
+#[derive(Debug, PartialEq, Eq)]
+struct MemoCursor {
+    order: u8,
+    key: MemoSortKey,
+    id: Uuid,
+}
+
+impl MemoCursor {
+    fn encode(&self) -> String {
+        let mut bytes = vec![self.order];
+        bytes.extend_from_slice(self.id.as_bytes());
+        match &self.key {
+            MemoSortKey::Time(at) => bytes.extend_from_slice(&at.timestamp_millis().to_be_bytes()),
+            MemoSortKey::Title(title) => bytes.extend_from_slice(title.as_bytes()),
+        }
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// カーソルを復元（別の並び順で発行されたカーソルは拒否）
+    fn decode(cursor: &str, order: u8) -> AppResult<Self> {
+        let invalid = || AppError::BadRequest("Invalid cursor".into());
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let (sort, _) = MEMO_LIST_ORDERS.get(order as usize).ok_or_else(invalid)?;
+        if bytes.len() < 17 || bytes[0] != order {
+            return Err(invalid());
+        }
+
+        let id = Uuid::from_slice(&bytes[1..17]).map_err(|_| invalid())?;
+        let key = &bytes[17..];
+        let key = match sort {
+            MemoSort::UpdatedAt | MemoSort::CreatedAt => {
+                let millis = i64::from_be_bytes(key.try_into().map_err(|_| invalid())?);
+                MemoSortKey::Time(DateTime::from_timestamp_millis(millis).ok_or_else(invalid)?)
+            }
+            MemoSort::Title => {
+                MemoSortKey::Title(String::from_utf8(key.to_vec()).map_err(|_| invalid())?)
+            }
+        };
+
+        Ok(Self { order, key, id })
+    }
+}
+
+/// メモ一覧の1ページ分の読み込みの状態
+struct MemoPageScan {
+    order: u8,
+    sort: MemoSort,
+    limit: usize,
+    /// 最後に読んだ行（次に読み込む位置）
+    position: Option<MemoCursor>,
+    listed: Vec<Memo>,
+    scanned: usize,
+}
+
+impl MemoPageScan {
+    fn new(order: u8, sort: MemoSort, position: Option<MemoCursor>, limit: usize) -> Self {
+        Self { order, sort, limit, position, listed: Vec::new(), scanned: 0 }
+    }
+
+    /// 1行を読み、ページが確定した場合は `true` を返す
+    ///
+    /// `filter` に合うメモが `limit` 件に達するか、読み込んだ行数が上限に達するとページを確定します。
+    fn read(&mut self, memo: Memo, filter: &(dyn Fn(&Memo) -> bool + Send + Sync)) -> bool {
+        self.scanned += 1;
+        self.position = Some(MemoCursor {
+            order: self.order,
+            key: MemoSortKey::of(&memo, self.sort),
+            id: memo.id,
+        });
+        if filter(&memo) {
+            self.listed.push(memo);
+        }
+
+        self.listed.len() >= self.limit || self.scanned >= MEMO_LIST_MAX_SCANNED_ROWS
+    }
+
+    fn next_cursor(&self) -> Option<String> {
+        self.position.as_ref().map(MemoCursor::encode)
+    }
+}
+
+/// フォルダの行
+type FolderRow = (Uuid, Uuid, Option<Uuid>, String, DateTime<Utc>, DateTime<Utc>);
+
+const FOLDER_COLUMNS: &str = "user_id, id, parent_id, name, created_at, updated_at";
+
+/// メモのバージョン履歴の行
+type MemoVersionRow = (Uuid, i32, String, String, Option<Vec<String>>, DateTime<Utc>);
+
+const MEMO_VERSION_COLUMNS: &str = "memo_id, version, title, content, tags, saved_at";
+
+const API_KEY_COLUMNS: &str =
+    "user_id, id, name, prefix, key_hash, scopes, created_at, expires_at, last_used_at";
+
 impl ScyllaDB {
     pub async fn new(uri: &str) -> AppResult<Self> {
         let session = SessionBuilder::new()
             .known_node(uri)
             .build()
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to connect to ScyllaDB: {}", e)))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to connect to ScyllaDB: {}", e)))?;
+
+        let session = Arc::new(session);
+        
+        // キースペースとテーブルの初期化
+        Self::initialize_schema(&session).await?;
+
+        // プリペアドステートメントの準備
+        let prepared_statements = Self::prepare_statements(&session).await?;
+
+        let db = Self { 
+            session,
+            prepared_statements,
+        };
+
+        // 所有者の索引を追加する前に作成されたメモを索引に登録
+        db.run_migration("backfill_memos_by_id", || db.backfill_memo_owners()).await?;
+
+        Ok(db)
+    }
+
+    /// 一度だけ実行するデータ移行
+    ///
+    /// 完了した移行は `schema_migrations` に記録し、次回以降の起動では実行しません。
+    /// 複数のインスタンスが同時に起動した場合は重複して実行されるため、冪等な処理に限ります。
+    async fn run_migration<F, Fut>(&self, name: &str, migration: F) -> AppResult<()>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = AppResult<()>>,
+    {
+        let result = self.session
+            .query_unpaged("SELECT name FROM memo_app.schema_migrations WHERE name = ?", (name,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch migration {}: {}", name, e)))?;
+        if result.rows.is_some_and(|rows| !rows.is_empty()) {
+            return Ok(());
+        }
+
+        migration().await?;
+
+        self.session
+            .query_unpaged(
+                "INSERT INTO memo_app.schema_migrations (name, applied_at) VALUES (?, ?)",
+                (name, Utc::now()),
+            )
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to record migration {}: {}", name, e)))?;
+
+        log::info!("Applied migration {}", name);
+        Ok(())
+    }
+
+    /// 既存のメモを所有者の索引に登録
+    async fn backfill_memo_owners(&self) -> AppResult<()> {
+        let mut paging_state = None;
+        loop {
+            let (memos, next_page) = self.scan_memos(paging_state).await?;
+            futures::future::try_join_all(memos.iter().map(|memo| async move {
+                self.session
+                    .execute_unpaged(&self.prepared_statements.save_memo_owner, (memo.id, memo.user_id))
+                    .await
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to backfill memo owner: {}", e)))
+            }))
+            .await?;
+
+            match next_page {
+                Some(next_page) => paging_state = Some(next_page),
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// プリペアドステートメントの初期化
+    async fn prepare_statements(session: &Session) -> AppResult<PreparedStatements> {
+        Ok(PreparedStatements {
+            find_by_id: session.prepare(
+                format!("SELECT {} FROM memo_app.memos WHERE user_id = ? AND id = ?", MEMO_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_by_id: {}", e)))?,
+
+            find_memo_owner: session.prepare("SELECT user_id FROM memo_app.memos_by_id WHERE id = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_memo_owner: {}", e)))?,
+            
+            find_all_by_user_id: session.prepare(
+                format!("SELECT {} FROM memo_app.memos WHERE user_id = ?", MEMO_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_all_by_user_id: {}", e)))?,
+            
+            list_memos: Self::prepare_memo_list_statements(session, false).await?,
+
+            list_memos_after: Self::prepare_memo_list_statements(session, true).await?,
+
+            find_memos_by_ids: session.prepare(
+                format!("SELECT {} FROM memo_app.memos WHERE user_id = ? AND id IN ?", MEMO_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_memos_by_ids: {}", e)))?,
+
+            scan_memos: {
+                let mut statement = session.prepare(
+                    format!("SELECT {} FROM memo_app.memos", MEMO_COLUMNS)
+                ).await
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to prepare scan_memos: {}", e)))?;
+                statement.set_page_size(MEMO_SCAN_PAGE_SIZE);
+                statement
+            },
+
+            insert_memo: session.prepare(
+                format!(
+                    "INSERT INTO memo_app.memos ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) IF NOT EXISTS",
+                    MEMO_COLUMNS
+                )
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare insert_memo: {}", e)))?,
+
+            update_memo_content: session.prepare(
+                "UPDATE memo_app.memos SET title = ?, content = ?, tags = ?, updated_at = ?, version = ?
+                WHERE user_id = ? AND id = ? IF version = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare update_memo_content: {}", e)))?,
+
+            update_memo_metadata: session.prepare(
+                "UPDATE memo_app.memos SET folder_id = ?, deleted_at = ? WHERE user_id = ? AND id = ? IF EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare update_memo_metadata: {}", e)))?,
+
+            update_memo_pinned: session.prepare(
+                "UPDATE memo_app.memos SET pinned = ?, updated_at = ? WHERE user_id = ? AND id = ? IF EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare update_memo_pinned: {}", e)))?,
+
+            update_memo_archived: session.prepare(
+                "UPDATE memo_app.memos SET archived = ?, updated_at = ? WHERE user_id = ? AND id = ? IF EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare update_memo_archived: {}", e)))?,
+
+            archive_memo: session.prepare(
+                "UPDATE memo_app.memos SET archived = true, pinned = false, updated_at = ?
+                WHERE user_id = ? AND id = ? IF EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare archive_memo: {}", e)))?,
+
+            update_memo_favorite: session.prepare(
+                "UPDATE memo_app.memos SET favorite = ?, updated_at = ? WHERE user_id = ? AND id = ? IF EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare update_memo_favorite: {}", e)))?,
+            
+            save_memo_owner: session.prepare("INSERT INTO memo_app.memos_by_id (id, user_id) VALUES (?, ?)").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_memo_owner: {}", e)))?,
+            
+            delete_memo: session.prepare("DELETE FROM memo_app.memos WHERE user_id = ? AND id = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_memo: {}", e)))?,
+
+            delete_memo_owner: session.prepare("DELETE FROM memo_app.memos_by_id WHERE id = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_memo_owner: {}", e)))?,
+
+            save_memo_version: session.prepare(
+                format!(
+                    "INSERT INTO memo_app.memo_versions ({}) VALUES (?, ?, ?, ?, ?, ?) IF NOT EXISTS",
+                    MEMO_VERSION_COLUMNS
+                )
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_memo_version: {}", e)))?,
+
+            find_memo_versions: session.prepare(
+                format!("SELECT {} FROM memo_app.memo_versions WHERE memo_id = ?", MEMO_VERSION_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_memo_versions: {}", e)))?,
+
+            find_memo_version: session.prepare(
+                format!("SELECT {} FROM memo_app.memo_versions WHERE memo_id = ? AND version = ?", MEMO_VERSION_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_memo_version: {}", e)))?,
+
+            delete_memo_versions: session.prepare("DELETE FROM memo_app.memo_versions WHERE memo_id = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_memo_versions: {}", e)))?,
+
+            find_user_by_id: session.prepare(
+                "SELECT id, email, name, password_hash, created_at, updated_at, 
+                        mfa_secret, mfa_enabled_at, mfa_recovery_codes, mfa_last_used_step, email_verified_at,
+                        deletion_requested_at, role, version 
+                 FROM memo_app.users WHERE id = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_user_by_id: {}", e)))?,
+
+            find_user_id_by_email: session.prepare("SELECT user_id FROM memo_app.users_by_email WHERE email = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_user_id_by_email: {}", e)))?,
+
+            // users と users_by_email への書き込みはすべて軽量トランザクションで行う
+            insert_user: session.prepare(
+                "INSERT INTO memo_app.users (id, email, name, password_hash, created_at, updated_at, 
+                                             mfa_secret, mfa_enabled_at, mfa_recovery_codes, mfa_last_used_step,
+                                             email_verified_at, deletion_requested_at, role, version) 
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) IF NOT EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare insert_user: {}", e)))?,
+
+            update_user: session.prepare(
+                "UPDATE memo_app.users SET name = ?, password_hash = ?, updated_at = ?, 
+                                           mfa_secret = ?, mfa_enabled_at = ?, mfa_recovery_codes = ?, 
+                                           mfa_last_used_step = ?, email_verified_at = ?, 
+                                           deletion_requested_at = ?, role = ?, version = ? 
+                 WHERE id = ? IF version = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare update_user: {}", e)))?,
+
+            claim_user_email: session.prepare(
+                "INSERT INTO memo_app.users_by_email (email, user_id) VALUES (?, ?) IF NOT EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare claim_user_email: {}", e)))?,
+
+            delete_user: session.prepare("DELETE FROM memo_app.users WHERE id = ? IF EXISTS").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_user: {}", e)))?,
+
+            release_user_email: session.prepare(
+                "DELETE FROM memo_app.users_by_email WHERE email = ? IF user_id = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare release_user_email: {}", e)))?,
+
+            find_oauth_identity: session.prepare(
+                "SELECT provider, subject, user_id, email, created_at FROM memo_app.oauth_identities 
+                 WHERE provider = ? AND subject = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_oauth_identity: {}", e)))?,
+
+            save_oauth_identity: session.prepare(
+                "INSERT INTO memo_app.oauth_identities (provider, subject, user_id, email, created_at) 
+                 VALUES (?, ?, ?, ?, ?)"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_oauth_identity: {}", e)))?,
+
+            find_oauth_identities_by_user_id: session.prepare(
+                "SELECT provider, subject, user_id, email, created_at FROM memo_app.oauth_identities 
+                 WHERE user_id = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_oauth_identities_by_user_id: {}", e)))?,
+
+            delete_oauth_identity: session.prepare(
+                "DELETE FROM memo_app.oauth_identities WHERE provider = ? AND subject = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_oauth_identity: {}", e)))?,
+
+            find_api_key: session.prepare(
+                format!("SELECT {} FROM memo_app.api_keys WHERE user_id = ? AND id = ?", API_KEY_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_api_key: {}", e)))?,
+
+            find_api_keys_by_user_id: session.prepare(
+                format!("SELECT {} FROM memo_app.api_keys WHERE user_id = ?", API_KEY_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_api_keys_by_user_id: {}", e)))?,
+
+            find_api_key_id_by_hash: session.prepare(
+                "SELECT user_id, id FROM memo_app.api_keys_by_hash WHERE key_hash = ?"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_api_key_id_by_hash: {}", e)))?,
+
+            save_api_key: session.prepare(
+                format!("INSERT INTO memo_app.api_keys ({}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)", API_KEY_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_api_key: {}", e)))?,
+
+            save_api_key_hash: session.prepare(
+                "INSERT INTO memo_app.api_keys_by_hash (key_hash, user_id, id) VALUES (?, ?, ?)"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_api_key_hash: {}", e)))?,
+
+            touch_api_key: session.prepare(
+                "UPDATE memo_app.api_keys SET last_used_at = ? WHERE user_id = ? AND id = ? IF EXISTS"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare touch_api_key: {}", e)))?,
+
+            delete_api_key: session.prepare("DELETE FROM memo_app.api_keys WHERE user_id = ? AND id = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_api_key: {}", e)))?,
+
+            delete_api_key_hash: session.prepare("DELETE FROM memo_app.api_keys_by_hash WHERE key_hash = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_api_key_hash: {}", e)))?,
+
+            save_audit_event: session.prepare(
+                "INSERT INTO memo_app.audit_events (subject, occurred_at, id, kind, user_id, ip, detail) 
+                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_audit_event: {}", e)))?,
+
+            find_folder: session.prepare(
+                format!("SELECT {} FROM memo_app.folders WHERE user_id = ? AND id = ?", FOLDER_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_folder: {}", e)))?,
+
+            find_folders_by_user_id: session.prepare(
+                format!("SELECT {} FROM memo_app.folders WHERE user_id = ?", FOLDER_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_folders_by_user_id: {}", e)))?,
+
+            save_folder: session.prepare(
+                format!("INSERT INTO memo_app.folders ({}) VALUES (?, ?, ?, ?, ?, ?)", FOLDER_COLUMNS)
+            ).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_folder: {}", e)))?,
+
+            delete_folder: session.prepare("DELETE FROM memo_app.folders WHERE user_id = ? AND id = ?").await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_folder: {}", e)))?,
+        })
+    }
+
+    /// 並び順ごとのメモ一覧のステートメント
+    ///
+    /// `after` の場合は並び順のキーとIDを受け取り、その行より後を読み込みます。
+    async fn prepare_memo_list_statements(session: &Session, after: bool) -> AppResult<Vec<PreparedStatement>> {
+        let mut statements = Vec::with_capacity(MEMO_LIST_ORDERS.len());
+
+        for (sort, order) in MEMO_LIST_ORDERS {
+            let column = Self::memo_sort_column(sort);
+            let (direction, comparison) = match order {
+                SortOrder::Asc => ("ASC", ">"),
+                SortOrder::Desc => ("DESC", "<"),
+            };
+            let condition = if after {
+                format!(" AND ({}, id) {} (?, ?)", column, comparison)
+            } else {
+                String::new()
+            };
+            let statement = session.prepare(format!(
+                "SELECT {} FROM memo_app.memo_list_by_{} WHERE user_id = ?{} ORDER BY {} {}, id {} LIMIT {}",
+                MEMO_LIST_COLUMNS, column, condition, column, direction, direction, MEMO_LIST_PAGE_SIZE
+            )).await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare list_memos: {}", e)))?;
+            statements.push(statement);
+        }
+
+        Ok(statements)
+    }
+
+    fn memo_sort_column(sort: MemoSort) -> &'static str {
+        match sort {
+            MemoSort::UpdatedAt => "updated_at",
+            MemoSort::CreatedAt => "created_at",
+            MemoSort::Title => "title",
+        }
+    }
+
+    /// データベーススキーマの初期化
+    async fn initialize_schema(session: &Session) -> AppResult<()> {
+        // キースペース作成のバッチ
+        let keyspace_batch = Batch::new(BatchType::Logged).add_statement(
+            Query::new(
+                "CREATE KEYSPACE IF NOT EXISTS memo_app 
+                WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1}"
+            )
+        );
+
+        session.batch(&keyspace_batch)
+            .consistency(Consistency::All)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create keyspace: {}", e)))?;
+
+        // テーブル作成のバッチ
+        let table_batch = Batch::new(BatchType::Logged).add_statement(
+            Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.memos (
+                    id uuid,
+                    title text,
+                    content text,
+                    tags list<text>,
+                    user_id uuid,
+                    created_at timestamp,
+                    updated_at timestamp,
+                    version int,
+                    PRIMARY KEY ((user_id), id)
+                ) WITH CLUSTERING ORDER BY (id DESC)"
+            )
+        )
+        .add_statement(
+            // IDからメモの所有者（パーティションキー）を引くための索引
+            Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.memos_by_id (
+                    id uuid PRIMARY KEY,
+                    user_id uuid
+                )"
+            )
+        )
+        .add_statement(
+            // 適用済みのデータ移行
+            Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.schema_migrations (
+                    name text PRIMARY KEY,
+                    applied_at timestamp
+                )"
+            )
+        )
+        .add_statement(
+            // 保存ごとのスナップショット（追記のみで書き換えない）
+            Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.memo_versions (
+                    memo_id uuid,
+                    version int,
+                    title text,
+                    content text,
+                    tags list<text>,
+                    saved_at timestamp,
+                    PRIMARY KEY ((memo_id), version)
+                ) WITH CLUSTERING ORDER BY (version DESC)"
+            )
+        );
+
+        session.batch(&table_batch)
+            .consistency(Consistency::All)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create table: {}", e)))?;
+
+        // ユーザーテーブルとメールアドレス索引テーブル
+        let user_table_batch = Batch::new(BatchType::Logged)
+            .add_statement(Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.users (
+                    id uuid PRIMARY KEY,
+                    email text,
+                    name text,
+                    password_hash text,
+                    created_at timestamp,
+                    updated_at timestamp
+                )"
+            ))
+            .add_statement(Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.users_by_email (
+                    email text PRIMARY KEY,
+                    user_id uuid
+                )"
+            ))
+            .add_statement(Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.oauth_identities (
+                    provider text,
+                    subject text,
+                    user_id uuid,
+                    email text,
+                    created_at timestamp,
+                    PRIMARY KEY ((provider, subject))
+                )"
+            ));
+
+        session.batch(&user_table_batch)
+            .consistency(Consistency::All)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create user tables: {}", e)))?;
+
+        // アカウント削除時にユーザーの紐付けを列挙するための索引
+        session
+            .query_unpaged(
+                "CREATE INDEX IF NOT EXISTS oauth_identities_user_id_idx ON memo_app.oauth_identities (user_id)",
+                &[],
+            )
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create oauth identity index: {}", e)))?;
+
+        // APIキーとハッシュ索引テーブル
+        let api_key_table_batch = Batch::new(BatchType::Logged)
+            .add_statement(Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.api_keys (
+                    user_id uuid,
+                    id uuid,
+                    name text,
+                    prefix text,
+                    key_hash text,
+                    scopes list<text>,
+                    created_at timestamp,
+                    expires_at timestamp,
+                    last_used_at timestamp,
+                    PRIMARY KEY ((user_id), id)
+                )"
+            ))
+            .add_statement(Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.api_keys_by_hash (
+                    key_hash text PRIMARY KEY,
+                    user_id uuid,
+                    id uuid
+                )"
+            ));
+
+        session.batch(&api_key_table_batch)
+            .consistency(Consistency::All)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create api key tables: {}", e)))?;
+
+        // フォルダ（ユーザー単位で階層全体を読み込む）
+        let folder_table_batch = Batch::new(BatchType::Logged).add_statement(
+            Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.folders (
+                    user_id uuid,
+                    id uuid,
+                    parent_id uuid,
+                    name text,
+                    created_at timestamp,
+                    updated_at timestamp,
+                    PRIMARY KEY ((user_id), id)
+                )"
+            )
+        );
+
+        session.batch(&folder_table_batch)
+            .consistency(Consistency::All)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create folder table: {}", e)))?;
+
+        // セキュリティ監査ログ
+        let audit_table_batch = Batch::new(BatchType::Logged).add_statement(
+            Query::new(
+                "CREATE TABLE IF NOT EXISTS memo_app.audit_events (
+                    subject text,
+                    occurred_at timestamp,
+                    id uuid,
+                    kind text,
+                    user_id uuid,
+                    ip text,
+                    detail text,
+                    PRIMARY KEY ((subject), occurred_at, id)
+                ) WITH CLUSTERING ORDER BY (occurred_at DESC, id ASC)"
+            )
+        );
+
+        session.batch(&audit_table_batch)
+            .consistency(Consistency::All)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create audit table: {}", e)))?;
+
+        // 既存テーブルへの列追加（多要素認証・メールアドレス確認・アカウント削除・ロール）
+        Self::add_columns(session, "memo_app.users", &[
+            ("mfa_secret", "text"),
+            ("mfa_enabled_at", "timestamp"),
+            ("mfa_recovery_codes", "list<text>"),
+            ("mfa_last_used_step", "bigint"),
+            ("email_verified_at", "timestamp"),
+            ("deletion_requested_at", "timestamp"),
+            ("role", "text"),
+            // 楽観ロック（導入前のユーザーは null）
+            ("version", "bigint"),
+        ]).await?;
+
+        // ゴミ箱・フォルダ
+        Self::add_columns(session, "memo_app.memos", &[
+            ("deleted_at", "timestamp"),
+            ("folder_id", "uuid"),
+        ]).await?;
+
+        // 固定・アーカイブ・お気に入り
+        Self::add_columns(session, "memo_app.memos", &[
+            ("pinned", "boolean"),
+            ("archived", "boolean"),
+            ("favorite", "boolean"),
+        ]).await?;
+
+        // 並び順ごとのメモ一覧（本文は一覧のページを確定した後に元のテーブルから読み込む）
+        for sort in [MemoSort::UpdatedAt, MemoSort::CreatedAt, MemoSort::Title] {
+            let column = Self::memo_sort_column(sort);
+
+            // 全カラムを複製していた以前のビュー
+            session
+                .query_unpaged(format!("DROP MATERIALIZED VIEW IF EXISTS memo_app.memos_by_{column}"), &[])
+                .await
+                .map_err(|e| {
+                    AppError::DatabaseError(format!("Failed to drop memos_by_{} view: {}", column, e))
+                })?;
+
+            let statement = format!(
+                "CREATE MATERIALIZED VIEW IF NOT EXISTS memo_app.memo_list_by_{column} AS
+                SELECT {MEMO_LIST_COLUMNS} FROM memo_app.memos
+                WHERE user_id IS NOT NULL AND {column} IS NOT NULL AND id IS NOT NULL
+                PRIMARY KEY ((user_id), {column}, id)
+                WITH CLUSTERING ORDER BY ({column} DESC, id DESC)"
+            );
+            session.query_unpaged(statement, &[]).await.map_err(|e| {
+                AppError::DatabaseError(format!("Failed to create memo_list_by_{} view: {}", column, e))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// 既存テーブルへのカラム追加
+    ///
+    /// `CREATE TABLE IF NOT EXISTS` は既存テーブルを変更しないため、
+    /// 後から追加したカラムはここで追加します。既に存在するカラムは無視します。
+    async fn add_columns(session: &Session, table: &str, columns: &[(&str, &str)]) -> AppResult<()> {
+        for (name, cql_type) in columns {
+            let statement = format!("ALTER TABLE {} ADD {} {}", table, name, cql_type);
+            if let Err(e) = session.query_unpaged(statement, &[]).await {
+                let message = e.to_string();
+                if !message.contains("conflicts with an existing column") && !message.contains("already exists") {
+                    return Err(AppError::DatabaseError(format!(
+                        "Failed to add column {} to {}: {}", name, table, e
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn memo_from_row(row: MemoRow) -> Memo {
+        let (
+            id, title, content, tags, user_id, created_at, updated_at, version, deleted_at, folder_id,
+            pinned, archived, favorite,
+        ) = row;
+
+        Memo {
+            id,
+            title,
+            content,
+            tags: tags.unwrap_or_default(),
+            user_id,
+            created_at,
+            updated_at,
+            version,
+            deleted_at,
+            folder_id,
+            // カラム追加前のメモは未設定
+            pinned: pinned.unwrap_or_default(),
+            archived: archived.unwrap_or_default(),
+            favorite: favorite.unwrap_or_default(),
+        }
+    }
+
+    /// メモ一覧のビューの行（本文は空）
+    fn memo_from_list_row(row: MemoListRow) -> Memo {
+        let (
+            id, title, tags, user_id, created_at, updated_at, version, deleted_at, folder_id,
+            pinned, archived, favorite,
+        ) = row;
+
+        Self::memo_from_row((
+            id, title, String::new(), tags, user_id, created_at, updated_at, version, deleted_at, folder_id,
+            pinned, archived, favorite,
+        ))
+    }
+
+    fn memo_version_from_row(row: MemoVersionRow) -> MemoVersion {
+        let (memo_id, version, title, content, tags, saved_at) = row;
+
+        MemoVersion {
+            memo_id,
+            version,
+            title,
+            content,
+            tags: tags.unwrap_or_default(),
+            saved_at,
+        }
+    }
+
+    /// メモの所有者を索引から取得
+    async fn find_memo_owner(&self, id: Uuid) -> AppResult<Option<Uuid>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_memo_owner, (id,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memo owner: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        rows.into_typed::<(Uuid,)>()
+            .next()
+            .transpose()
+            .map(|row| row.map(|(user_id,)| user_id))
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+    }
+
+    /// IDによるメモの検索
+    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Memo>> {
+        let Some(user_id) = self.find_memo_owner(id).await? else {
+            return Ok(None);
+        };
+
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_by_id, (user_id, id))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memo: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        rows.into_typed::<MemoRow>()
+            .next()
+            .transpose()
+            .map(|row| row.map(Self::memo_from_row))
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+    }
+
+    /// ユーザーIDによるメモの一覧取得
+    pub async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Memo>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_all_by_user_id, (user_id,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memos: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(Vec::new());
+        };
+
+        rows.into_typed::<MemoRow>()
+            .map(|row| {
+                row.map(Self::memo_from_row)
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+            })
+            .collect()
+    }
+
+    /// ユーザーのメモを並び順に沿って1ページ分取得
+    ///
+    /// `find_memo_summary_page` でページを確定させてから、ページに含めるメモだけを
+    /// 元のテーブルから本文も含めて読み込みます。
+    pub async fn find_memo_page(
+        &self,
+        user_id: Uuid,
+        sort: MemoSort,
+        order: SortOrder,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+    ) -> AppResult<MemoPage> {
+        let page = self
+            .find_memo_summary_page(user_id, sort, order, cursor, limit, filter)
+            .await?;
+        let ids: Vec<Uuid> = page.memos.iter().map(|memo| memo.id).collect();
+
+        Ok(MemoPage {
+            memos: self.find_listed_memos(user_id, &ids, filter).await?,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// ユーザーのメモを並び順に沿って1ページ分取得（本文は空）
+    ///
+    /// 本文を除いたビューを読み進めて `filter` に合うメモが `limit` 件に達するか、
+    /// 読み込んだ行数が上限に達した行を次のカーソルとして返します。
+    /// 行数の上限で止まった場合は `limit` 件に満たなくても `next_cursor` を返します。
+    /// `filter` には本文を空にしたメモを渡します。
+    pub async fn find_memo_summary_page(
+        &self,
+        user_id: Uuid,
+        sort: MemoSort,
+        order: SortOrder,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+    ) -> AppResult<MemoPage> {
+        let index = MEMO_LIST_ORDERS
+            .iter()
+            .position(|&key| key == (sort, order))
+            .ok_or_else(|| AppError::InternalServerError("Unsupported memo order".into()))?;
+
+        let position = match cursor {
+            Some(cursor) => Some(MemoCursor::decode(cursor, index as u8)?),
+            None => None,
+        };
+        let mut scan = MemoPageScan::new(index as u8, sort, position, limit);
+
+        let next_cursor = 'scan: loop {
+            let rows = self.find_memo_list_rows(index, user_id, scan.position.as_ref()).await?;
+            let page_len = rows.len();
+
+            for memo in rows {
+                if scan.read(memo, filter) {
+                    break 'scan scan.next_cursor();
+                }
+            }
+
+            if page_len < MEMO_LIST_PAGE_SIZE {
+                break None;
+            }
+        };
+
+        Ok(MemoPage {
+            memos: scan.listed,
+            next_cursor,
+        })
+    }
+
+    /// 一覧のページに含めるメモを本文も含めて取得
+    ///
+    /// 一覧の読み込み後に更新されたメモも除けるよう、`filter` に合うものだけを返します。
+    async fn find_listed_memos(
+        &self,
+        user_id: Uuid,
+        ids: &[Uuid],
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+    ) -> AppResult<Vec<Memo>> {
+        let mut memos = self.find_memos_by_ids(user_id, ids).await?;
+        memos.retain(|memo| filter(memo));
+        Ok(memos)
+    }
+
+    /// メモ一覧のビューを `after` の次の行から読み込む（本文は空）
+    async fn find_memo_list_rows(
+        &self,
+        index: usize,
+        user_id: Uuid,
+        after: Option<&MemoCursor>,
+    ) -> AppResult<Vec<Memo>> {
+        let statements = &self.prepared_statements;
+        let result = match after {
+            None => self.session.execute_unpaged(&statements.list_memos[index], (user_id,)).await,
+            Some(MemoCursor { key: MemoSortKey::Time(at), id, .. }) => {
+                self.session
+                    .execute_unpaged(&statements.list_memos_after[index], (user_id, *at, *id))
+                    .await
+            }
+            Some(MemoCursor { key: MemoSortKey::Title(title), id, .. }) => {
+                self.session
+                    .execute_unpaged(&statements.list_memos_after[index], (user_id, title, *id))
+                    .await
+            }
+        }
+        .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memos: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(Vec::new());
+        };
+
+        rows.into_typed::<MemoListRow>()
+            .map(|row| {
+                row.map(Self::memo_from_list_row)
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+            })
+            .collect()
+    }
+
+    /// ユーザーのメモのうち指定したIDのものを指定した順に取得（存在しないものは除く）
+    pub async fn find_memos_by_ids(&self, user_id: Uuid, ids: &[Uuid]) -> AppResult<Vec<Memo>> {
+        let mut memos: HashMap<Uuid, Memo> = HashMap::with_capacity(ids.len());
+
+        // `IN` に指定できるキーの数には上限がある
+        for chunk in ids.chunks(MEMO_IDS_PER_QUERY) {
+            let result = self.session
+                .execute_unpaged(&self.prepared_statements.find_memos_by_ids, (user_id, chunk.to_vec()))
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memos: {}", e)))?;
+
+            let Some(rows) = result.rows else {
+                continue;
+            };
+            for row in rows.into_typed::<MemoRow>() {
+                let memo = row
+                    .map(Self::memo_from_row)
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))?;
+                memos.insert(memo.id, memo);
+            }
+        }
+
+        Ok(ids.iter().filter_map(|id| memos.remove(id)).collect())
+    }
+
+    /// 全ユーザーのメモを1ページ分取得
+    ///
+    /// `paging_state` には前回返したページングステートを渡します。
+    /// 最後のページでは次のページングステートとして `None` を返します。
+    pub async fn scan_memos(
+        &self,
+        paging_state: Option<Arc<[u8]>>,
+    ) -> AppResult<(Vec<Memo>, Option<Arc<[u8]>>)> {
+        let paging_state = match paging_state {
+            Some(bytes) => PagingState::new_from_raw_bytes(bytes),
+            None => PagingState::start(),
+        };
+        let (result, paging_state_response) = self.session
+            .execute_single_page(&self.prepared_statements.scan_memos, (), paging_state)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to scan memos: {}", e)))?;
+
+        let memos = match result.rows {
+            Some(rows) => rows.into_typed::<MemoRow>()
+                .map(|row| {
+                    row.map(Self::memo_from_row)
+                        .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+                })
+                .collect::<AppResult<_>>()?,
+            None => Vec::new(),
+        };
+
+        let next_page = match paging_state_response.into_paging_control_flow() {
+            ControlFlow::Continue(next_page) => next_page.as_bytes_slice().cloned(),
+            ControlFlow::Break(()) => None,
+        };
+
+        Ok((memos, next_page))
+    }
+
+    /// 新しいメモの保存（所有者の索引と最初のバージョンの履歴も記録）
+    ///
+    /// 同じIDのメモが既に存在する場合は保存せずに `false` を返します。
+    pub async fn create_memo(&self, memo: &Memo) -> AppResult<bool> {
+        // 索引と履歴を先に書き、メモ本体が見つかれば必ず所有者を引けるようにする
+        self.session
+            .execute_unpaged(&self.prepared_statements.save_memo_owner, (memo.id, memo.user_id))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save memo owner: {}", e)))?;
+        self.save_memo_version(&MemoVersion::snapshot(memo)).await?;
+
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.insert_memo, (
+                memo.id,
+                &memo.title,
+                &memo.content,
+                &memo.tags,
+                memo.user_id,
+                memo.created_at,
+                memo.updated_at,
+                memo.version,
+                memo.deleted_at,
+                memo.folder_id,
+                memo.pinned,
+                memo.archived,
+                memo.favorite,
+            ))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save memo: {}", e)))?;
+
+        Ok(lwt_applied(&result))
+    }
+
+    /// メモの内容（タイトル・本文・タグ）の更新と、新しいバージョンの履歴の記録
+    ///
+    /// 保存済みのバージョンが `memo.version - 1`（編集の基になったバージョン）の場合のみ
+    /// 更新し、他の保存が先に行われていれば `false` を返します。
+    pub async fn update_memo_content(&self, memo: &Memo) -> AppResult<bool> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.update_memo_content, (
+                &memo.title,
+                &memo.content,
+                &memo.tags,
+                memo.updated_at,
+                memo.version,
+                memo.user_id,
+                memo.id,
+                memo.version - 1,
+            ))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update memo: {}", e)))?;
+
+        if !lwt_applied(&result) {
+            return Ok(false);
+        }
+
+        // バージョンを進められるのは1つの保存だけなので、履歴の同じバージョンは書き換わらない
+        self.save_memo_version(&MemoVersion::snapshot(memo)).await?;
+        Ok(true)
+    }
+
+    /// 所属フォルダとゴミ箱の状態の更新
+    ///
+    /// 内容やフラグのカラムには触れないため、並行した更新を上書きしません。
+    /// メモが削除済みの場合は `false` を返します。
+    pub async fn update_memo_metadata(&self, memo: &Memo) -> AppResult<bool> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.update_memo_metadata, (
+                memo.folder_id,
+                memo.deleted_at,
+                memo.user_id,
+                memo.id,
+            ))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update memo: {}", e)))?;
+
+        Ok(lwt_applied(&result))
+    }
+
+    /// 1つのフラグのカラムと更新日時のみの更新（アーカイブした場合は固定も解除）
+    ///
+    /// メモが削除済みの場合は `false` を返します。
+    pub async fn update_memo_flag(&self, memo: &Memo, flag: MemoFlag) -> AppResult<bool> {
+        let statements = &self.prepared_statements;
+        let (user_id, id, updated_at) = (memo.user_id, memo.id, memo.updated_at);
+        let result = match flag {
+            MemoFlag::Pinned => {
+                self.session
+                    .execute_unpaged(&statements.update_memo_pinned, (memo.pinned, updated_at, user_id, id))
+                    .await
+            }
+            MemoFlag::Archived if memo.archived => {
+                self.session
+                    .execute_unpaged(&statements.archive_memo, (updated_at, user_id, id))
+                    .await
+            }
+            MemoFlag::Archived => {
+                self.session
+                    .execute_unpaged(&statements.update_memo_archived, (false, updated_at, user_id, id))
+                    .await
+            }
+            MemoFlag::Favorite => {
+                self.session
+                    .execute_unpaged(&statements.update_memo_favorite, (memo.favorite, updated_at, user_id, id))
+                    .await
+            }
+        }
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update memo flag: {}", e)))?;
+
+        Ok(lwt_applied(&result))
+    }
+
+    async fn save_memo_version(&self, snapshot: &MemoVersion) -> AppResult<()> {
+        self.session
+            .execute_unpaged(&self.prepared_statements.save_memo_version, (
+                snapshot.memo_id,
+                snapshot.version,
+                &snapshot.title,
+                &snapshot.content,
+                &snapshot.tags,
+                snapshot.saved_at,
+            ))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save memo version: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// メモのバージョン履歴（新しい順）
+    pub async fn find_memo_versions(&self, memo_id: Uuid) -> AppResult<Vec<MemoVersion>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_memo_versions, (memo_id,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memo versions: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(Vec::new());
+        };
+
+        rows.into_typed::<MemoVersionRow>()
+            .map(|row| {
+                row.map(Self::memo_version_from_row)
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+            })
+            .collect()
+    }
+
+    /// 指定バージョンのスナップショットを取得
+    pub async fn find_memo_version(&self, memo_id: Uuid, version: i32) -> AppResult<Option<MemoVersion>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_memo_version, (memo_id, version))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memo version: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        rows.into_typed::<MemoVersionRow>()
+            .next()
+            .transpose()
+            .map(|row| row.map(Self::memo_version_from_row))
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+    }
+
+    /// メモの削除（存在しない場合は何もしない）
+    pub async fn delete(&self, id: Uuid) -> AppResult<()> {
+        match self.find_memo_owner(id).await? {
+            Some(user_id) => self.delete_owned(user_id, id).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 所有者を指定したメモの削除（バージョン履歴も削除）
+    pub async fn delete_owned(&self, user_id: Uuid, id: Uuid) -> AppResult<()> {
+        let batch = Batch::new(BatchType::Logged)
+            .add_statement(self.prepared_statements.delete_memo.bind((user_id, id)))
+            .add_statement(self.prepared_statements.delete_memo_owner.bind((id,)))
+            .add_statement(self.prepared_statements.delete_memo_versions.bind((id,)));
+
+        self.session
+            .batch(&batch)
+            .consistency(Consistency::Quorum)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete memo: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// メモの存在確認
+    pub async fn exists(&self, id: Uuid) -> AppResult<bool> {
+        Ok(self.find_memo_owner(id).await?.is_some())
+    }
+
+    /// IDによるユーザーの検索
+    pub async fn find_user_by_id(&self, id: Uuid) -> AppResult<Option<User>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_user_by_id, (id,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch user: {}", e)))?;
 
-        let session = Arc::new(session);
-        
-        // キースペースとテーブルの初期化
-        Self::initialize_schema(&session).await?;
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
 
-        // プリペアドステートメントの準備
-        let prepared_statements = Self::prepare_statements(&session).await?;
+        match rows.into_typed::<(
+            Uuid, String, String, String, DateTime<Utc>, DateTime<Utc>,
+            Option<String>, Option<DateTime<Utc>>, Option<Vec<String>>, Option<i64>,
+            Option<DateTime<Utc>>, Option<DateTime<Utc>>, Option<String>, Option<i64>
+        )>().next() {
+            Some(row) => {
+                let (
+                    id, email, name, password_hash, created_at, updated_at,
+                    mfa_secret, mfa_enabled_at, mfa_recovery_codes, mfa_last_used_step,
+                    email_verified_at, deletion_requested_at, role, version,
+                ) = row
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))?;
 
-        Ok(Self { 
-            session,
-            prepared_statements,
-        })
+                // ロール導入前のユーザーは一般メンバー
+                let role = role
+                    .map(|role| role.parse::<Role>().map_err(AppError::DatabaseError))
+                    .transpose()?
+                    .unwrap_or_default();
+
+                Ok(Some(User {
+                    id,
+                    email,
+                    name,
+                    password_hash,
+                    role,
+                    created_at,
+                    updated_at,
+                    email_verified_at,
+                    deletion_requested_at,
+                    mfa_secret,
+                    mfa_enabled_at,
+                    mfa_recovery_codes: mfa_recovery_codes.unwrap_or_default(),
+                    mfa_last_used_step,
+                    // バージョン導入前のユーザーは 0
+                    version: version.unwrap_or_default(),
+                }))
+            }
+            None => Ok(None),
+        }
     }
 
-    /// プリペアドステートメントの初期化
-    async fn prepare_statements(session: &Session) -> AppResult<PreparedStatements> {
-        Ok(PreparedStatements {
-            find_by_id: session.prepare("SELECT * FROM memo_app.memos WHERE id = ?").await
-                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_by_id: {}", e)))?,
-            
-            find_all_by_user_id: session.prepare("SELECT * FROM memo_app.memos WHERE user_id = ?").await
-                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare find_all_by_user_id: {}", e)))?,
-            
-            save_memo: session.prepare(
-                "INSERT INTO memo_app.memos (id, title, content, tags, user_id, created_at, updated_at, version) 
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
-            ).await
-                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare save_memo: {}", e)))?,
-            
-            delete_memo: session.prepare("DELETE FROM memo_app.memos WHERE id = ?").await
-                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare delete_memo: {}", e)))?,
-            
-            exists: session.prepare("SELECT id FROM memo_app.memos WHERE id = ?").await
-                .map_err(|e| AppError::DatabaseError(format!("Failed to prepare exists: {}", e)))?,
-        })
+    /// メールアドレスからユーザーIDを検索
+    pub async fn find_user_id_by_email(&self, email: &str) -> AppResult<Option<Uuid>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_user_id_by_email, (email,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch user by email: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        rows.into_typed::<(Uuid,)>()
+            .next()
+            .transpose()
+            .map(|row| row.map(|(user_id,)| user_id))
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
     }
 
-    /// データベーススキーマの初期化
-    async fn initialize_schema(session: &Session) -> AppResult<()> {
-        // キースペース作成のバッチ
-        let keyspace_batch = Batch::new(BatchType::Logged).add_statement(
-            Query::new(
-                "CREATE KEYSPACE IF NOT EXISTS memo_app 
-                WITH replication = {'class': 'SimpleStrategy', 'replication_factor': 1}"
-            )
-        );
+    /// ユーザーの更新
+    ///
+    /// 保存済みのバージョンが `user.version`（読み込んだ時点のバージョン）の場合のみ
+    /// `user.version + 1` として更新し、他の更新が先に行われていれば `false` を返します。
+    /// メールアドレスは変更しないため、索引テーブルには書き込みません。
+    pub async fn update_user(&self, user: &User) -> AppResult<bool> {
+        // バージョン導入前のユーザーの行は null
+        let expected_version = (user.version != 0).then_some(user.version);
 
-        session.batch(&keyspace_batch)
-            .consistency(Consistency::All)
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.update_user, (
+                &user.name,
+                &user.password_hash,
+                user.updated_at,
+                &user.mfa_secret,
+                user.mfa_enabled_at,
+                &user.mfa_recovery_codes,
+                user.mfa_last_used_step,
+                user.email_verified_at,
+                user.deletion_requested_at,
+                user.role.as_str(),
+                user.version + 1,
+                user.id,
+                expected_version,
+            ))
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to create keyspace: {}", e)))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update user: {}", e)))?;
 
-        // テーブル作成のバッチ
-        let table_batch = Batch::new(BatchType::Logged).add_statement(
-            Query::new(
-                "CREATE TABLE IF NOT EXISTS memo_app.memos (
-                    id uuid,
-                    title text,
-                    content text,
-                    tags list<text>,
-                    user_id uuid,
-                    created_at timestamp,
-                    updated_at timestamp,
-                    version int,
-                    PRIMARY KEY ((user_id), id)
-                ) WITH CLUSTERING ORDER BY (id DESC)"
-            )
-        );
+        Ok(lwt_applied(&result))
+    }
 
-        session.batch(&table_batch)
-            .consistency(Consistency::All)
+    /// 新規ユーザーの保存
+    ///
+    /// メールアドレスの索引を軽量トランザクションで確保してから保存します。
+    /// 既に登録済みのメールアドレスであれば保存せずに `false` を返します。
+    pub async fn create_user(&self, user: &User) -> AppResult<bool> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.claim_user_email, (&user.email, user.id))
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to create table: {}", e)))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to claim user email: {}", e)))?;
+
+        if !lwt_applied(&result) {
+            return Ok(false);
+        }
+
+        let inserted = self.session
+            .execute_unpaged(&self.prepared_statements.insert_user, (
+                user.id,
+                &user.email,
+                &user.name,
+                &user.password_hash,
+                user.created_at,
+                user.updated_at,
+                &user.mfa_secret,
+                user.mfa_enabled_at,
+                &user.mfa_recovery_codes,
+                user.mfa_last_used_step,
+                user.email_verified_at,
+                user.deletion_requested_at,
+                user.role.as_str(),
+                user.version,
+            ))
+            .await;
+
+        if let Err(e) = inserted {
+            // 確保したメールアドレスを解放し、再登録できるようにする
+            if let Err(release_error) = self.release_user_email(&user.email, user.id).await {
+                log::warn!("Failed to release email of user {}: {}", user.id, release_error);
+            }
+            return Err(AppError::DatabaseError(format!("Failed to save user: {}", e)));
+        }
+
+        Ok(true)
+    }
+
+    /// ユーザーの削除
+    ///
+    /// 索引テーブルを先に削除します。途中で失敗しても、ユーザーが残っていれば
+    /// 再実行でメールアドレスを読み直して続きから削除できます。
+    pub async fn delete_user(&self, id: Uuid, email: &str) -> AppResult<()> {
+        self.release_user_email(email, id).await?;
+
+        self.session
+            .execute_unpaged(&self.prepared_statements.delete_user, (id,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete user: {}", e)))?;
 
         Ok(())
     }
 
-    /// IDによるメモの検索
-    pub async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Memo>> {
-        let batch = Batch::new(BatchType::Logged)
-            .add_statement(self.prepared_statements.find_by_id.bind((id,)));
+    /// `user_id` が確保しているメールアドレスの索引を削除
+    async fn release_user_email(&self, email: &str, user_id: Uuid) -> AppResult<()> {
+        self.session
+            .execute_unpaged(&self.prepared_statements.release_user_email, (email, user_id))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to release user email: {}", e)))?;
 
+        Ok(())
+    }
+
+    /// 外部プロバイダーのアカウント紐付けを検索
+    pub async fn find_oauth_identity(&self, provider: &str, subject: &str) -> AppResult<Option<OAuthIdentity>> {
         let result = self.session
-            .batch(&batch)
-            .consistency(Consistency::One)
+            .execute_unpaged(&self.prepared_statements.find_oauth_identity, (provider, subject))
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memo: {}", e)))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch oauth identity: {}", e)))?;
 
-        if let Some(rows) = result.rows {
-            if let Some(row) = rows.into_typed::<(
-                Uuid, String, String, Vec<String>, Uuid, DateTime<Utc>, DateTime<Utc>, i32
-            )>().next() {
-                let (id, title, content, tags, user_id, created_at, updated_at, version) = row
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        match rows.into_typed::<(String, String, Uuid, Option<String>, DateTime<Utc>)>().next() {
+            Some(row) => {
+                let (provider, subject, user_id, email, created_at) = row
                     .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))?;
 
-                Ok(Some(Memo {
-                    id,
-                    title,
-                    content,
-                    tags,
+                Ok(Some(OAuthIdentity {
+                    provider,
+                    subject,
                     user_id,
+                    email,
                     created_at,
-                    updated_at,
-                    version,
                 }))
-            } else {
-                Ok(None)
             }
-        } else {
-            Ok(None)
+            None => Ok(None),
         }
     }
 
-    /// ユーザーIDによるメモの一覧取得
-    pub async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Memo>> {
-        let batch = Batch::new(BatchType::Logged)
-            .add_statement(self.prepared_statements.find_all_by_user_id.bind((user_id,)));
-
+    /// ユーザーに紐付いた外部プロバイダーのアカウントを列挙
+    pub async fn find_oauth_identities_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<OAuthIdentity>> {
         let result = self.session
-            .batch(&batch)
-            .consistency(Consistency::One)
+            .execute_unpaged(&self.prepared_statements.find_oauth_identities_by_user_id, (user_id,))
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch memos: {}", e)))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch oauth identities: {}", e)))?;
 
-        let mut memos = Vec::new();
+        let Some(rows) = result.rows else {
+            return Ok(Vec::new());
+        };
 
-        if let Some(rows) = result.rows {
-            for row in rows.into_typed::<(
-                Uuid, String, String, Vec<String>, Uuid, DateTime<Utc>, DateTime<Utc>, i32
-            )>() {
-                let (id, title, content, tags, user_id, created_at, updated_at, version) = row
+        rows.into_typed::<(String, String, Uuid, Option<String>, DateTime<Utc>)>()
+            .map(|row| {
+                let (provider, subject, user_id, email, created_at) = row
                     .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))?;
 
-                memos.push(Memo {
-                    id,
-                    title,
-                    content,
-                    tags,
+                Ok(OAuthIdentity {
+                    provider,
+                    subject,
                     user_id,
+                    email,
                     created_at,
-                    updated_at,
-                    version,
-                });
-            }
-        }
+                })
+            })
+            .collect()
+    }
 
-        Ok(memos)
+    /// 外部プロバイダーのアカウント紐付けを削除
+    pub async fn delete_oauth_identity(&self, provider: &str, subject: &str) -> AppResult<()> {
+        let batch = Batch::new(BatchType::Logged)
+            .add_statement(self.prepared_statements.delete_oauth_identity.bind((provider, subject)));
+
+        self.session
+            .batch(&batch)
+            .consistency(Consistency::Quorum)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete oauth identity: {}", e)))?;
+
+        Ok(())
     }
 
-    /// メモの保存
-    pub async fn save(&self, memo: &Memo) -> AppResult<()> {
+    /// 外部プロバイダーのアカウント紐付けを保存
+    pub async fn save_oauth_identity(&self, identity: &OAuthIdentity) -> AppResult<()> {
         let batch = Batch::new(BatchType::Logged)
-            .add_statement(self.prepared_statements.save_memo.bind((
-                memo.id,
-                &memo.title,
-                &memo.content,
-                &memo.tags,
-                memo.user_id,
-                memo.created_at,
-                memo.updated_at,
-                memo.version,
+            .add_statement(self.prepared_statements.save_oauth_identity.bind((
+                &identity.provider,
+                &identity.subject,
+                identity.user_id,
+                &identity.email,
+                identity.created_at,
             )));
 
-        match self.session
+        self.session
             .batch(&batch)
             .consistency(Consistency::Quorum)
             .await
-        {
-            Ok(_) => Ok(()),
-            Err(e) => match e {
-                QueryError::DbError(ref dbe) if dbe.message.contains("already exists") => {
-                    Err(AppError::Conflict("Memo already exists".into()))
-                }
-                e => Err(AppError::DatabaseError(format!("Failed to save memo: {}", e))),
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save oauth identity: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn api_key_from_row(row: ApiKeyRow) -> ApiKey {
+        let (user_id, id, name, prefix, key_hash, scopes, created_at, expires_at, last_used_at) = row;
+
+        ApiKey {
+            id,
+            user_id,
+            name,
+            prefix,
+            key_hash,
+            // 廃止されたスコープは読み飛ばす
+            scopes: scopes
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|scope| scope.parse::<ApiScope>().ok())
+                .collect(),
+            created_at,
+            expires_at,
+            last_used_at,
+        }
+    }
+
+    /// APIキーの取得
+    pub async fn find_api_key(&self, user_id: Uuid, id: Uuid) -> AppResult<Option<ApiKey>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_api_key, (user_id, id))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch api key: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        rows.into_typed::<ApiKeyRow>()
+            .next()
+            .transpose()
+            .map(|row| row.map(Self::api_key_from_row))
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+    }
+
+    /// ユーザーのAPIキー一覧
+    pub async fn find_api_keys_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<ApiKey>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_api_keys_by_user_id, (user_id,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch api keys: {}", e)))?;
+
+        let mut api_keys = Vec::new();
+
+        if let Some(rows) = result.rows {
+            for row in rows.into_typed::<ApiKeyRow>() {
+                let row = row
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))?;
+                api_keys.push(Self::api_key_from_row(row));
             }
         }
+
+        Ok(api_keys)
     }
 
-    /// メモの削除
-    pub async fn delete(&self, id: Uuid) -> AppResult<()> {
+    /// キーハッシュからAPIキーの所有者とIDを検索
+    pub async fn find_api_key_id_by_hash(&self, key_hash: &str) -> AppResult<Option<(Uuid, Uuid)>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_api_key_id_by_hash, (key_hash,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch api key by hash: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        rows.into_typed::<(Uuid, Uuid)>()
+            .next()
+            .transpose()
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+    }
+
+    /// APIキーの保存（ハッシュ索引も同時に更新）
+    pub async fn save_api_key(&self, api_key: &ApiKey) -> AppResult<()> {
+        let scopes: Vec<&str> = api_key.scopes.iter().map(ApiScope::as_str).collect();
+
         let batch = Batch::new(BatchType::Logged)
-            .add_statement(self.prepared_statements.delete_memo.bind((id,)));
+            .add_statement(self.prepared_statements.save_api_key.bind((
+                api_key.user_id,
+                api_key.id,
+                &api_key.name,
+                &api_key.prefix,
+                &api_key.key_hash,
+                scopes,
+                api_key.created_at,
+                api_key.expires_at,
+                api_key.last_used_at,
+            )))
+            .add_statement(self.prepared_statements.save_api_key_hash.bind((
+                &api_key.key_hash,
+                api_key.user_id,
+                api_key.id,
+            )));
 
         self.session
             .batch(&batch)
             .consistency(Consistency::Quorum)
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to delete memo: {}", e)))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save api key: {}", e)))?;
 
         Ok(())
     }
 
-    /// メモの存在確認
-    pub async fn exists(&self, id: Uuid) -> AppResult<bool> {
+    /// APIキーの最終使用日時のみを更新（削除済みのキーは書き戻さない）
+    pub async fn touch_api_key(&self, user_id: Uuid, id: Uuid, last_used_at: DateTime<Utc>) -> AppResult<()> {
+        self.session
+            .execute_unpaged(&self.prepared_statements.touch_api_key, (last_used_at, user_id, id))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update api key usage: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// APIキーの削除
+    pub async fn delete_api_key(&self, api_key: &ApiKey) -> AppResult<()> {
         let batch = Batch::new(BatchType::Logged)
-            .add_statement(self.prepared_statements.exists.bind((id,)));
+            .add_statement(self.prepared_statements.delete_api_key.bind((api_key.user_id, api_key.id)))
+            .add_statement(self.prepared_statements.delete_api_key_hash.bind((&api_key.key_hash,)));
+
+        self.session
+            .batch(&batch)
+            .consistency(Consistency::Quorum)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete api key: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn folder_from_row(row: FolderRow) -> Folder {
+        let (user_id, id, parent_id, name, created_at, updated_at) = row;
+
+        Folder {
+            id,
+            user_id,
+            parent_id,
+            name,
+            created_at,
+            updated_at,
+        }
+    }
+
+    /// フォルダの取得
+    pub async fn find_folder(&self, user_id: Uuid, id: Uuid) -> AppResult<Option<Folder>> {
+        let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_folder, (user_id, id))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch folder: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(None);
+        };
+
+        rows.into_typed::<FolderRow>()
+            .next()
+            .transpose()
+            .map(|row| row.map(Self::folder_from_row))
+            .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+    }
 
+    /// ユーザーのフォルダ一覧
+    pub async fn find_folders_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Folder>> {
         let result = self.session
+            .execute_unpaged(&self.prepared_statements.find_folders_by_user_id, (user_id,))
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to fetch folders: {}", e)))?;
+
+        let Some(rows) = result.rows else {
+            return Ok(Vec::new());
+        };
+
+        rows.into_typed::<FolderRow>()
+            .map(|row| {
+                row.map(Self::folder_from_row)
+                    .map_err(|e| AppError::DatabaseError(format!("Failed to parse row: {}", e)))
+            })
+            .collect()
+    }
+
+    /// フォルダの保存
+    pub async fn save_folder(&self, folder: &Folder) -> AppResult<()> {
+        let batch = Batch::new(BatchType::Logged)
+            .add_statement(self.prepared_statements.save_folder.bind((
+                folder.user_id,
+                folder.id,
+                folder.parent_id,
+                &folder.name,
+                folder.created_at,
+                folder.updated_at,
+            )));
+
+        self.session
             .batch(&batch)
-            .consistency(Consistency::One)
+            .consistency(Consistency::Quorum)
             .await
-            .map_err(|e| AppError::DatabaseError(format!("Failed to check memo existence: {}", e)))?;
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save folder: {}", e)))?;
 
-        Ok(result.rows.map_or(false, |rows| !rows.is_empty()))
+        Ok(())
+    }
+
+    /// フォルダの削除
+    pub async fn delete_folder(&self, folder: &Folder) -> AppResult<()> {
+        let batch = Batch::new(BatchType::Logged)
+            .add_statement(self.prepared_statements.delete_folder.bind((folder.user_id, folder.id)));
+
+        self.session
+            .batch(&batch)
+            .consistency(Consistency::Quorum)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to delete folder: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// 監査イベントの保存
+    pub async fn save_audit_event(&self, event: &AuditEvent) -> AppResult<()> {
+        let batch = Batch::new(BatchType::Logged)
+            .add_statement(self.prepared_statements.save_audit_event.bind((
+                &event.subject,
+                event.occurred_at,
+                event.id,
+                event.kind.as_str(),
+                event.user_id,
+                &event.ip,
+                &event.detail,
+            )));
+
+        self.session
+            .batch(&batch)
+            .consistency(Consistency::Quorum)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save audit event: {}", e)))?;
+
+        Ok(())
     }
 
     /// ヘルスチェック
@@ -266,11 +1845,81 @@ impl ScyllaDB {
     }
 }
 
+/// 軽量トランザクション（`IF ...`）の条件が満たされて書き込まれたか
+///
+/// 結果の先頭の列 `[applied]` を読みます。
+fn lwt_applied(result: &QueryResult) -> bool {
+    result
+        .rows
+        .as_ref()
+        .and_then(|rows| rows.first())
+        .and_then(|row| row.columns.first())
+        .and_then(|column| column.as_ref())
+        .and_then(|value| value.as_boolean())
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
 
+    #[test]
+    fn test_memo_cursor_round_trip() {
+        let cursor = MemoCursor {
+            order: 3,
+            key: MemoSortKey::Time(DateTime::from_timestamp_millis(1_700_000_000_123).unwrap()),
+            id: Uuid::new_v4(),
+        };
+        let encoded = cursor.encode();
+        assert_eq!(MemoCursor::decode(&encoded, 3).unwrap(), cursor);
+
+        let title = MemoCursor {
+            order: 5,
+            key: MemoSortKey::Title("議事録".to_string()),
+            id: Uuid::new_v4(),
+        };
+        assert_eq!(MemoCursor::decode(&title.encode(), 5).unwrap(), title);
+
+        // 別の並び順のカーソルや壊れたカーソルは拒否
+        assert!(matches!(MemoCursor::decode(&encoded, 0), Err(AppError::BadRequest(_))));
+        assert!(matches!(MemoCursor::decode(&encoded, 5), Err(AppError::BadRequest(_))));
+        assert!(matches!(MemoCursor::decode("not a cursor!", 3), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_memo_page_scan_stops_at_scanned_rows_limit() {
+        let user_id = Uuid::new_v4();
+        let memos: Vec<Memo> = (0..MEMO_LIST_MAX_SCANNED_ROWS)
+            .map(|i| Memo::new(format!("memo {}", i), String::new(), Vec::new(), user_id))
+            .collect();
+        let first_id = memos[0].id;
+        let last_id = memos[MEMO_LIST_MAX_SCANNED_ROWS - 1].id;
+        let only_first = move |memo: &Memo| memo.id == first_id;
+
+        let mut scan = MemoPageScan::new(0, MemoSort::UpdatedAt, None, 20);
+        let mut memos = memos.into_iter();
+        for memo in memos.by_ref().take(MEMO_LIST_MAX_SCANNED_ROWS - 1) {
+            assert!(!scan.read(memo, &only_first));
+        }
+
+        // 行数の上限で確定したページは `limit` 件に満たなくても続きのカーソルを返す
+        assert!(scan.read(memos.next().unwrap(), &only_first));
+        assert_eq!(scan.listed.len(), 1);
+        let cursor = MemoCursor::decode(&scan.next_cursor().unwrap(), 0).unwrap();
+        assert_eq!(cursor.id, last_id);
+    }
+
+    #[test]
+    fn test_memo_page_scan_stops_at_limit() {
+        let user_id = Uuid::new_v4();
+        let mut scan = MemoPageScan::new(0, MemoSort::UpdatedAt, None, 2);
+
+        assert!(!scan.read(Memo::new("a".into(), String::new(), Vec::new(), user_id), &|_: &Memo| true));
+        assert!(scan.read(Memo::new("b".into(), String::new(), Vec::new(), user_id), &|_: &Memo| true));
+        assert_eq!(scan.listed.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_save_and_find_memo() {
         let scylla = ScyllaDB::new("scylla://localhost:9042").await.unwrap();
@@ -284,9 +1933,15 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             version: 1,
+            deleted_at: None,
+            folder_id: None,
+            pinned: false,
+            archived: false,
+            favorite: false,
         };
 
-        scylla.save(&memo).await.unwrap();
+        assert!(scylla.create_memo(&memo).await.unwrap());
+        assert!(!scylla.create_memo(&memo).await.unwrap());
 
         let found = scylla.find_by_id(memo.id).await.unwrap().unwrap();
         assert_eq!(found.id, memo.id);
@@ -296,6 +1951,38 @@ mod tests {
         assert_eq!(found.user_id, memo.user_id);
         assert_eq!(found.version, memo.version);
 
+        // 同じバージョンを基にした2回目の更新は競合として拒否される
+        let mut updated = memo.clone();
+        updated.update(Some("Updated Memo".to_string()), None, None);
+        assert!(scylla.update_memo_content(&updated).await.unwrap());
+        assert!(!scylla.update_memo_content(&updated).await.unwrap());
+
         scylla.delete(memo.id).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_create_and_update_user() {
+        let scylla = ScyllaDB::new("scylla://localhost:9042").await.unwrap();
+
+        let mut user = User::new(
+            format!("{}@example.com", Uuid::new_v4()),
+            "Test User".to_string(),
+            "password_hash".to_string(),
+        );
+        assert!(scylla.create_user(&user).await.unwrap());
+        assert!(!scylla.create_user(&user).await.unwrap());
+
+        // 同じバージョンを基にした2回目の更新は競合として拒否される
+        user.update_profile(Some("Updated User".to_string()));
+        assert!(scylla.update_user(&user).await.unwrap());
+        assert!(!scylla.update_user(&user).await.unwrap());
+
+        let found = scylla.find_user_by_id(user.id).await.unwrap().unwrap();
+        assert_eq!(found.name, "Updated User");
+        assert_eq!(found.version, user.version + 1);
+
+        scylla.delete_user(user.id, &user.email).await.unwrap();
+        assert!(scylla.find_user_by_id(user.id).await.unwrap().is_none());
+        assert!(scylla.find_user_id_by_email(&user.email).await.unwrap().is_none());
+    }
 }
\ No newline at end of file
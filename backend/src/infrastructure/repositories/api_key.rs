@@ -0,0 +1,55 @@
+// src/infrastructure/repositories/api_key.rs
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::{
+    domain::api_key::{entity::ApiKey, repository::ApiKeyRepository},
+    error::AppResult,
+    infrastructure::persistence::scylla::ScyllaDB,
+};
+
+pub struct ApiKeyRepositoryImpl {
+    scylla: Arc<ScyllaDB>,
+}
+
+impl ApiKeyRepositoryImpl {
+    pub fn new(scylla: Arc<ScyllaDB>) -> Self {
+        Self { scylla }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for ApiKeyRepositoryImpl {
+    async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> AppResult<Option<ApiKey>> {
+        self.scylla.find_api_key(user_id, id).await
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> AppResult<Option<ApiKey>> {
+        match self.scylla.find_api_key_id_by_hash(key_hash).await? {
+            Some((user_id, id)) => self.scylla.find_api_key(user_id, id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<ApiKey>> {
+        self.scylla.find_api_keys_by_user_id(user_id).await
+    }
+
+    async fn save(&self, api_key: &ApiKey) -> AppResult<()> {
+        self.scylla.save_api_key(api_key).await
+    }
+
+    async fn touch(&self, api_key: &ApiKey) -> AppResult<()> {
+        let Some(last_used_at) = api_key.last_used_at else {
+            return Ok(());
+        };
+        self.scylla
+            .touch_api_key(api_key.user_id, api_key.id, last_used_at)
+            .await
+    }
+
+    async fn delete(&self, api_key: &ApiKey) -> AppResult<()> {
+        self.scylla.delete_api_key(api_key).await
+    }
+}
@@ -0,0 +1,26 @@
+// src/infrastructure/repositories/audit.rs
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use crate::{
+    domain::audit::{entity::AuditEvent, repository::AuditRepository},
+    error::AppResult,
+    infrastructure::persistence::scylla::ScyllaDB,
+};
+
+pub struct AuditRepositoryImpl {
+    scylla: Arc<ScyllaDB>,
+}
+
+impl AuditRepositoryImpl {
+    pub fn new(scylla: Arc<ScyllaDB>) -> Self {
+        Self { scylla }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for AuditRepositoryImpl {
+    async fn save(&self, event: &AuditEvent) -> AppResult<()> {
+        self.scylla.save_audit_event(event).await
+    }
+}
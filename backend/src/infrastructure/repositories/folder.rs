@@ -0,0 +1,39 @@
+// src/infrastructure/repositories/folder.rs
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::{
+    domain::folder::{entity::Folder, repository::FolderRepository},
+    error::AppResult,
+    infrastructure::persistence::scylla::ScyllaDB,
+};
+
+pub struct FolderRepositoryImpl {
+    scylla: Arc<ScyllaDB>,
+}
+
+impl FolderRepositoryImpl {
+    pub fn new(scylla: Arc<ScyllaDB>) -> Self {
+        Self { scylla }
+    }
+}
+
+#[async_trait]
+impl FolderRepository for FolderRepositoryImpl {
+    async fn find_by_id(&self, user_id: Uuid, id: Uuid) -> AppResult<Option<Folder>> {
+        self.scylla.find_folder(user_id, id).await
+    }
+
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Folder>> {
+        self.scylla.find_folders_by_user_id(user_id).await
+    }
+
+    async fn save(&self, folder: &Folder) -> AppResult<()> {
+        self.scylla.save_folder(folder).await
+    }
+
+    async fn delete(&self, folder: &Folder) -> AppResult<()> {
+        self.scylla.delete_folder(folder).await
+    }
+}
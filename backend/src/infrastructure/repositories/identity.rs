@@ -0,0 +1,41 @@
+// src/infrastructure/repositories/identity.rs
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::{
+    domain::identity::{entity::OAuthIdentity, repository::OAuthIdentityRepository},
+    error::AppResult,
+    infrastructure::persistence::scylla::ScyllaDB,
+};
+
+pub struct OAuthIdentityRepositoryImpl {
+    scylla: Arc<ScyllaDB>,
+}
+
+impl OAuthIdentityRepositoryImpl {
+    pub fn new(scylla: Arc<ScyllaDB>) -> Self {
+        Self { scylla }
+    }
+}
+
+#[async_trait]
+impl OAuthIdentityRepository for OAuthIdentityRepositoryImpl {
+    async fn find(&self, provider: &str, subject: &str) -> AppResult<Option<OAuthIdentity>> {
+        self.scylla.find_oauth_identity(provider, subject).await
+    }
+
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<OAuthIdentity>> {
+        self.scylla.find_oauth_identities_by_user_id(user_id).await
+    }
+
+    async fn save(&self, identity: &OAuthIdentity) -> AppResult<()> {
+        self.scylla.save_oauth_identity(identity).await
+    }
+
+    async fn delete(&self, identity: &OAuthIdentity) -> AppResult<()> {
+        self.scylla
+            .delete_oauth_identity(&identity.provider, &identity.subject)
+            .await
+    }
+}
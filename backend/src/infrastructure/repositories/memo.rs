@@ -4,8 +4,16 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use uuid::Uuid;
 use crate::{
-    domain::memo::{entity::Memo, repository::MemoRepository},
-    error::AppResult,
+    domain::{
+        folder::entity::MAX_DEPTH,
+        memo::{
+            entity::{Memo, MemoFlag, MemoVersion},
+            repository::{
+                MemoPage, MemoRepository, MemoSearch, MemoSearchPage, MemoSort, MemoSuggestions, SortOrder,
+            },
+        },
+    },
+    error::{AppError, AppResult},
     infrastructure::persistence::{
         scylla::ScyllaDB,
         redis::RedisCache,
@@ -37,6 +45,50 @@ impl MemoRepositoryImpl {
     fn cache_key(id: Uuid) -> String {
         format!("memo:{}", id)
     }
+
+    /// 最上位から所属フォルダまでのフォルダID
+    ///
+    /// ユーザーの全フォルダを読み込まず、所属フォルダから親を1つずつたどります。
+    async fn folder_path(&self, memo: &Memo) -> AppResult<Vec<Uuid>> {
+        let mut path = Vec::new();
+        let mut current = memo.folder_id;
+
+        // 親の循環があっても停止するよう、階層の上限までたどる
+        while let Some(folder_id) = current {
+            if path.len() > MAX_DEPTH {
+                break;
+            }
+            let Some(folder) = self.scylla.find_folder(memo.user_id, folder_id).await? else {
+                break;
+            };
+            path.push(folder.id);
+            current = folder.parent_id;
+        }
+
+        path.reverse();
+        Ok(path)
+    }
+
+    /// 所属フォルダのパスを求めて検索インデックスを更新
+    async fn index(&self, memo: &Memo) -> AppResult<()> {
+        let folder_path = self.folder_path(memo).await?;
+        self.reindex(memo, &folder_path).await
+    }
+
+    /// 保存後の内容で検索インデックスとキャッシュを更新
+    ///
+    /// 並行した保存が別のカラムを書き換えている可能性があるため、保存済みの行を読み直します。
+    async fn sync(&self, id: Uuid) -> AppResult<()> {
+        let cache_key = Self::cache_key(id);
+        match self.scylla.find_by_id(id).await? {
+            Some(memo) => {
+                self.index(&memo).await?;
+                self.redis.set(&cache_key, &memo, Some(CACHE_TTL)).await?;
+            }
+            None => self.redis.delete(&cache_key).await?,
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -62,12 +114,42 @@ impl MemoRepository for MemoRepositoryImpl {
         self.scylla.find_all_by_user_id(user_id).await
     }
 
-    async fn save(&self, memo: &Memo) -> AppResult<()> {
+    async fn find_page_by_user_id(
+        &self,
+        user_id: Uuid,
+        sort: MemoSort,
+        order: SortOrder,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+    ) -> AppResult<MemoPage> {
+        self.scylla
+            .find_memo_page(user_id, sort, order, cursor, limit, filter)
+            .await
+    }
+
+    async fn find_summary_page_by_user_id(
+        &self,
+        user_id: Uuid,
+        sort: MemoSort,
+        order: SortOrder,
+        cursor: Option<&str>,
+        limit: usize,
+        filter: &(dyn Fn(&Memo) -> bool + Send + Sync),
+    ) -> AppResult<MemoPage> {
+        self.scylla
+            .find_memo_summary_page(user_id, sort, order, cursor, limit, filter)
+            .await
+    }
+
+    async fn create(&self, memo: &Memo) -> AppResult<()> {
         // ScyllaDBに保存
-        self.scylla.save(memo).await?;
+        if !self.scylla.create_memo(memo).await? {
+            return Err(AppError::Conflict("Memo already exists".into()));
+        }
 
         // Elasticsearchにインデックス
-        self.elasticsearch.index_memo(memo).await?;
+        self.index(memo).await?;
 
         // キャッシュを更新
         let cache_key = Self::cache_key(memo.id);
@@ -76,6 +158,42 @@ impl MemoRepository for MemoRepositoryImpl {
         Ok(())
     }
 
+    async fn update(&self, memo: &Memo) -> AppResult<()> {
+        if !self.scylla.update_memo_content(memo).await? {
+            // 読み込んだ内容が古かった可能性があるため、キャッシュを読み直させる
+            self.redis.delete(&Self::cache_key(memo.id)).await?;
+            return Err(AppError::Conflict("Memo has been updated by another user".into()));
+        }
+
+        self.sync(memo.id).await
+    }
+
+    async fn update_metadata(&self, memo: &Memo) -> AppResult<()> {
+        if !self.scylla.update_memo_metadata(memo).await? {
+            self.redis.delete(&Self::cache_key(memo.id)).await?;
+            return Err(AppError::NotFound("Memo not found".into()));
+        }
+
+        self.sync(memo.id).await
+    }
+
+    async fn update_flag(&self, memo: &Memo, flag: MemoFlag) -> AppResult<()> {
+        if !self.scylla.update_memo_flag(memo, flag).await? {
+            self.redis.delete(&Self::cache_key(memo.id)).await?;
+            return Err(AppError::NotFound("Memo not found".into()));
+        }
+
+        self.sync(memo.id).await
+    }
+
+    async fn find_versions(&self, memo_id: Uuid) -> AppResult<Vec<MemoVersion>> {
+        self.scylla.find_memo_versions(memo_id).await
+    }
+
+    async fn find_version(&self, memo_id: Uuid, version: i32) -> AppResult<Option<MemoVersion>> {
+        self.scylla.find_memo_version(memo_id, version).await
+    }
+
     async fn delete(&self, id: Uuid) -> AppResult<()> {
         // ScyllaDBから削除
         self.scylla.delete(id).await?;
@@ -89,8 +207,38 @@ impl MemoRepository for MemoRepositoryImpl {
         Ok(())
     }
 
-    async fn search(&self, query: &str, tag: Option<String>, user_id: Uuid) -> AppResult<Vec<Memo>> {
-        self.elasticsearch.search_memos(query, tag, user_id).await
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> AppResult<()> {
+        // 検索インデックスはユーザー単位でまとめて削除
+        self.elasticsearch.delete_memos_by_user_id(user_id).await?;
+
+        // 正本であるScyllaDBは最後に削除し、中断しても再実行で残りのメモを見つけられるようにする
+        for memo in self.scylla.find_all_by_user_id(user_id).await? {
+            self.redis.delete(&Self::cache_key(memo.id)).await?;
+            self.scylla.delete_owned(user_id, memo.id).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn reindex(&self, memo: &Memo, folder_path: &[Uuid]) -> AppResult<()> {
+        // ゴミ箱のメモは検索対象から外す
+        if memo.is_trashed() {
+            return self.elasticsearch.delete_memo(memo.id).await;
+        }
+
+        self.elasticsearch.index_memo(memo, folder_path).await
+    }
+
+    async fn search(&self, search: &MemoSearch) -> AppResult<MemoSearchPage> {
+        self.elasticsearch.search_memos(search).await
+    }
+
+    async fn suggest(&self, user_id: Uuid, prefix: &str, limit: usize) -> AppResult<MemoSuggestions> {
+        self.elasticsearch.suggest(user_id, prefix, limit).await
+    }
+
+    async fn count_tags(&self, user_id: Uuid) -> AppResult<Vec<(String, u64)>> {
+        self.elasticsearch.tag_counts(user_id).await
     }
 
     async fn exists(&self, id: Uuid) -> AppResult<bool> {
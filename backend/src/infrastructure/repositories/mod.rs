@@ -1 +1,7 @@
+pub mod api_key;
+pub mod audit;
+pub mod folder;
+pub mod identity;
 pub mod memo;
+pub mod session;
+pub mod user;
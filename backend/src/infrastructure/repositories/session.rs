@@ -0,0 +1,122 @@
+// src/infrastructure/repositories/session.rs
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+use crate::{
+    domain::session::{entity::Session, repository::SessionRepository},
+    error::AppResult,
+    infrastructure::persistence::redis::RedisCache,
+};
+
+/// Redisを使用したセッションストア
+///
+/// `session:{id}` にセッション本体を、`user_sessions:{user_id}` に
+/// ユーザーごとのセッションID集合を保持します。
+pub struct SessionRepositoryImpl {
+    redis: Arc<RedisCache>,
+}
+
+impl SessionRepositoryImpl {
+    pub fn new(redis: Arc<RedisCache>) -> Self {
+        Self { redis }
+    }
+
+    fn session_key(id: Uuid) -> String {
+        format!("session:{}", id)
+    }
+
+    fn user_sessions_key(user_id: Uuid) -> String {
+        format!("user_sessions:{}", user_id)
+    }
+
+    /// セッションの残りの有効期間
+    fn ttl(session: &Session) -> std::time::Duration {
+        (session.expires_at - Utc::now())
+            .to_std()
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SessionRepositoryImpl {
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<Session>> {
+        self.redis.get::<Session>(&Self::session_key(id)).await
+    }
+
+    async fn find_all_by_user_id(&self, user_id: Uuid) -> AppResult<Vec<Session>> {
+        let index_key = Self::user_sessions_key(user_id);
+        let mut sessions = Vec::new();
+
+        for member in self.redis.set_members(&index_key).await? {
+            let Ok(id) = Uuid::parse_str(&member) else {
+                self.redis.remove_from_set(&index_key, &member).await?;
+                continue;
+            };
+
+            // 期限切れで消えたセッションは索引からも除去
+            match self.find_by_id(id).await? {
+                Some(session) => sessions.push(session),
+                None => self.redis.remove_from_set(&index_key, &member).await?,
+            }
+        }
+
+        Ok(sessions)
+    }
+
+    async fn save(&self, session: &Session) -> AppResult<()> {
+        let ttl = Self::ttl(session);
+
+        self.redis
+            .set(&Self::session_key(session.id), session, Some(ttl))
+            .await?;
+
+        // 索引は最も遅く期限切れになるセッションまで残す
+        self.redis
+            .add_to_set_extending_ttl(&Self::user_sessions_key(session.user_id), &session.id.to_string(), ttl)
+            .await
+    }
+
+    async fn save_if_unchanged(&self, session: &Session, refresh_token_hash: &str) -> AppResult<bool> {
+        let ttl = Self::ttl(session);
+
+        if !self
+            .redis
+            .compare_and_set(
+                &Self::session_key(session.id),
+                "refresh_token_hash",
+                refresh_token_hash,
+                session,
+                ttl,
+            )
+            .await?
+        {
+            return Ok(false);
+        }
+
+        self.redis
+            .add_to_set_extending_ttl(&Self::user_sessions_key(session.user_id), &session.id.to_string(), ttl)
+            .await?;
+        Ok(true)
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> AppResult<()> {
+        self.redis.delete(&Self::session_key(id)).await?;
+        self.redis
+            .remove_from_set(&Self::user_sessions_key(user_id), &id.to_string())
+            .await
+    }
+
+    async fn delete_all_by_user_id(&self, user_id: Uuid) -> AppResult<()> {
+        let index_key = Self::user_sessions_key(user_id);
+
+        for member in self.redis.set_members(&index_key).await? {
+            if let Ok(id) = Uuid::parse_str(&member) {
+                self.redis.delete(&Self::session_key(id)).await?;
+            }
+        }
+
+        self.redis.delete(&index_key).await
+    }
+}
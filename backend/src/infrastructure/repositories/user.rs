@@ -0,0 +1,76 @@
+// src/infrastructure/repositories/user.rs
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::{
+    domain::user::{entity::User, repository::UserRepository},
+    error::{AppError, AppResult},
+    infrastructure::persistence::scylla::ScyllaDB,
+};
+
+pub struct UserRepositoryImpl {
+    scylla: Arc<ScyllaDB>,
+}
+
+impl UserRepositoryImpl {
+    pub fn new(scylla: Arc<ScyllaDB>) -> Self {
+        Self { scylla }
+    }
+
+    /// メールアドレスの正規化（索引テーブルのキー）
+    fn normalize_email(email: &str) -> String {
+        email.trim().to_lowercase()
+    }
+}
+
+#[async_trait]
+impl UserRepository for UserRepositoryImpl {
+    async fn find_by_id(&self, id: Uuid) -> AppResult<Option<User>> {
+        self.scylla.find_user_by_id(id).await
+    }
+
+    async fn find_by_email(&self, email: &str) -> AppResult<Option<User>> {
+        match self.scylla.find_user_id_by_email(&Self::normalize_email(email)).await? {
+            Some(user_id) => self.scylla.find_user_by_id(user_id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn create(&self, user: &User) -> AppResult<()> {
+        let mut user = user.clone();
+        user.email = Self::normalize_email(&user.email);
+        if !self.scylla.create_user(&user).await? {
+            return Err(AppError::Conflict("Email is already registered".into()));
+        }
+        Ok(())
+    }
+
+    async fn save(&self, user: &mut User) -> AppResult<()> {
+        if !self.scylla.update_user(user).await? {
+            return Err(AppError::Conflict("User has been updated by another request".into()));
+        }
+        user.version += 1;
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid) -> AppResult<()> {
+        // 索引テーブルの削除にメールアドレスが必要
+        if let Some(user) = self.scylla.find_user_by_id(id).await? {
+            self.scylla.delete_user(user.id, &user.email).await?;
+        }
+        Ok(())
+    }
+
+    async fn exists(&self, id: Uuid) -> AppResult<bool> {
+        Ok(self.scylla.find_user_by_id(id).await?.is_some())
+    }
+
+    async fn exists_by_email(&self, email: &str) -> AppResult<bool> {
+        Ok(self
+            .scylla
+            .find_user_id_by_email(&Self::normalize_email(email))
+            .await?
+            .is_some())
+    }
+}
@@ -0,0 +1,119 @@
+// src/interfaces/auth.rs
+
+use std::net::IpAddr;
+use actix_web::{dev::Payload, http::header, web::Data, FromRequest, HttpRequest};
+use futures::future::{ready, FutureExt, LocalBoxFuture};
+use crate::{
+    application::{
+        api_key::service::ApiKeyService,
+        auth::AuthenticatedUser,
+        session::{dto::ClientInfo, service::SessionService},
+    },
+    error::{AppError, AppResult},
+    infrastructure::auth::jwt::JwtService,
+};
+
+/// 前段のリバースプロキシの設定
+pub struct ProxyConfig {
+    /// `X-Forwarded-For` などの転送ヘッダーを信頼する接続元
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl ProxyConfig {
+    /// 環境変数から設定を読み込む
+    ///
+    /// * `TRUSTED_PROXIES` - 転送ヘッダーを信頼するプロキシのIPアドレス（カンマ区切り、既定は空）
+    pub fn from_env() -> AppResult<Self> {
+        let trusted_proxies = std::env::var("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value.parse::<IpAddr>().map_err(|_| {
+                    AppError::InternalServerError(format!("Invalid TRUSTED_PROXIES entry: {}", value))
+                })
+            })
+            .collect::<AppResult<_>>()?;
+
+        Ok(Self { trusted_proxies })
+    }
+}
+
+/// `Authorization: Bearer <token>` ヘッダーからトークンを取り出す
+fn bearer_token(req: &HttpRequest) -> Result<&str, AppError> {
+    let value = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .ok_or_else(|| AppError::Unauthorized("Missing Authorization header".into()))?
+        .to_str()
+        .map_err(|_| AppError::Unauthorized("Invalid Authorization header".into()))?;
+
+    value
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| AppError::Unauthorized("Expected Bearer token".into()))
+}
+
+/// リクエストから端末情報（User-Agent、クライアントIP）を取得
+pub fn client_info(req: &HttpRequest) -> ClientInfo {
+    ClientInfo {
+        device: req
+            .headers()
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from),
+        ip: client_ip(req),
+    }
+}
+
+/// クライアントIP
+///
+/// 転送ヘッダーはクライアントが自由に設定できるため、接続元が
+/// 信頼するプロキシの場合だけ使い、それ以外は接続元のアドレスを使います。
+fn client_ip(req: &HttpRequest) -> Option<String> {
+    let peer = req.peer_addr()?.ip();
+    let trusted = req
+        .app_data::<Data<ProxyConfig>>()
+        .is_some_and(|config| config.trusted_proxies.contains(&peer));
+
+    if trusted {
+        if let Some(forwarded) = req.connection_info().realip_remote_addr() {
+            return Some(forwarded.to_string());
+        }
+    }
+    Some(peer.to_string())
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let (Some(jwt), Some(sessions), Some(api_keys)) = (
+            req.app_data::<Data<JwtService>>().cloned(),
+            req.app_data::<Data<SessionService>>().cloned(),
+            req.app_data::<Data<ApiKeyService>>().cloned(),
+        ) else {
+            return ready(Err(AppError::InternalServerError(
+                "Authentication services are not configured".into(),
+            )))
+            .boxed_local();
+        };
+
+        let token = match bearer_token(req) {
+            Ok(token) => token.to_string(),
+            Err(e) => return ready(Err(e)).boxed_local(),
+        };
+
+        // APIキー（mk_...）とJWTの両方を受け付ける
+        if ApiKeyService::is_api_key(&token) {
+            return async move { api_keys.authenticate(&token).await }.boxed_local();
+        }
+
+        // 署名検証は同期的に行い、セッションの失効確認のみ非同期で行う
+        let subject = jwt.authenticate(&token);
+        async move { sessions.authenticate(subject?).await }.boxed_local()
+    }
+}
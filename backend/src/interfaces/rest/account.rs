@@ -0,0 +1,35 @@
+use actix_web::{
+    http::header,
+    web::{Data, Json},
+    HttpResponse,
+};
+use crate::{
+    application::account::{dto::DeleteAccountDto, service::AccountService},
+    application::auth::AuthenticatedUser,
+    error::AppResult,
+};
+
+// 個人データのエクスポートエンドポイント
+pub async fn export_data(
+    service: Data<AccountService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let export = service.export_data(&user).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", export.filename),
+        ))
+        .body(export.content))
+}
+
+// アカウント削除エンドポイント
+pub async fn delete_account(
+    service: Data<AccountService>,
+    user: AuthenticatedUser,
+    payload: Json<DeleteAccountDto>,
+) -> AppResult<HttpResponse> {
+    service.delete_account(&user, payload.into_inner()).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
@@ -0,0 +1,77 @@
+use actix_web::{
+    web::{Data, Json, Path, Query},
+    HttpResponse,
+};
+use uuid::Uuid;
+use crate::{
+    application::admin::{dto::ChangeRoleDto, service::AdminService},
+    application::auth::AuthenticatedUser,
+    application::memo::{
+        dto::{MemoListFilter, MemoListQuery},
+        service::MemoService,
+    },
+    error::AppResult,
+};
+
+// ユーザー取得エンドポイント
+pub async fn get_user(
+    service: Data<AdminService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let profile = service.get_user(&user, id.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+// ロール変更エンドポイント
+pub async fn change_role(
+    service: Data<AdminService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    payload: Json<ChangeRoleDto>,
+) -> AppResult<HttpResponse> {
+    let profile = service
+        .change_role(&user, id.into_inner(), payload.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+// MFAリセットエンドポイント
+pub async fn reset_mfa(
+    service: Data<AdminService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    service.reset_mfa(&user, id.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// ユーザーのメモ一覧エンドポイント
+pub async fn list_user_memos(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    query: Query<MemoListQuery>,
+    filter: Query<MemoListFilter>,
+) -> AppResult<HttpResponse> {
+    let memos = service.get_memos_of_user(id.into_inner(), &query, &filter, &user).await?;
+    Ok(HttpResponse::Ok().json(memos))
+}
+
+// 再インデックス開始エンドポイント
+pub async fn start_reindex(
+    service: Data<AdminService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let job = service.start_reindex(&user).await?;
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+// 再インデックスの進捗取得エンドポイント
+pub async fn get_reindex(
+    service: Data<AdminService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let job = service.get_reindex(&user).await?;
+    Ok(HttpResponse::Ok().json(job))
+}
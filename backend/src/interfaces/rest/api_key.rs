@@ -0,0 +1,39 @@
+use actix_web::{
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use uuid::Uuid;
+use crate::{
+    application::auth::AuthenticatedUser,
+    application::api_key::{dto::CreateApiKeyDto, service::ApiKeyService},
+    error::AppResult,
+};
+
+// APIキー発行エンドポイント
+pub async fn create_api_key(
+    service: Data<ApiKeyService>,
+    user: AuthenticatedUser,
+    payload: Json<CreateApiKeyDto>,
+) -> AppResult<HttpResponse> {
+    let api_key = service.create_api_key(&user, payload.into_inner()).await?;
+    Ok(HttpResponse::Created().json(api_key))
+}
+
+// APIキー一覧取得エンドポイント
+pub async fn list_api_keys(
+    service: Data<ApiKeyService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let api_keys = service.list_api_keys(&user).await?;
+    Ok(HttpResponse::Ok().json(api_keys))
+}
+
+// APIキー失効エンドポイント
+pub async fn revoke_api_key(
+    service: Data<ApiKeyService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    service.revoke_api_key(&user, id.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
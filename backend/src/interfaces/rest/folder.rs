@@ -0,0 +1,101 @@
+use actix_web::{
+    web::{Data, Json, Path, Query},
+    HttpResponse,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+use crate::{
+    application::auth::AuthenticatedUser,
+    application::folder::{
+        dto::{CreateFolderDto, MoveFolderDto, RenameFolderDto},
+        service::FolderService,
+    },
+    error::AppResult,
+    interfaces::rest::memo::PageParams,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteFolderParams {
+    /// 配下のフォルダとメモもまとめて削除する
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+// フォルダ作成エンドポイント
+pub async fn create_folder(
+    service: Data<FolderService>,
+    user: AuthenticatedUser,
+    payload: Json<CreateFolderDto>,
+) -> AppResult<HttpResponse> {
+    let folder = service.create_folder(payload.into_inner(), &user).await?;
+    Ok(HttpResponse::Created().json(folder))
+}
+
+// フォルダ一覧取得エンドポイント
+pub async fn list_folders(
+    service: Data<FolderService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let folders = service.list_folders(&user).await?;
+    Ok(HttpResponse::Ok().json(folders))
+}
+
+// フォルダ取得エンドポイント
+pub async fn get_folder(
+    service: Data<FolderService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let folder = service.get_folder(id.into_inner(), &user).await?;
+    Ok(HttpResponse::Ok().json(folder))
+}
+
+// フォルダ名変更エンドポイント
+pub async fn rename_folder(
+    service: Data<FolderService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    payload: Json<RenameFolderDto>,
+) -> AppResult<HttpResponse> {
+    let folder = service
+        .rename_folder(id.into_inner(), payload.into_inner(), &user)
+        .await?;
+    Ok(HttpResponse::Ok().json(folder))
+}
+
+// フォルダ移動エンドポイント
+pub async fn move_folder(
+    service: Data<FolderService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    payload: Json<MoveFolderDto>,
+) -> AppResult<HttpResponse> {
+    let folder = service
+        .move_folder(id.into_inner(), payload.into_inner(), &user)
+        .await?;
+    Ok(HttpResponse::Ok().json(folder))
+}
+
+// フォルダ削除エンドポイント
+pub async fn delete_folder(
+    service: Data<FolderService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    params: Query<DeleteFolderParams>,
+) -> AppResult<HttpResponse> {
+    service.delete_folder(id.into_inner(), params.cascade, &user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// フォルダ内のメモ一覧取得エンドポイント
+pub async fn list_folder_memos(
+    service: Data<FolderService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    params: Query<PageParams>,
+) -> AppResult<HttpResponse> {
+    let memos = service
+        .list_memos(id.into_inner(), params.page, params.limit, &user)
+        .await?;
+    Ok(HttpResponse::Ok().json(memos))
+}
@@ -5,23 +5,50 @@ use actix_web::{
 use serde::Deserialize;
 use uuid::Uuid;
 use crate::{
+    application::auth::AuthenticatedUser,
     application::memo::{
-        dto::{CreateMemoDto, UpdateMemoDto},
+        dto::{
+            CreateMemoDto, MemoListFilter, MemoListQuery, MoveMemoDto, SearchMemosQuery, SuggestQuery, UpdateMemoDto,
+        },
         service::MemoService,
     },
+    domain::memo::entity::MemoFlag,
     error::AppResult,
 };
 
 #[derive(Debug, Deserialize)]
-pub struct SearchParams {
-    pub query: Option<String>,
-    pub tag: Option<String>,
+pub struct PageParams {
     #[serde(default = "default_page")]
     pub page: usize,
     #[serde(default = "default_limit")]
     pub limit: usize,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct DeleteParams {
+    #[serde(default)]
+    pub permanent: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiffParams {
+    pub from: i32,
+    pub to: i32,
+    #[serde(default)]
+    pub format: DiffFormat,
+}
+
+/// 差分の出力形式
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffFormat {
+    /// ハンクを構造化したJSON
+    #[default]
+    Json,
+    /// `text/x-diff` 形式の unified diff
+    Text,
+}
+
 fn default_page() -> usize {
     1
 }
@@ -33,69 +60,194 @@ fn default_limit() -> usize {
 // メモ作成エンドポイント
 pub async fn create_memo(
     service: Data<MemoService>,
+    user: AuthenticatedUser,
     payload: Json<CreateMemoDto>,
 ) -> AppResult<HttpResponse> {
-    let user_id = Uuid::parse_str("12345678-1234-1234-1234-123456789012").unwrap();
-    let memo = service.create_memo(payload.into_inner(), user_id).await?;
+    let memo = service.create_memo(payload.into_inner(), &user).await?;
     Ok(HttpResponse::Created().json(memo))
 }
 
 // メモ更新エンドポイント
 pub async fn update_memo(
     service: Data<MemoService>,
+    user: AuthenticatedUser,
     id: Path<Uuid>,
     payload: Json<UpdateMemoDto>,
 ) -> AppResult<HttpResponse> {
-    let user_id = Uuid::parse_str("12345678-1234-1234-1234-123456789012").unwrap();
-    let memo = service.update_memo(id.into_inner(), payload.into_inner(), user_id).await?;
+    let memo = service.update_memo(id.into_inner(), payload.into_inner(), &user).await?;
     Ok(HttpResponse::Ok().json(memo))
 }
 
 // メモ取得エンドポイント
 pub async fn get_memo(
     service: Data<MemoService>,
+    user: AuthenticatedUser,
     id: Path<Uuid>,
 ) -> AppResult<HttpResponse> {
-    let user_id = Uuid::parse_str("12345678-1234-1234-1234-123456789012").unwrap();
-    let memo = service.get_memo(id.into_inner(), user_id).await?;
+    let memo = service.get_memo(id.into_inner(), &user).await?;
     Ok(HttpResponse::Ok().json(memo))
 }
 
-// メモ削除エンドポイント
+// メモ削除エンドポイント（既定ではゴミ箱に移動）
 pub async fn delete_memo(
     service: Data<MemoService>,
+    user: AuthenticatedUser,
     id: Path<Uuid>,
+    params: Query<DeleteParams>,
 ) -> AppResult<HttpResponse> {
-    let user_id = Uuid::parse_str("12345678-1234-1234-1234-123456789012").unwrap();
-    service.delete_memo(id.into_inner(), user_id).await?;
+    service.delete_memo(id.into_inner(), params.permanent, &user).await?;
     Ok(HttpResponse::NoContent().finish())
 }
 
+// メモのフォルダ移動エンドポイント
+pub async fn move_memo(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    payload: Json<MoveMemoDto>,
+) -> AppResult<HttpResponse> {
+    let memo = service.move_memo(id.into_inner(), payload.into_inner(), &user).await?;
+    Ok(HttpResponse::Ok().json(memo))
+}
+
+// メモのフラグ設定エンドポイント
+pub async fn set_memo_flag(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    path: Path<(Uuid, MemoFlag)>,
+) -> AppResult<HttpResponse> {
+    let (id, flag) = path.into_inner();
+    let memo = service.set_flag(id, flag, true, &user).await?;
+    Ok(HttpResponse::Ok().json(memo))
+}
+
+// メモのフラグ解除エンドポイント
+pub async fn clear_memo_flag(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    path: Path<(Uuid, MemoFlag)>,
+) -> AppResult<HttpResponse> {
+    let (id, flag) = path.into_inner();
+    let memo = service.set_flag(id, flag, false, &user).await?;
+    Ok(HttpResponse::Ok().json(memo))
+}
+
+// ゴミ箱のメモ一覧取得エンドポイント
+pub async fn list_trash(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    query: Query<MemoListQuery>,
+) -> AppResult<HttpResponse> {
+    let memos = service.list_trash(&query, &user).await?;
+    Ok(HttpResponse::Ok().json(memos))
+}
+
+// ゴミ箱からの復元エンドポイント
+pub async fn restore_memo(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let memo = service.restore_from_trash(id.into_inner(), &user).await?;
+    Ok(HttpResponse::Ok().json(memo))
+}
+
+// メモのバージョン履歴取得エンドポイント
+pub async fn list_memo_versions(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let versions = service.list_versions(id.into_inner(), &user).await?;
+    Ok(HttpResponse::Ok().json(versions))
+}
+
+// メモの特定バージョン取得エンドポイント
+pub async fn get_memo_version(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    path: Path<(Uuid, i32)>,
+) -> AppResult<HttpResponse> {
+    let (id, version) = path.into_inner();
+    let snapshot = service.get_version(id, version, &user).await?;
+    Ok(HttpResponse::Ok().json(snapshot))
+}
+
+// メモのバージョン復元エンドポイント
+pub async fn restore_memo_version(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    path: Path<(Uuid, i32)>,
+) -> AppResult<HttpResponse> {
+    let (id, version) = path.into_inner();
+    let memo = service.restore_version(id, version, &user).await?;
+    Ok(HttpResponse::Ok().json(memo))
+}
+
+// メモのバージョン間の差分取得エンドポイント
+pub async fn diff_memo_versions(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+    params: Query<DiffParams>,
+) -> AppResult<HttpResponse> {
+    let id = id.into_inner();
+    match params.format {
+        DiffFormat::Json => {
+            let diff = service.diff_versions(id, params.from, params.to, &user).await?;
+            Ok(HttpResponse::Ok().json(diff))
+        }
+        DiffFormat::Text => {
+            let diff = service
+                .unified_diff_versions(id, params.from, params.to, &user)
+                .await?;
+            Ok(HttpResponse::Ok()
+                .content_type("text/x-diff; charset=utf-8")
+                .body(diff))
+        }
+    }
+}
+
 // ユーザーのメモ一覧取得エンドポイント
 pub async fn list_memos(
     service: Data<MemoService>,
+    user: AuthenticatedUser,
+    query: Query<MemoListQuery>,
+    filter: Query<MemoListFilter>,
 ) -> AppResult<HttpResponse> {
-    let user_id = Uuid::parse_str("12345678-1234-1234-1234-123456789012").unwrap();
-    let memos = service.get_user_memos(user_id).await?;
+    let memos = service.list_memos(&query, &filter, &user).await?;
     Ok(HttpResponse::Ok().json(memos))
 }
 
 // メモ検索エンドポイント
 pub async fn search_memos(
     service: Data<MemoService>,
-    query_params: Query<SearchParams>,
+    user: AuthenticatedUser,
+    query: Query<SearchMemosQuery>,
 ) -> AppResult<HttpResponse> {
-    let user_id = Uuid::parse_str("12345678-1234-1234-1234-123456789012").unwrap();
-    let result = service
-        .search_memos(
-            &query_params.query.clone().unwrap_or_default(),
-            query_params.tag.clone(),
-            user_id,
-        )
-        .await?;
+    let result = service.search_memos(&query, &user).await?;
     Ok(HttpResponse::Ok().json(result))
 }
 
+// 検索語の補完候補エンドポイント
+pub async fn suggest(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+    query: Query<SuggestQuery>,
+) -> AppResult<HttpResponse> {
+    let suggestions = service.suggest(&query, &user).await?;
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+// 検索履歴の削除エンドポイント
+pub async fn clear_search_history(
+    service: Data<MemoService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    service.clear_search_history(&user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
 // ヘルスチェックエンドポイント
 pub async fn health_check() -> HttpResponse {
     HttpResponse::Ok().json(serde_json::json!({
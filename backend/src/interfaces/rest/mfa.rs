@@ -0,0 +1,62 @@
+use actix_web::{
+    web::{Data, Json},
+    HttpRequest, HttpResponse,
+};
+use crate::{
+    application::auth::AuthenticatedUser,
+    application::mfa::{
+        dto::{DisableMfaDto, MfaCodeDto, MfaLoginDto},
+        service::MfaService,
+    },
+    error::AppResult,
+    interfaces::auth::client_info,
+};
+
+// 第2要素によるログイン完了エンドポイント
+pub async fn login_mfa(
+    service: Data<MfaService>,
+    req: HttpRequest,
+    payload: Json<MfaLoginDto>,
+) -> AppResult<HttpResponse> {
+    let tokens = service.complete_login(payload.into_inner(), client_info(&req)).await?;
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+// TOTP登録開始エンドポイント
+pub async fn enroll(
+    service: Data<MfaService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let enrollment = service.enroll(&user).await?;
+    Ok(HttpResponse::Ok().json(enrollment))
+}
+
+// TOTP登録確認エンドポイント
+pub async fn confirm(
+    service: Data<MfaService>,
+    user: AuthenticatedUser,
+    payload: Json<MfaCodeDto>,
+) -> AppResult<HttpResponse> {
+    let codes = service.confirm(&user, payload.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(codes))
+}
+
+// リカバリーコード再発行エンドポイント
+pub async fn regenerate_recovery_codes(
+    service: Data<MfaService>,
+    user: AuthenticatedUser,
+    payload: Json<MfaCodeDto>,
+) -> AppResult<HttpResponse> {
+    let codes = service.regenerate_recovery_codes(&user, payload.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(codes))
+}
+
+// MFA無効化エンドポイント
+pub async fn disable(
+    service: Data<MfaService>,
+    user: AuthenticatedUser,
+    payload: Json<DisableMfaDto>,
+) -> AppResult<HttpResponse> {
+    service.disable(&user, payload.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
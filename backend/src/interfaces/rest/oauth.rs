@@ -0,0 +1,66 @@
+use actix_web::{
+    cookie::{time::Duration, Cookie, SameSite},
+    web::{Data, Path, Query},
+    HttpRequest, HttpResponse,
+};
+use crate::{
+    application::oauth::{dto::OAuthCallbackParams, service::OAuthService},
+    error::AppResult,
+    infrastructure::auth::oauth::PENDING_AUTHORIZATION_TTL,
+    interfaces::auth::client_info,
+};
+
+/// 認可を開始したブラウザを識別するクッキー
+const BROWSER_BINDING_COOKIE: &str = "oauth_binding";
+const BROWSER_BINDING_COOKIE_PATH: &str = "/api/v1/auth/oauth";
+
+fn browser_binding_cookie(value: String) -> Cookie<'static> {
+    Cookie::build(BROWSER_BINDING_COOKIE, value)
+        .path(BROWSER_BINDING_COOKIE_PATH)
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::Lax)
+        .max_age(Duration::seconds(PENDING_AUTHORIZATION_TTL.as_secs() as i64))
+        .finish()
+}
+
+// 有効なプロバイダー一覧エンドポイント
+pub async fn list_providers(service: Data<OAuthService>) -> HttpResponse {
+    HttpResponse::Ok().json(service.providers())
+}
+
+// 認可URL発行エンドポイント
+pub async fn authorize(
+    service: Data<OAuthService>,
+    provider: Path<String>,
+) -> AppResult<HttpResponse> {
+    let (response, browser_binding) = service.authorize(&provider.into_inner()).await?;
+    Ok(HttpResponse::Ok()
+        .cookie(browser_binding_cookie(browser_binding))
+        .json(response))
+}
+
+// 認可コールバックエンドポイント
+pub async fn callback(
+    service: Data<OAuthService>,
+    req: HttpRequest,
+    provider: Path<String>,
+    params: Query<OAuthCallbackParams>,
+) -> AppResult<HttpResponse> {
+    let browser_binding = req
+        .cookie(BROWSER_BINDING_COOKIE)
+        .map(|cookie| cookie.value().to_string());
+    let tokens = service
+        .callback(
+            &provider.into_inner(),
+            params.into_inner(),
+            browser_binding.as_deref(),
+            client_info(&req),
+        )
+        .await?;
+
+    // クッキーは一度しか使わない
+    let mut removal = browser_binding_cookie(String::new());
+    removal.make_removal();
+    Ok(HttpResponse::Ok().cookie(removal).json(tokens))
+}
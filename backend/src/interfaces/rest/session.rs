@@ -0,0 +1,49 @@
+use actix_web::{
+    web::{Data, Json, Path},
+    HttpRequest, HttpResponse,
+};
+use uuid::Uuid;
+use crate::{
+    application::auth::AuthenticatedUser,
+    application::session::{dto::RefreshTokenDto, service::SessionService},
+    error::AppResult,
+    interfaces::auth::client_info,
+};
+
+// トークン再発行エンドポイント
+pub async fn refresh(
+    service: Data<SessionService>,
+    req: HttpRequest,
+    payload: Json<RefreshTokenDto>,
+) -> AppResult<HttpResponse> {
+    let tokens = service.refresh(payload.into_inner(), client_info(&req)).await?;
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+// セッション一覧取得エンドポイント
+pub async fn list_sessions(
+    service: Data<SessionService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let sessions = service.list_sessions(&user).await?;
+    Ok(HttpResponse::Ok().json(sessions))
+}
+
+// セッション失効エンドポイント
+pub async fn revoke_session(
+    service: Data<SessionService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    service.revoke_session(&user, id.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// 全端末ログアウトエンドポイント
+pub async fn revoke_all_sessions(
+    service: Data<SessionService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    service.logout_everywhere(&user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -0,0 +1,55 @@
+use actix_web::{
+    web::{Data, Json, Path},
+    HttpResponse,
+};
+use uuid::Uuid;
+use crate::{
+    application::auth::AuthenticatedUser,
+    application::tag::{
+        dto::{MergeTagsDto, RenameTagDto},
+        service::TagService,
+    },
+    error::AppResult,
+};
+
+// タグ一覧取得エンドポイント
+pub async fn list_tags(
+    service: Data<TagService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let tags = service.list_tags(&user).await?;
+    Ok(HttpResponse::Ok().json(tags))
+}
+
+// タグ名変更エンドポイント
+pub async fn rename_tag(
+    service: Data<TagService>,
+    user: AuthenticatedUser,
+    name: Path<String>,
+    payload: Json<RenameTagDto>,
+) -> AppResult<HttpResponse> {
+    let job = service
+        .rename_tag(name.into_inner(), payload.into_inner(), &user)
+        .await?;
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+// タグ統合エンドポイント
+pub async fn merge_tags(
+    service: Data<TagService>,
+    user: AuthenticatedUser,
+    payload: Json<MergeTagsDto>,
+) -> AppResult<HttpResponse> {
+    let job = service.merge_tags(payload.into_inner(), &user).await?;
+    Ok(HttpResponse::Accepted().json(job))
+}
+
+// タグ書き換えジョブの進捗取得エンドポイント
+pub async fn get_tag_job(
+    service: Data<TagService>,
+    user: AuthenticatedUser,
+    id: Path<Uuid>,
+) -> AppResult<HttpResponse> {
+    let job = service.get_job(id.into_inner(), &user).await?;
+    Ok(HttpResponse::Ok().json(job))
+}
@@ -0,0 +1,61 @@
+use actix_web::{
+    web::{Data, Json},
+    HttpRequest, HttpResponse,
+};
+use crate::{
+    application::auth::AuthenticatedUser,
+    application::user::{
+        dto::{CreateUserDto, LoginDto, UpdatePasswordDto, UpdateUserDto},
+        service::UserService,
+    },
+    error::AppResult,
+    interfaces::auth::client_info,
+};
+
+// ユーザー登録エンドポイント
+pub async fn register(
+    service: Data<UserService>,
+    payload: Json<CreateUserDto>,
+) -> AppResult<HttpResponse> {
+    let user = service.register(payload.into_inner()).await?;
+    Ok(HttpResponse::Created().json(user))
+}
+
+// ログインエンドポイント
+pub async fn login(
+    service: Data<UserService>,
+    req: HttpRequest,
+    payload: Json<LoginDto>,
+) -> AppResult<HttpResponse> {
+    let tokens = service.login(payload.into_inner(), client_info(&req)).await?;
+    Ok(HttpResponse::Ok().json(tokens))
+}
+
+// プロフィール取得エンドポイント
+pub async fn get_me(
+    service: Data<UserService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    let profile = service.get_profile(&user).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+// プロフィール更新エンドポイント
+pub async fn update_me(
+    service: Data<UserService>,
+    user: AuthenticatedUser,
+    payload: Json<UpdateUserDto>,
+) -> AppResult<HttpResponse> {
+    let profile = service.update_profile(&user, payload.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+// パスワード変更エンドポイント
+pub async fn update_password(
+    service: Data<UserService>,
+    user: AuthenticatedUser,
+    payload: Json<UpdatePasswordDto>,
+) -> AppResult<HttpResponse> {
+    service.update_password(&user, payload.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
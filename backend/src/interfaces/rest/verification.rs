@@ -0,0 +1,52 @@
+use actix_web::{
+    web::{Data, Json},
+    HttpRequest, HttpResponse,
+};
+use crate::{
+    application::auth::AuthenticatedUser,
+    application::verification::{
+        dto::{ForgotPasswordDto, ResetPasswordDto, VerifyEmailDto},
+        service::VerificationService,
+    },
+    error::AppResult,
+    interfaces::auth::client_info,
+};
+
+// パスワード再設定メールの送信エンドポイント
+pub async fn forgot_password(
+    service: Data<VerificationService>,
+    req: HttpRequest,
+    payload: Json<ForgotPasswordDto>,
+) -> AppResult<HttpResponse> {
+    service
+        .request_password_reset(payload.into_inner(), client_info(&req))
+        .await?;
+    Ok(HttpResponse::Accepted().finish())
+}
+
+// パスワード再設定エンドポイント
+pub async fn reset_password(
+    service: Data<VerificationService>,
+    payload: Json<ResetPasswordDto>,
+) -> AppResult<HttpResponse> {
+    service.reset_password(payload.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// メールアドレス確認エンドポイント
+pub async fn verify_email(
+    service: Data<VerificationService>,
+    payload: Json<VerifyEmailDto>,
+) -> AppResult<HttpResponse> {
+    let user = service.verify_email(payload.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+// 確認メール再送エンドポイント
+pub async fn resend_verification_email(
+    service: Data<VerificationService>,
+    user: AuthenticatedUser,
+) -> AppResult<HttpResponse> {
+    service.resend_verification_email(&user).await?;
+    Ok(HttpResponse::Accepted().finish())
+}
@@ -1,5 +1,5 @@
 use actix_web::web;
-use crate::interfaces::rest::memo;
+use crate::interfaces::rest::{account, admin, api_key, folder, memo, mfa, oauth, session, tag, user, verification};
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg
@@ -10,9 +10,84 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                         .route("", web::post().to(memo::create_memo))
                         .route("", web::get().to(memo::list_memos))
                         .route("/search", web::get().to(memo::search_memos))
+                        .route("/search/history", web::delete().to(memo::clear_search_history))
+                        .route("/suggest", web::get().to(memo::suggest))
+                        .route("/trash", web::get().to(memo::list_trash))
                         .route("/{id}", web::get().to(memo::get_memo))
                         .route("/{id}", web::patch().to(memo::update_memo))
-                        .route("/{id}", web::delete().to(memo::delete_memo)),
+                        .route("/{id}", web::delete().to(memo::delete_memo))
+                        .route("/{id}/restore", web::post().to(memo::restore_memo))
+                        .route("/{id}/move", web::post().to(memo::move_memo))
+                        .route("/{id}/flags/{flag}", web::put().to(memo::set_memo_flag))
+                        .route("/{id}/flags/{flag}", web::delete().to(memo::clear_memo_flag))
+                        .route("/{id}/diff", web::get().to(memo::diff_memo_versions))
+                        .route("/{id}/versions", web::get().to(memo::list_memo_versions))
+                        .route("/{id}/versions/{version}", web::get().to(memo::get_memo_version))
+                        .route("/{id}/versions/{version}/restore", web::post().to(memo::restore_memo_version)),
+                )
+                .service(
+                    web::scope("/folders")
+                        .route("", web::post().to(folder::create_folder))
+                        .route("", web::get().to(folder::list_folders))
+                        .route("/{id}", web::get().to(folder::get_folder))
+                        .route("/{id}", web::patch().to(folder::rename_folder))
+                        .route("/{id}", web::delete().to(folder::delete_folder))
+                        .route("/{id}/move", web::post().to(folder::move_folder))
+                        .route("/{id}/memos", web::get().to(folder::list_folder_memos)),
+                )
+                .service(
+                    web::scope("/tags")
+                        .route("", web::get().to(tag::list_tags))
+                        .route("/merge", web::post().to(tag::merge_tags))
+                        .route("/jobs/{id}", web::get().to(tag::get_tag_job))
+                        .route("/{name}/rename", web::post().to(tag::rename_tag)),
+                )
+                .service(
+                    web::scope("/users")
+                        .route("", web::post().to(user::register))
+                        .route("/login", web::post().to(user::login))
+                        .route("/login/mfa", web::post().to(mfa::login_mfa))
+                        .route("/password/forgot", web::post().to(verification::forgot_password))
+                        .route("/password/reset", web::post().to(verification::reset_password))
+                        .route("/email/verify", web::post().to(verification::verify_email))
+                        .route("/me", web::get().to(user::get_me))
+                        .route("/me", web::patch().to(user::update_me))
+                        .route("/me", web::delete().to(account::delete_account))
+                        .route("/me/export", web::get().to(account::export_data))
+                        .route("/me/password", web::put().to(user::update_password))
+                        .route("/me/email/verification", web::post().to(verification::resend_verification_email))
+                        .route("/me/mfa/enroll", web::post().to(mfa::enroll))
+                        .route("/me/mfa/confirm", web::post().to(mfa::confirm))
+                        .route("/me/mfa/recovery-codes", web::post().to(mfa::regenerate_recovery_codes))
+                        .route("/me/mfa/disable", web::post().to(mfa::disable)),
+                )
+                .service(
+                    web::scope("/sessions")
+                        .route("", web::get().to(session::list_sessions))
+                        .route("", web::delete().to(session::revoke_all_sessions))
+                        .route("/refresh", web::post().to(session::refresh))
+                        .route("/{id}", web::delete().to(session::revoke_session)),
+                )
+                .service(
+                    web::scope("/auth/oauth")
+                        .route("/providers", web::get().to(oauth::list_providers))
+                        .route("/{provider}/authorize", web::get().to(oauth::authorize))
+                        .route("/{provider}/callback", web::get().to(oauth::callback)),
+                )
+                .service(
+                    web::scope("/api-keys")
+                        .route("", web::post().to(api_key::create_api_key))
+                        .route("", web::get().to(api_key::list_api_keys))
+                        .route("/{id}", web::delete().to(api_key::revoke_api_key)),
+                )
+                .service(
+                    web::scope("/admin")
+                        .route("/users/{id}", web::get().to(admin::get_user))
+                        .route("/users/{id}/role", web::put().to(admin::change_role))
+                        .route("/users/{id}/mfa/reset", web::post().to(admin::reset_mfa))
+                        .route("/users/{id}/memos", web::get().to(admin::list_user_memos))
+                        .route("/search/reindex", web::post().to(admin::start_reindex))
+                        .route("/search/reindex", web::get().to(admin::get_reindex)),
                 )
                 .route("/health", web::get().to(memo::health_check)),
         );
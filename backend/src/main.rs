@@ -1,5 +1,14 @@
 use env_logger::Env;
-use memo_app_backend::startup::Application;
+use memo_app_backend::{
+    application::{admin::bootstrap::AdminConfig, memo::trash::TrashConfig},
+    infrastructure::{
+        auth::{jwt::JwtConfig, oauth::OAuthConfig},
+        mail::MailConfig,
+        persistence::analysis::SearchConfig,
+    },
+    interfaces::auth::ProxyConfig,
+    startup::Application,
+};
 
 mod application;
 mod domain;
@@ -24,12 +33,26 @@ async fn main() -> std::io::Result<()> {
         .unwrap_or_else(|_| "8080".to_string())
         .parse::<u16>()
         .expect("Failed to parse PORT");
+    let jwt_config = JwtConfig::from_env().expect("Failed to load JWT configuration");
+    let oauth_config = OAuthConfig::from_env().await;
+    let mail_config = MailConfig::from_env().expect("Failed to load mail configuration");
+    let trash_config = TrashConfig::from_env().expect("Failed to load trash configuration");
+    let search_config = SearchConfig::from_env().expect("Failed to load search configuration");
+    let proxy_config = ProxyConfig::from_env().expect("Failed to load proxy configuration");
+    let admin_config = AdminConfig::from_env().expect("Failed to load admin configuration");
 
     // アプリケーションの構築と起動
     let application = Application::build(
         scylla_uri,
         redis_uri,
         elasticsearch_uri,
+        jwt_config,
+        oauth_config,
+        mail_config,
+        trash_config,
+        search_config,
+        proxy_config,
+        admin_config,
         port,
     )
     .await?;
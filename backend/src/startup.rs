@@ -2,16 +2,54 @@
 use std::sync::Arc;
 use actix_web::{web::Data, App, HttpServer, middleware};
 use crate::{
-    application::memo::service::MemoService,
+    application::{
+        account::{deletion::AccountDeletionJob, service::AccountService},
+        admin::{
+            bootstrap::{AdminBootstrap, AdminConfig},
+            reindex::SearchReindexer,
+            service::AdminService,
+        },
+        api_key::service::ApiKeyService,
+        auth::login_guard::LoginGuard,
+        folder::service::FolderService,
+        memo::{
+            history::SearchHistory,
+            service::MemoService,
+            trash::{MemoTrash, TrashConfig},
+        },
+        mfa::service::MfaService,
+        oauth::service::OAuthService,
+        session::service::SessionService,
+        tag::{job::TagJobQueue, service::TagService},
+        user::service::UserService,
+        verification::service::VerificationService,
+    },
     infrastructure::{
+        auth::{
+            action_token::ActionTokenService,
+            jwt::{JwtConfig, JwtService},
+            mfa::MfaChallengeStore,
+            oauth::{OAuthClient, OAuthConfig},
+            throttle::LoginThrottle,
+        },
+        mail::{outbox::MailOutbox, MailConfig},
         persistence::{
+            analysis::SearchConfig,
             scylla::ScyllaDB,
             redis::RedisCache,
             elasticsearch::ElasticsearchClient,
         },
-        repositories::memo::MemoRepositoryImpl,
+        repositories::{
+            api_key::ApiKeyRepositoryImpl,
+            audit::AuditRepositoryImpl,
+            folder::FolderRepositoryImpl,
+            identity::OAuthIdentityRepositoryImpl,
+            memo::MemoRepositoryImpl,
+            session::SessionRepositoryImpl,
+            user::UserRepositoryImpl,
+        },
     },
-    interfaces::routes::configure_routes,
+    interfaces::{auth::ProxyConfig, routes::configure_routes},
 };
 use std::io;
 
@@ -25,6 +63,13 @@ impl Application {
         scylla_uri: String,
         redis_uri: String,
         elasticsearch_uri: String,
+        jwt_config: JwtConfig,
+        oauth_config: OAuthConfig,
+        mail_config: MailConfig,
+        trash_config: TrashConfig,
+        search_config: SearchConfig,
+        proxy_config: ProxyConfig,
+        admin_config: AdminConfig,
         port: u16,
     ) -> io::Result<Self> {
         // Scylla 接続
@@ -40,7 +85,7 @@ impl Application {
         );
         // Elasticsearch 接続
         let elasticsearch = Arc::new(
-            ElasticsearchClient::new(&elasticsearch_uri)
+            ElasticsearchClient::new(&elasticsearch_uri, &search_config)
                 .await
                 .expect("Failed to connect to Elasticsearch")
         );
@@ -52,10 +97,139 @@ impl Application {
                 redis.clone(),
                 elasticsearch.clone(),
             )
+            .await
+            .expect("Failed to build memo repository")
         );
 
+        let user_repository = Arc::new(UserRepositoryImpl::new(scylla.clone()));
+        let session_repository = Arc::new(SessionRepositoryImpl::new(redis.clone()));
+        let identity_repository = Arc::new(OAuthIdentityRepositoryImpl::new(scylla.clone()));
+        let api_key_repository = Arc::new(ApiKeyRepositoryImpl::new(scylla.clone()));
+        let audit_repository = Arc::new(AuditRepositoryImpl::new(scylla.clone()));
+        let folder_repository = Arc::new(FolderRepositoryImpl::new(scylla.clone()));
+
+        // 認証
+        let jwt_service = Arc::new(JwtService::new(jwt_config));
+        let oauth_client = Arc::new(OAuthClient::new(oauth_config, redis.clone()));
+        let mfa_challenge_store = Arc::new(MfaChallengeStore::new(redis.clone()));
+        let login_guard = Arc::new(LoginGuard::new(
+            Arc::new(LoginThrottle::new(redis.clone())),
+            audit_repository,
+        ));
+        let action_token_service = Arc::new(ActionTokenService::new(jwt_service.clone(), redis.clone()));
+
+        // メール送信（失敗時の再送はバックグラウンドで行う）
+        let mailer = mail_config
+            .build_mailer()
+            .expect("Failed to configure mailer");
+        let mail_outbox = Arc::new(MailOutbox::new(redis.clone(), mailer));
+        actix_web::rt::spawn(mail_outbox.clone().run());
+
+        let search_history = Arc::new(SearchHistory::new(redis.clone()));
+
+        // アカウント削除（中断した削除もここで再開される）
+        let account_deletion_job = Arc::new(AccountDeletionJob::new(
+            redis.clone(),
+            user_repository.clone(),
+            memo_repository.clone(),
+            folder_repository.clone(),
+            api_key_repository.clone(),
+            identity_repository.clone(),
+            session_repository.clone(),
+            search_history.clone(),
+        ));
+        actix_web::rt::spawn(account_deletion_job.clone().run());
+
+        // ゴミ箱（保持期限を過ぎたメモはバックグラウンドで完全に削除）
+        let memo_trash = Arc::new(MemoTrash::new(
+            redis.clone(),
+            scylla.clone(),
+            memo_repository.clone(),
+            trash_config,
+        ));
+        actix_web::rt::spawn(memo_trash.clone().run());
+
+        // タグの名前変更・統合（中断したジョブもここで再開される）
+        let tag_job_queue = Arc::new(TagJobQueue::new(redis.clone(), memo_repository.clone()));
+        actix_web::rt::spawn(tag_job_queue.clone().run());
+
+        // 検索インデックスの再インデックス（定義が古ければ起動時に開始し、中断したジョブもここで再開される）
+        let search_reindexer = Arc::new(SearchReindexer::new(
+            scylla.clone(),
+            redis.clone(),
+            elasticsearch.clone(),
+        ));
+        search_reindexer
+            .enqueue_if_outdated()
+            .await
+            .expect("Failed to check search index");
+        actix_web::rt::spawn(search_reindexer.clone().run());
+
         // サービス
-        let memo_service = Data::new(MemoService::new(memo_repository));
+        let memo_service = Data::new(MemoService::new(
+            memo_repository.clone(),
+            folder_repository.clone(),
+            memo_trash.clone(),
+            search_history,
+        ));
+        let folder_service = Data::new(FolderService::new(
+            folder_repository.clone(),
+            memo_repository.clone(),
+            memo_trash,
+        ));
+        let tag_service = Data::new(TagService::new(memo_repository.clone(), tag_job_queue));
+        let session_service = Arc::new(SessionService::new(session_repository, jwt_service.clone()));
+        let mfa_service = Arc::new(MfaService::new(
+            user_repository.clone(),
+            session_service.clone(),
+            mfa_challenge_store,
+            login_guard.clone(),
+        ));
+        let verification_service = Arc::new(VerificationService::new(
+            user_repository.clone(),
+            session_service.clone(),
+            action_token_service,
+            mail_outbox,
+            login_guard.clone(),
+            mail_config.public_url.clone(),
+        ));
+        let admin_bootstrap = Arc::new(AdminBootstrap::new(user_repository.clone(), admin_config));
+        let user_service = Data::new(UserService::new(
+            user_repository.clone(),
+            session_service.clone(),
+            mfa_service.clone(),
+            login_guard,
+            verification_service.clone(),
+            admin_bootstrap.clone(),
+        ));
+        let oauth_service = Data::new(OAuthService::new(
+            oauth_client,
+            user_repository.clone(),
+            identity_repository.clone(),
+            mfa_service.clone(),
+            admin_bootstrap,
+        ));
+        let account_service = Data::new(AccountService::new(
+            user_repository.clone(),
+            memo_repository,
+            folder_repository,
+            api_key_repository.clone(),
+            identity_repository,
+            session_service.clone(),
+            account_deletion_job,
+        ));
+        let admin_service = Data::new(AdminService::new(
+            user_repository.clone(),
+            session_service.clone(),
+            mfa_service.clone(),
+            search_reindexer,
+        ));
+        let mfa_service = Data::from(mfa_service);
+        let verification_service = Data::from(verification_service);
+        let api_key_service = Data::new(ApiKeyService::new(api_key_repository, user_repository));
+        let session_service = Data::from(session_service);
+        let jwt_service = Data::from(jwt_service);
+        let proxy_config = Data::new(proxy_config);
 
         // Actix Webサーバー起動
         let server = HttpServer::new(move || {
@@ -63,6 +237,18 @@ impl Application {
                 .wrap(middleware::Logger::default())
                 .wrap(middleware::Compress::default())
                 .app_data(memo_service.clone())
+                .app_data(folder_service.clone())
+                .app_data(tag_service.clone())
+                .app_data(user_service.clone())
+                .app_data(session_service.clone())
+                .app_data(oauth_service.clone())
+                .app_data(mfa_service.clone())
+                .app_data(api_key_service.clone())
+                .app_data(verification_service.clone())
+                .app_data(account_service.clone())
+                .app_data(admin_service.clone())
+                .app_data(jwt_service.clone())
+                .app_data(proxy_config.clone())
                 .configure(configure_routes)
         })
         .bind(("0.0.0.0", port))?
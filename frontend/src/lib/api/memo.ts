@@ -8,7 +8,8 @@ export async function fetchMemos(): Promise<Memo[]> {
   if (!response.ok) {
     throw new Error('Failed to fetch memos');
   }
-  return response.json();
+  const page: { items: Memo[]; next_cursor: string | null } = await response.json();
+  return page.items;
 }
 
 export async function fetchMemoById(id: string): Promise<Memo> {
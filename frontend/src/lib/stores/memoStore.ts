@@ -29,7 +29,7 @@ function createMemoStore() {
         if (!response.ok) {
           throw new Error('Failed to fetch memos');
         }
-        const memos = await response.json();
+        const { items: memos } = await response.json();
         
         update(state => ({
           ...state,
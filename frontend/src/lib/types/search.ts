@@ -9,6 +9,6 @@ export interface SearchParams {
   export interface SearchResult<T> {
     items: T[];
     total: number;
-    page: number;
+    page: number | null;
     totalPages: number;
   }
\ No newline at end of file